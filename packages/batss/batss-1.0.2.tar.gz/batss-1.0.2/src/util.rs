@@ -2,7 +2,7 @@ use num_bigint::BigInt;
 use rand::rngs::SmallRng;
 use rand::Rng;
 
-use rand_distr::{Distribution, Hypergeometric, StandardUniform};
+use rand_distr::Hypergeometric;
 // use rug::Float;
 
 #[allow(unused_imports)]
@@ -39,6 +39,10 @@ pub fn multinomial_sample(n: u64, pix: &Vec<f64>, result: &mut [u64], rng: &mut
     multinomial_sample_manual(n, pix, result, rng);
 }
 
+pub fn poisson_sample(lambda: f64, rng: &mut SmallRng) -> u64 {
+    poisson_sample_manual(lambda, rng)
+}
+
 /////////////////////////////////////////////////////////////////////////////////
 // ln_gamma
 
@@ -296,18 +300,75 @@ const PRECOMPUTED_SMALL_RATIONAL_LN_GAMMAS: [[f128; MAX_PRECOMPUTED_DENOMINATOR]
     ],
 ];
 
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+lazy_static::lazy_static! {
+    // The runtime path below is about MIN_LARGE_LN_GAMMA_INPUT ln_f128 calls, so cache results for
+    // (num, den) pairs beyond the precomputed table. CRN models reuse a handful of
+    // generativity values for an entire run, so this stays small without needing
+    // real LRU eviction.
+    static ref SMALL_RATIONAL_RUNTIME_CACHE: Mutex<HashMap<(usize, usize), f128>> =
+        Mutex::new(HashMap::new());
+}
+
 pub fn ln_gamma_small_rational(num: usize, den: usize) -> f128 {
     // flame::start("small_rational");
-    assert!(
-        den <= MAX_PRECOMPUTED_DENOMINATOR,
-        "For now, we're assuming generativity is less than 10."
-    );
     assert!(
         num <= den,
         "ln_gamma_small_rational should only be called on values between 0 and 1."
     );
+    if den <= MAX_PRECOMPUTED_DENOMINATOR {
+        // flame::end("small_rational");
+        return PRECOMPUTED_SMALL_RATIONAL_LN_GAMMAS[num - 1][den - 1];
+    }
+    if let Some(&cached) = SMALL_RATIONAL_RUNTIME_CACHE
+        .lock()
+        .unwrap()
+        .get(&(num, den))
+    {
+        // flame::end("small_rational");
+        return cached;
+    }
+    let value = ln_gamma_small_rational_runtime(num, den);
+    SMALL_RATIONAL_RUNTIME_CACHE
+        .lock()
+        .unwrap()
+        .insert((num, den), value);
     // flame::end("small_rational");
-    PRECOMPUTED_SMALL_RATIONAL_LN_GAMMAS[num - 1][den - 1]
+    value
+}
+
+// Shifts x = num/den up past MIN_LARGE_LN_GAMMA_INPUT via the recurrence
+// ln_gamma(x) = ln_gamma(x+1) - ln(x), accumulating the -ln_f128(x+i) terms along
+// the way, then hands the now-accurate shifted argument to
+// ln_gamma_manual_high_precision_large.
+fn ln_gamma_small_rational_runtime(num: usize, den: usize) -> f128 {
+    let mut shifted: f128 = num as f128 / den as f128;
+    let mut shift_sum: f128 = 0.0;
+    // Kahan summation: with ~MIN_LARGE_LN_GAMMA_INPUT terms to accumulate, naive
+    // summation's rounding error compounds enough to matter at this precision.
+    let mut compensation: f128 = 0.0;
+    while shifted <= MIN_LARGE_LN_GAMMA_INPUT {
+        let term = -ln_f128_any_positive(shifted);
+        let y = term - compensation;
+        let t = shift_sum + y;
+        compensation = (t - shift_sum) - y;
+        shift_sum = t;
+        shifted += 1.0;
+    }
+    shift_sum + ln_gamma_manual_high_precision_large(shifted)
+}
+
+// ln_f128 only accepts arguments >= 1.0 (true of every shifted value except
+// possibly the very first, since num/den <= 1.0); fall back to -ln_f128(1/x)
+// for the one sub-1.0 case so the recurrence stays at quad precision throughout.
+fn ln_f128_any_positive(x: f128) -> f128 {
+    if x >= 1.0 {
+        ln_f128(x)
+    } else {
+        -ln_f128(1.0 / x)
+    }
 }
 
 // pub fn ln_gamma_small_rational_rug(prec: u32, num: usize, den: usize) -> Float {
@@ -422,6 +483,50 @@ pub fn ln_f128(x: f128) -> f128 {
     out
 }
 
+const EXP_TAYLOR_TERMS: u32 = 34;
+
+// f128 exp, the inverse of ln_f128. Range-reduce x = k*LN2 + r with k = round(x /
+// LN2) so |r| <= LN2/2, evaluate exp(r) with a plain Taylor series (at this
+// range 34 terms comfortably clears quad precision), then rescale by 2^k via
+// the same to_bits exponent-field trick ln_f128 uses in reverse.
+pub fn exp_f128(x: f128) -> f128 {
+    let k = (x / LN2).round();
+    let r = x - k * LN2;
+
+    let mut term: f128 = 1.0;
+    let mut sum: f128 = 1.0;
+    for n in 1..=EXP_TAYLOR_TERMS {
+        term *= r / n as f128;
+        sum += term;
+    }
+
+    scale_f128_by_pow2(sum, k as i64)
+}
+
+// Multiplies `x` by 2^k by adding k directly to its biased exponent field,
+// the inverse of the exponent extraction ln_f128 and f128_to_decimal perform.
+fn scale_f128_by_pow2(x: f128, k: i64) -> f128 {
+    if k == 0 {
+        return x;
+    }
+    let bits = x.to_bits();
+    let sign_bit = bits & (1u128 << 127);
+    let mantissa = bits & 0x0000_FFFF_FFFF_FFFF_FFFF_FFFF_FFFF_FFFF;
+    let exponent = ((bits >> 112) & 0x7FFF) as i64 + k;
+    assert!(
+        exponent > 0 && exponent < 0x7FFF,
+        "exp_f128 result exponent out of range"
+    );
+    let new_bits = sign_bit | ((exponent as u128) << 112) | mantissa;
+    f128::from_bits(new_bits)
+}
+
+// pow via exp(e * ln(b)), both at quad precision, so ln_gamma_manual_high_precision's
+// results can be turned back into probabilities without dropping back to f64.
+pub fn pow_f128(base: f128, exp: f128) -> f128 {
+    exp_f128(exp * ln_f128(base))
+}
+
 const ALGMCS: [f64; 15] = [
     0.1666389480451863247205729650822,
     -0.1384948176067563840732986059135e-4,
@@ -567,43 +672,167 @@ pub fn hypergeometric_sample_manual(
     if draws >= 10 && draws <= popsize - 10 {
         hypergeometric_sample_hrua(popsize, good, draws, rng)
     } else {
-        hypergeometric_sample_naive(popsize, good, draws, rng)
-    }
-}
-
-fn hypergeometric_sample_naive(popsize: u64, good: u64, draws: u64, rng: &mut SmallRng) -> u64 {
-    // This is the simpler naive implementation for small samples.
-    // https://github.com/numpy/numpy/blob/b76bb2329032809229e8a531ba3179c34b0a3f0a/numpy/random/src/distributions/random_hypergeometric.c#L46
-    // variable name translations from numpy source code:
-    //   total --> popsize
-    //   good  --> good
-    //   bad   --> popsize - good
-    //   draws --> sample
-    let mut remaining_total = popsize;
-    let mut remaining_good = good;
-    let mut computed_sample = draws;
-    if computed_sample > popsize / 2 {
-        computed_sample = remaining_total - computed_sample;
-    }
-    while computed_sample > 0 && remaining_good > 0 && remaining_total > remaining_good {
-        // random_range(0..=max) returns an integer in
-        // [0, max] *inclusive*, so we decrement remaining_total before
-        // passing it to random_range().
-        remaining_total -= 1;
-        if rng.random_range(0..=remaining_total) < remaining_good {
-            // Selected a "good" one, so decrement remaining_good.
-            remaining_good -= 1;
-        }
-        computed_sample -= 1;
-    }
-    if remaining_total == remaining_good {
-        // Only "good" choices are left.
-        remaining_good -= computed_sample
-    }
-    if draws > popsize / 2 {
-        remaining_good
+        hypergeometric_sample_inversion(popsize, good, draws, rng)
+    }
+}
+
+// exact fallback for parameter ranges hypergeometric_sample_hrua doesn't
+// cover: walks k upward from 0, computing p(k) from p(k-1) via the
+// incremental ratio ((mingoodbad-k+1)*(computed_sample-k+1)) /
+// (k*(maxgoodbad-computed_sample+k)) and accumulating waiting draws against
+// it, the same BINV-style technique as binomial_sample_inversion but adapted
+// to the hypergeometric pmf. Uses the same mingoodbad/maxgoodbad/
+// computed_sample reparameterization as hypergeometric_sample_hrua, which
+// guarantees the walk's support starts at k=0 (computed_sample is capped at
+// popsize/2, which never exceeds maxgoodbad).
+fn hypergeometric_sample_inversion(
+    popsize: u64,
+    good: u64,
+    sample: u64,
+    rng: &mut SmallRng,
+) -> u64 {
+    let bad = popsize - good;
+    let computed_sample = sample.min(popsize - sample);
+    let mingoodbad = good.min(bad);
+    let maxgoodbad = good.max(bad);
+    let bound = computed_sample.min(mingoodbad);
+
+    let log_p0 = ln_factorial(maxgoodbad) + ln_factorial(popsize - computed_sample)
+        - ln_factorial(maxgoodbad - computed_sample)
+        - ln_factorial(popsize);
+    let p0 = log_p0.exp();
+
+    let mut k: u64 = 0;
+    let mut px = p0;
+    let mut u: f64 = high_precision_open01(rng);
+    loop {
+        if u <= px {
+            break;
+        }
+        u -= px;
+        k += 1;
+        if k > bound {
+            k = 0;
+            px = p0;
+            u = high_precision_open01(rng);
+            continue;
+        }
+        px *= ((mingoodbad - k + 1) as f64 * (computed_sample - k + 1) as f64)
+            / (k as f64 * (maxgoodbad - computed_sample + k) as f64);
+    }
+
+    if good > bad {
+        k = computed_sample - k;
+    }
+    if computed_sample < sample {
+        k = good - k;
+    }
+    k
+}
+
+/////////////////////////////////////////////////////////////////////////////////
+// high_precision_open01
+
+// rng.random::<f64>() only produces one of 2^53 evenly spaced values in
+// [0, 1), which under-resolves the acceptance tests deep in the tails of the
+// rejection samplers below: values of u near 0 all collapse onto the same
+// handful of quantized floats. This instead samples the continuous [0, 1)
+// interval and rounds down to the nearest representable f64, including
+// subnormals, so values near zero get the extra low-order precision they
+// deserve. Draw 64-bit words until a nonzero one appears (each all-zero word
+// means 64 more leading zero bits, so the exponent drops by 64), count that
+// word's leading zeros to finish locating the leading one bit, then draw a
+// fresh word to supply the 52 mantissa bits.
+pub fn high_precision_open01(rng: &mut SmallRng) -> f64 {
+    const MANTISSA_BITS: u32 = 52;
+    const MIN_NORMAL_EXPONENT: i64 = -1022;
+    const MIN_EXPONENT: i64 = -1074; // smallest subnormal f64, 2^-1074
+
+    let mut exponent: i64 = -1;
+    loop {
+        let word: u64 = rng.random();
+        if word == 0 {
+            exponent -= 64;
+            if exponent < MIN_EXPONENT {
+                return 0.0;
+            }
+            continue;
+        }
+        exponent -= word.leading_zeros() as i64;
+        break;
+    }
+
+    let mantissa = rng.random::<u64>() >> (64 - MANTISSA_BITS);
+
+    if exponent >= MIN_NORMAL_EXPONENT {
+        let biased_exponent = (exponent + 1023) as u64;
+        f64::from_bits((biased_exponent << 52) | mantissa)
     } else {
-        good - remaining_good
+        // Subnormal: the leading one bit we located sits inside the 52-bit
+        // stored mantissa itself rather than being implicit, at bit position
+        // exponent - MIN_EXPONENT (0 for the smallest subnormal, 51 just
+        // below the smallest normal). Only that many of the freshly drawn
+        // mantissa bits fit below it; the rest are dropped, rounding down.
+        let bit_position = (exponent - MIN_EXPONENT) as u32;
+        let stored = (1u64 << bit_position) | (mantissa >> (MANTISSA_BITS - bit_position));
+        f64::from_bits(stored)
+    }
+}
+
+/////////////////////////////////////////////////////////////////////////////////
+// ClampToInt
+
+// The hypergeometric and binomial rejection samplers below round several
+// floating-point bounds down to an integer (e.g. "16 standard deviations past
+// the mean") and convert a couple of u64 counts to i64. A plain `as` either
+// saturates silently in ways that are easy to lose track of (float -> int) or
+// wraps via bit reinterpretation (u64 -> i64 for n near u64::MAX), so this
+// makes the saturating behavior explicit and named at each call site instead.
+pub trait ClampToInt {
+    fn clamp_to_u64(self) -> u64;
+    fn clamp_to_i64(self) -> i64;
+}
+
+impl ClampToInt for f64 {
+    fn clamp_to_u64(self) -> u64 {
+        if self.is_nan() {
+            0
+        } else {
+            self.clamp(0.0, u64::MAX as f64) as u64
+        }
+    }
+    fn clamp_to_i64(self) -> i64 {
+        if self.is_nan() {
+            0
+        } else {
+            self.clamp(i64::MIN as f64, i64::MAX as f64) as i64
+        }
+    }
+}
+
+impl ClampToInt for f128 {
+    fn clamp_to_u64(self) -> u64 {
+        if self.is_nan() {
+            0
+        } else {
+            self.clamp(0.0, u64::MAX as f128) as u64
+        }
+    }
+    fn clamp_to_i64(self) -> i64 {
+        if self.is_nan() {
+            0
+        } else {
+            self.clamp(i64::MIN as f128, i64::MAX as f128) as i64
+        }
+    }
+}
+
+impl ClampToInt for u64 {
+    fn clamp_to_u64(self) -> u64 {
+        self
+    }
+    fn clamp_to_i64(self) -> i64 {
+        self.min(i64::MAX as u64) as i64
     }
 }
 
@@ -665,12 +894,12 @@ pub fn hypergeometric_sample_hrua(popsize: u64, good: u64, sample: u64, rng: &mu
      *  but there is no documented justification for this value.  A lower value
      *  might work just as well, but I've kept the value 16 here.
      */
-    let b = (computed_sample.min(mingoodbad) + 1).min((a + 16.0 * c).floor() as u64);
+    let b = (computed_sample.min(mingoodbad) + 1).min((a + 16.0 * c).floor().clamp_to_u64());
 
     let mut k: u64;
     loop {
-        let u = rng.random::<f64>();
-        let v = rng.random::<f64>(); // "U star" in Stadlober (1989)
+        let u = high_precision_open01(rng);
+        let v = high_precision_open01(rng); // "U star" in Stadlober (1989)
         let x = a + h * (v - 0.5) / u;
 
         // fast rejection:
@@ -678,7 +907,7 @@ pub fn hypergeometric_sample_hrua(popsize: u64, good: u64, sample: u64, rng: &mu
             continue;
         }
 
-        k = x.floor() as u64;
+        k = x.floor().clamp_to_u64();
 
         let gp = ln_factorial(k)
             + ln_factorial(mingoodbad - k)
@@ -715,33 +944,170 @@ pub fn hypergeometric_sample_hrua(popsize: u64, good: u64, sample: u64, rng: &mu
 }
 
 /////////////////////////////////////////////////////////////////////////////////
-// multinomial_sample
+// binomial_sample
+
+pub fn binomial_sample(n: u64, p: f64, rng: &mut SmallRng) -> u64 {
+    binomial_sample_manual(n, p, rng)
+}
+
+// dispatches to BINV (inversion) for small n*min(p,1-p) and to BTPE
+// (Kachitvichyanukul & Schmeiser, 1988) otherwise, following numpy's thresholds;
+// see binomial_sample_btpe for why BTPE's acceptance test is exact here rather
+// than numpy's polynomial squeeze.
+const BINOMIAL_SMALL_NP_CUTOFF: f64 = 10.0;
+
+pub fn binomial_sample_manual(n: u64, p: f64, rng: &mut SmallRng) -> u64 {
+    if p <= 0.0 {
+        return 0;
+    }
+    if p >= 1.0 {
+        return n;
+    }
+    let r = p.min(1.0 - p);
+    let y = if (n as f64) * r <= BINOMIAL_SMALL_NP_CUTOFF {
+        binomial_sample_inversion(n, r, rng)
+    } else {
+        binomial_sample_btpe(n, r, rng)
+    };
+    if p > 0.5 {
+        n - y
+    } else {
+        y
+    }
+}
+
+// the BINV algorithm: walk the cumulative pmf starting from pmf(0) = q^n,
+// refreshing the running term via the pmf(x+1)/pmf(x) recurrence until the
+// waiting draw u falls under the accumulated mass. Restarts from 0 if x grows
+// past `bound`, the same safety valve numpy's random_binomial_inversion uses.
+fn binomial_sample_inversion(n: u64, p: f64, rng: &mut SmallRng) -> u64 {
+    let q = 1.0 - p;
+    let qn = (n as f64 * q.ln()).exp();
+    let np = n as f64 * p;
+    let bound = (n as f64)
+        .min(np + 10.0 * (np * q + 1.0).sqrt())
+        .clamp_to_u64();
+
+    let mut x: u64 = 0;
+    let mut px = qn;
+    let mut u: f64 = rng.random();
+    loop {
+        if u <= px {
+            return x;
+        }
+        x += 1;
+        if x > bound {
+            x = 0;
+            px = qn;
+            u = rng.random();
+            continue;
+        }
+        u -= px;
+        px = ((n - x + 1) as f64 * p * px) / (x as f64 * q);
+    }
+}
 
-const SMALL_EXPECTED_FAILURE_THRESHOLD: f64 = 1.0 / 1_000.0;
-pub fn binomial_sample(n: u64, p: f64, mut rng: &mut SmallRng) -> u64 {
-    // let n: usize = 2517438726;
-    // let p = 0.9999999999999994;
-    // TODO: this is a terrible, terrible hack, to get around a bug in rand_distr::Binomial
-    // that happens when called with the above n and p.
-    let expected_failures = n as f64 * (1.0 - p);
-    if expected_failures < SMALL_EXPECTED_FAILURE_THRESHOLD && n > core::i32::MAX as u64 {
-        let mut out = n;
-        while out > 0 {
-            let val: f64 = rng.sample(StandardUniform);
-            if val < expected_failures {
-                out -= 1;
-            } else {
-                println!("{:?}, {:?}, {:?}", out, n, expected_failures);
-                return out;
+// adapted from numpy's implementation of the BTPE (Binomial, Triangle,
+// Parallelogram, Exponential) rejection algorithm (Kachitvichyanukul &
+// Schmeiser, 1988), as of April 2025:
+// https://github.com/numpy/numpy/blob/b76bb2329032809229e8a531ba3179c34b0a3f0a/numpy/random/src/distributions/distributions.c#L661
+// numpy squeezes the final acceptance test with a four-term Stirling-series
+// polynomial to avoid a log/gamma call per candidate; we already have an exact
+// ln_factorial (with its own Stirling correction for large counts) on hand, so
+// binomial_sample_btpe_accept just evaluates the true log-pmf ratio instead.
+fn binomial_sample_btpe(n: u64, p: f64, rng: &mut SmallRng) -> u64 {
+    let q = 1.0 - p;
+    let nf = n as f64;
+    let s = nf * p * q; // variance of the distribution
+    let fm = nf * p + p;
+    let m = fm.floor() as i64;
+    let p1 = (2.195 * s.sqrt() - 4.6 * q).floor() + 0.5;
+    let xm = m as f64 + 0.5;
+    let xl = xm - p1;
+    let xr = xm + p1;
+    let c = 0.134 + 20.5 / (15.3 + m as f64);
+    let al = (fm - xl) / (fm - xl * p);
+    let laml = al * (1.0 + al / 2.0);
+    let ar = (xr - fm) / (xr * q);
+    let lamr = ar * (1.0 + ar / 2.0);
+    let p2 = p1 * (1.0 + 2.0 * c);
+    let p3 = p2 + c / laml;
+    let p4 = p3 + c / lamr;
+
+    let log_p = p.ln();
+    let log_q = q.ln();
+    let log_pmf_m = ln_factorial(n) - ln_factorial(m as u64) - ln_factorial(n - m as u64)
+        + m as f64 * log_p
+        + (n - m as u64) as f64 * log_q;
+
+    loop {
+        let u = high_precision_open01(rng) * p4;
+        let v = high_precision_open01(rng);
+
+        if u <= p1 {
+            // central triangular region: inverse-sampled directly, no test needed
+            let y = (xm - p1 * v + u).floor() as i64;
+            return y as u64;
+        } else if u <= p2 {
+            // parallelogram region
+            let x = xl + (u - p1) / c;
+            let v = v * c + 1.0 - (m as f64 - x + 0.5).abs() / p1;
+            if v > 1.0 || v <= 0.0 {
+                continue;
+            }
+            let y = x.floor() as i64;
+            if binomial_sample_btpe_accept(y, n, log_p, log_q, log_pmf_m, v.ln()) {
+                return y as u64;
+            }
+        } else if u <= p3 {
+            // left exponential tail
+            let y = (xl + v.ln() / laml).floor() as i64;
+            if y < 0 {
+                continue;
+            }
+            let v = v * (u - p2) * laml;
+            if binomial_sample_btpe_accept(y, n, log_p, log_q, log_pmf_m, v.ln()) {
+                return y as u64;
+            }
+        } else {
+            // right exponential tail
+            let y = (xr - v.ln() / lamr).floor() as i64;
+            if y > n.clamp_to_i64() {
+                continue;
+            }
+            let v = v * (u - p3) * lamr;
+            if binomial_sample_btpe_accept(y, n, log_p, log_q, log_pmf_m, v.ln()) {
+                return y as u64;
             }
         }
     }
-    let binomial_distribution = rand_distr::Binomial::new(n as u64, p).unwrap();
-    let sample = binomial_distribution.sample(&mut rng);
-    sample
 }
 
-// port of numpy's multinomial sample to Rust, using rand_distr::Binomial as the underlying binomial sampler
+// exact acceptance test for binomial_sample_btpe: accept the candidate y when
+// the log-pmf ratio pmf(y)/pmf(m) (via ln_factorial) dominates the proposal's
+// remaining log-density.
+fn binomial_sample_btpe_accept(
+    y: i64,
+    n: u64,
+    log_p: f64,
+    log_q: f64,
+    log_pmf_m: f64,
+    log_v: f64,
+) -> bool {
+    if y < 0 || y as u64 > n {
+        return false;
+    }
+    let y = y as u64;
+    let log_pmf_y = ln_factorial(n) - ln_factorial(y) - ln_factorial(n - y)
+        + y as f64 * log_p
+        + (n - y) as f64 * log_q;
+    log_v <= log_pmf_y - log_pmf_m
+}
+
+/////////////////////////////////////////////////////////////////////////////////
+// multinomial_sample
+
+// port of numpy's multinomial sample to Rust, using binomial_sample as the underlying binomial sampler
 // https://github.com/numpy/numpy/blob/4961a1414bba2222016f29a03dcf75e6034a13f7/numpy/random/src/distributions/distributions.c#L1726
 pub fn multinomial_sample_manual(n: u64, pix: &Vec<f64>, result: &mut [u64], rng: &mut SmallRng) {
     assert_eq!(
@@ -755,7 +1121,11 @@ pub fn multinomial_sample_manual(n: u64, pix: &Vec<f64>, result: &mut [u64], rng
     // Original Cython implementation zeroed out the result array initially, but
     // since we are overwriting the array, we only zero out the entries if we break out of the loop early.
     for j in 0..(d - 1) {
-        result[j] = binomial_sample(dn, pix[j] / remaining_p, rng);
+        // Clamp to dn: pix[j] / remaining_p can land on or fractionally above
+        // 1.0 from floating-point error, which would otherwise make
+        // binomial_sample return more than dn and underflow the subtraction
+        // below (dn is u64).
+        result[j] = binomial_sample(dn, pix[j] / remaining_p, rng).min(dn);
         dn -= result[j];
         if dn <= 0 {
             // erase old values in remainder of result array
@@ -769,8 +1139,206 @@ pub fn multinomial_sample_manual(n: u64, pix: &Vec<f64>, result: &mut [u64], rng
     result[d - 1] = dn;
 }
 
+/////////////////////////////////////////////////////////////////////////////////
+// poisson_sample
+//
+// Used by tau-leaping CRN integration: the number of firings of a reaction
+// channel over a fixed time step tau is Poisson(a_j * tau). Like rand_distr,
+// this dispatches on lambda into a small-lambda regime (Knuth's multiplicative
+// method) and a large-lambda regime (Hormann's 1993 transformed rejection with
+// squeeze, "PTRS", the same algorithm numpy's random_poisson_ptrs uses), with
+// the latter leaning on ln_gamma so we don't need a second gamma/factorial table.
+
+const POISSON_SMALL_LAMBDA_CUTOFF: f64 = 12.0;
+
+pub fn poisson_sample_manual(lambda: f64, rng: &mut SmallRng) -> u64 {
+    if lambda < POISSON_SMALL_LAMBDA_CUTOFF {
+        poisson_sample_knuth(lambda, rng)
+    } else {
+        poisson_sample_ptrs(lambda, rng)
+    }
+}
+
+// Knuth's multiplicative method: keep multiplying uniform draws into a running
+// product until it drops below exp(-lambda); the number of draws needed (minus
+// one) is Poisson(lambda) distributed.
+fn poisson_sample_knuth(lambda: f64, rng: &mut SmallRng) -> u64 {
+    let l = (-lambda).exp();
+    let mut k: u64 = 0;
+    let mut p = 1.0;
+    loop {
+        k += 1;
+        let u: f64 = rng.random();
+        p *= u;
+        if p < l {
+            break;
+        }
+    }
+    k - 1
+}
+
+// adapted from numpy's implementation of the transformed rejection method with
+// squeeze for the Poisson distribution (Hormann, 1993), as of April 2025:
+// https://github.com/numpy/numpy/blob/b76bb2329032809229e8a531ba3179c34b0a3f0a/numpy/random/src/distributions/random_mvhg_count.c
+// (the constants a/b/vr/invalpha below are Hormann's, tuned for a rejection
+// rate under 2% across all lambda; slam/loglam match the request's naming)
+fn poisson_sample_ptrs(lambda: f64, rng: &mut SmallRng) -> u64 {
+    let slam = lambda.sqrt();
+    let loglam = lambda.ln();
+    let b = 0.931 + 2.53 * slam;
+    let a = -0.059 + 0.02483 * b;
+    let inv_alpha = 1.1239 + 1.1328 / (b - 3.4);
+    let vr = 0.9277 - 3.6224 / (b - 2.0);
+
+    loop {
+        let u = rng.random::<f64>() - 0.5;
+        let v = rng.random::<f64>();
+        let us = 0.5 - u.abs();
+        let k = ((2.0 * a / us + b) * u + lambda + 0.43).floor();
+        if k < 0.0 {
+            continue;
+        }
+
+        // fast acceptance:
+        if us >= 0.07 && v <= vr {
+            return k as u64;
+        }
+
+        // fast rejection:
+        if us < 0.013 && v > us {
+            continue;
+        }
+
+        // squeeze acceptance/rejection, falling back to the exact log-ratio
+        // against the true Poisson pmf (via ln_gamma, as ln_gamma(k+1) = ln(k!)):
+        if v.ln() + inv_alpha.ln() - (a / (us * us) + b).ln()
+            <= -lambda + k * loglam - ln_gamma(k + 1.0)
+        {
+            return k as u64;
+        }
+    }
+}
+
+/////////////////////////////////////////////////////////////////////////////////
+// normal_sample
+//
+// Used by the chemical Langevin / CLE approximation: once reactant populations
+// are large enough that SSA and even tau-leaping are too slow, a reaction's
+// firing count over a step tau is approximated as Normal(a_j*tau, a_j*tau). We
+// sample it with the ziggurat method (Marsaglia & Tsang, 2000): a 256-layer
+// partition of the area under the half-normal density into equal-area boxes,
+// each identified by a boundary x_i (the box's width) and f(x_i) = exp(-x_i^2/2)
+// (its height). A draw picks a layer, scales a uniform into that layer's box,
+// and almost always accepts on the spot; the rare miss either falls back to a
+// dedicated exponential-tail sampler (layer 0, the unbounded base strip) or a
+// wedge rejection test against the true density (every other layer).
+//
+// The tables are precomputed once via lazy_static, the same way LOGFACT is.
+
+const ZIGGURAT_LAYERS: usize = 256;
+
+// The one solved constant the whole table constructions hangs off: the right
+// boundary of the base strip for a 256-layer ziggurat over exp(-x^2/2), chosen
+// so that the backward recurrence below lands on a sensible decreasing sequence
+// all the way up to the peak. Analogous to the LG1..LG13/BERNOULLI_COEFFS
+// constants above: a known derived value, not something we solve for at runtime.
+const ZIGGURAT_NORMAL_R: f64 = 3.654152885361008796;
+
+fn ziggurat_f(x: f64) -> f64 {
+    (-0.5 * x * x).exp()
+}
+
+// The area under exp(-x^2/2) from r to infinity: sqrt(pi/2) * erfc(r/sqrt(2)).
+fn ziggurat_tail_area(r: f64) -> f64 {
+    (std::f64::consts::FRAC_PI_2).sqrt() * special::Error::compl_error(r / std::f64::consts::SQRT_2)
+}
+
+// Backward recurrence for the box boundaries: every box has the same area v.
+// x[1] is the known constant r; x[0] (the base strip) has area
+// v = r*f(r) + tail_area(r) and width v/f(r), since its height is f(r) exactly.
+// Each subsequent x[i] solves x[i]*(f(x[i]) - f(x[i-1])) = v for the only
+// unknown once x[i-1] is known: x[i] = sqrt(-2*ln(v/x[i-1] + f(x[i-1]))).
+fn build_ziggurat_normal_x_table() -> [f64; ZIGGURAT_LAYERS + 1] {
+    let mut x = [0.0_f64; ZIGGURAT_LAYERS + 1];
+    let v =
+        ZIGGURAT_NORMAL_R * ziggurat_f(ZIGGURAT_NORMAL_R) + ziggurat_tail_area(ZIGGURAT_NORMAL_R);
+    x[1] = ZIGGURAT_NORMAL_R;
+    x[0] = v / ziggurat_f(ZIGGURAT_NORMAL_R);
+    for i in 2..ZIGGURAT_LAYERS {
+        x[i] = (-2.0 * (v / x[i - 1] + ziggurat_f(x[i - 1])).ln()).sqrt();
+    }
+    x[ZIGGURAT_LAYERS] = 0.0;
+    x
+}
+
+lazy_static! {
+    static ref ZIGGURAT_NORMAL_X: [f64; ZIGGURAT_LAYERS + 1] = build_ziggurat_normal_x_table();
+    static ref ZIGGURAT_NORMAL_F: [f64; ZIGGURAT_LAYERS + 1] = {
+        let mut f = [0.0_f64; ZIGGURAT_LAYERS + 1];
+        for i in 0..ZIGGURAT_LAYERS {
+            f[i] = ziggurat_f(ZIGGURAT_NORMAL_X[i]);
+        }
+        f[ZIGGURAT_LAYERS] = 1.0; // f(0), exact rather than exp(-0.0) rounding
+        f
+    };
+}
+
+// Layer 0 is the unbounded base strip; candidates that miss its box come from
+// the true exponential tail beyond r, via the standard rejection sampler:
+// x = -ln(U1)/r, y = -ln(U2), accept when 2y > x^2.
+fn ziggurat_normal_tail_sample(rng: &mut SmallRng) -> f64 {
+    loop {
+        let u1: f64 = rng.random();
+        let u2: f64 = rng.random();
+        let x = -u1.ln() / ZIGGURAT_NORMAL_R;
+        let y = -u2.ln();
+        if 2.0 * y > x * x {
+            return ZIGGURAT_NORMAL_R + x;
+        }
+    }
+}
+
+fn standard_normal_sample_manual(rng: &mut SmallRng) -> f64 {
+    loop {
+        let i = rng.random_range(0..ZIGGURAT_LAYERS);
+        let u: f64 = rng.random::<f64>() * 2.0 - 1.0;
+        let x = u * ZIGGURAT_NORMAL_X[i];
+
+        if x.abs() < ZIGGURAT_NORMAL_X[i + 1] {
+            return x;
+        }
+
+        if i == 0 {
+            let tail = ziggurat_normal_tail_sample(rng);
+            return if u < 0.0 { -tail } else { tail };
+        }
+
+        let y: f64 = rng.random();
+        let height =
+            ZIGGURAT_NORMAL_F[i + 1] + (ZIGGURAT_NORMAL_F[i] - ZIGGURAT_NORMAL_F[i + 1]) * y;
+        if height < ziggurat_f(x) {
+            return x;
+        }
+    }
+}
+
+pub fn normal_sample(mean: f64, std: f64, rng: &mut SmallRng) -> f64 {
+    mean + std * standard_normal_sample_manual(rng)
+}
+
+// Reflects draws below zero back into range, so CLE leap counts (which must be
+// non-negative) stay valid without discarding the sample outright.
+pub fn normal_sample_nonneg(mean: f64, std: f64, rng: &mut SmallRng) -> f64 {
+    normal_sample(mean, std, rng).abs()
+}
+
+// Decodes x's IEEE 754 binary128 bits and formats the fewest decimal digits
+// that round-trip back to the same bits, rounding half-to-even throughout via
+// exact BigInt arithmetic. Branches on biased exponent 0 so subnormals (no
+// implicit leading one, minimum exponent) decode correctly instead of being
+// read as if they were normal; see f128_to_shortest_decimal/f128_from_decimal
+// below for the matching parser and the shared digit-generation helpers.
 pub fn f128_to_decimal(x: f128) -> String {
-    // Handle special cases first
     if x.is_nan() {
         return "NaN".to_string();
     }
@@ -782,67 +1350,390 @@ pub fn f128_to_decimal(x: f128) -> String {
         };
     }
     if x == 0.0 {
-        return "0.0".to_string();
+        return if x.is_sign_negative() {
+            "-0.0".to_string()
+        } else {
+            "0.0".to_string()
+        };
     }
 
-    // Extract IEEE 754 binary128 components
-    let bits = x.to_bits();
-    let sign = (bits >> 127) != 0;
-    let exponent = ((bits >> 112) & 0x7FFF) as i32;
-    let mantissa = bits & 0x0000_FFFF_FFFF_FFFF_FFFF_FFFF_FFFF_FFFF;
+    for n_digits in 1..=MAX_SHORTEST_DECIMAL_DIGITS {
+        let candidate = format_f128_with_digits(x, n_digits);
+        if let Ok(parsed) = f128_from_decimal(&candidate) {
+            if parsed.to_bits() == x.to_bits() {
+                return candidate;
+            }
+        }
+    }
+    // Every finite nonzero f128 round-trips in well under 40 digits, so this
+    // is unreachable in practice; fall back to the maximum digit count.
+    format_f128_with_digits(x, MAX_SHORTEST_DECIMAL_DIGITS)
+}
 
-    // Handle sign
-    let sign_str = if sign { "-" } else { "" };
+/////////////////////////////////////////////////////////////////////////////////
+// f128_to_shortest_decimal / f128_from_decimal
+//
+// f128_to_decimal above and f128_from_decimal below are a matched pair for
+// checkpointing this module's quad-precision log-probability accumulators to
+// text and reloading them bit-for-bit: the fewest decimal digits that
+// round-trip back to the same bits, and a parser that recovers those bits
+// exactly rather than going through a lossy f64 intermediate.
+//
+// Both directions are built on the same big-integer scaling trick: decompose
+// x into significand*2^binary_exp via to_bits, then multiply/divide by powers
+// of ten with a BigInt so every division remainder is exact and rounding
+// decisions (round-half-to-even) are exact comparisons rather than
+// floating-point guesses.
+
+use std::cmp::Ordering;
+
+const F128_EXPONENT_BIAS: i64 = 16383;
+const F128_MAX_BIASED_EXPONENT: i64 = 0x7FFE; // 0x7FFF is reserved for inf/NaN
+
+// f128's significand holds 113 bits (1 implicit + 112 stored), which needs at
+// most ceil(113 * log10(2)) = 35 decimal digits to round-trip; a few digits
+// of headroom keeps the search loop below honest without looping forever.
+const MAX_SHORTEST_DECIMAL_DIGITS: u32 = 40;
+
+// f128_to_decimal already produces the shortest round-trippable form; this
+// name is kept as an alias for callers that want that guarantee spelled out
+// explicitly.
+pub fn f128_to_shortest_decimal(x: f128) -> String {
+    f128_to_decimal(x)
+}
 
-    // IEEE 754 binary128 has:
-    // - 1 sign bit
-    // - 15 exponent bits (bias = 16383)
-    // - 112 mantissa bits
+pub fn f128_from_decimal(s: &str) -> Result<f128, String> {
+    let trimmed = s.trim();
+    if trimmed.is_empty() {
+        return Err("cannot parse empty string as f128".to_string());
+    }
 
-    let bias = 16383;
-    let actual_exponent = exponent - bias;
+    let mut chars = trimmed.chars().peekable();
+    let mut negative = false;
+    if let Some(&c) = chars.peek() {
+        if c == '+' || c == '-' {
+            negative = c == '-';
+            chars.next();
+        }
+    }
 
-    // Build the significand (1.mantissa for normal numbers)
-    let mut significand = BigInt::from(1_u128 << 112); // Implicit leading 1
-    significand += BigInt::from(mantissa);
+    let mut int_part = String::new();
+    while let Some(&c) = chars.peek() {
+        if c.is_ascii_digit() {
+            int_part.push(c);
+            chars.next();
+        } else {
+            break;
+        }
+    }
 
-    // Calculate the actual value: significand * 2^(actual_exponent - 112)
-    let power_of_2 = actual_exponent - 112;
+    let mut frac_part = String::new();
+    if let Some(&c) = chars.peek() {
+        if c == '.' {
+            chars.next();
+            while let Some(&c2) = chars.peek() {
+                if c2.is_ascii_digit() {
+                    frac_part.push(c2);
+                    chars.next();
+                } else {
+                    break;
+                }
+            }
+        }
+    }
 
-    let mut result = significand;
+    let mut explicit_exp: i64 = 0;
+    if let Some(&c) = chars.peek() {
+        if c == 'e' || c == 'E' {
+            chars.next();
+            let mut exp_negative = false;
+            if let Some(&c2) = chars.peek() {
+                if c2 == '+' || c2 == '-' {
+                    exp_negative = c2 == '-';
+                    chars.next();
+                }
+            }
+            let mut exp_digits = String::new();
+            while let Some(&c2) = chars.peek() {
+                if c2.is_ascii_digit() {
+                    exp_digits.push(c2);
+                    chars.next();
+                } else {
+                    break;
+                }
+            }
+            if exp_digits.is_empty() {
+                return Err(format!("missing exponent digits in {:?}", s));
+            }
+            let magnitude: i64 = exp_digits
+                .parse()
+                .map_err(|_| format!("invalid exponent in {:?}", s))?;
+            explicit_exp = if exp_negative { -magnitude } else { magnitude };
+        }
+    }
 
-    if power_of_2 >= 0 {
-        // Multiply by 2^power_of_2
-        result <<= power_of_2;
-        format!("{}{}.0", sign_str, result)
-    } else {
-        // Divide by 2^(-power_of_2)
-        // This is where we need to do decimal division
-        let divisor = BigInt::from(1_u128) << (-power_of_2);
+    if chars.next().is_some() {
+        return Err(format!("unexpected trailing characters in {:?}", s));
+    }
+    if int_part.is_empty() && frac_part.is_empty() {
+        return Err(format!("no digits found in {:?}", s));
+    }
+
+    let decimal_exp = explicit_exp - frac_part.len() as i64;
+    let digit_str = format!("{}{}", int_part, frac_part);
+    let significand = digit_str
+        .parse::<BigInt>()
+        .map_err(|_| format!("invalid digits in {:?}", s))?;
 
-        // Perform long division to get decimal representation
-        let quotient = &result / &divisor;
-        let remainder = &result % &divisor;
+    if significand == BigInt::from(0) {
+        let bits: u128 = if negative { 1_u128 << 127 } else { 0 };
+        return Ok(f128::from_bits(bits));
+    }
+
+    let (mantissa_bits, biased_exponent) = decimal_to_f128_bits(&significand, decimal_exp)?;
+    let sign_bit: u128 = if negative { 1_u128 << 127 } else { 0 };
+    let bits = sign_bit | ((biased_exponent as u128) << 112) | mantissa_bits;
+    Ok(f128::from_bits(bits))
+}
 
-        if remainder == BigInt::from(0) {
-            format!("{}{}.0", sign_str, quotient)
+// Finds the correctly-rounded (round-half-to-even) 113-bit significand for
+// significand_dec * 10^decimal_exp, returning (mantissa bits, biased exponent).
+fn decimal_to_f128_bits(significand_dec: &BigInt, decimal_exp: i64) -> Result<(u128, i64), String> {
+    if let Some(exact) = exact_dyadic_bits(significand_dec, decimal_exp) {
+        return Ok(exact);
+    }
+
+    // Slow path: the decimal value isn't an exact (small enough) dyadic
+    // rational, so seed a binary exponent guess from the bit-length estimate
+    // and refine it with exact big-integer division until the rounded
+    // quotient lands in [2^112, 2^113). Below the smallest normal exponent
+    // the search instead clamps to that exponent and accepts a mantissa with
+    // fewer bits and no implicit leading one, i.e. a subnormal.
+    let bits_estimate =
+        significand_dec.bits() as f64 + decimal_exp as f64 * std::f64::consts::LOG2_10;
+    let min_normal_binary_exp = 1 - F128_EXPONENT_BIAS - 112;
+    // binary_exp never needs to go below the subnormal floor: every value
+    // down to the smallest subnormal is representable there with bit_len <= 112.
+    let mut binary_exp = ((bits_estimate - 113.0).floor() as i64).max(min_normal_binary_exp);
+
+    loop {
+        let (num, den) = scaled_ratio(significand_dec, 0, decimal_exp);
+        let (num, den) = if binary_exp >= 0 {
+            (num, den << binary_exp as usize)
         } else {
-            // Calculate decimal places
-            let mut decimal_digits = String::new();
-            let mut current_remainder = remainder * 10;
+            (num << (-binary_exp) as usize, den)
+        };
+        let (quotient, round_up) = divide_round_half_even(&num, &den);
+        let mut mantissa = quotient;
+        if round_up {
+            mantissa += BigInt::from(1);
+        }
 
-            for _ in 0..50 {
-                // Limit to 50 decimal places
-                let digit: BigInt = &current_remainder / &divisor;
-                decimal_digits.push_str(&digit.to_string());
-                current_remainder = (&current_remainder % &divisor) * 10;
+        let bit_len = mantissa.bits();
 
-                if current_remainder == BigInt::from(0) {
-                    break;
-                }
+        if bit_len > 113 {
+            binary_exp += 1;
+            continue;
+        }
+        if bit_len == 113 {
+            // A full 113-bit mantissa is always normal, even when binary_exp
+            // has been clamped to min_normal_binary_exp (the smallest normal
+            // value itself has exactly 113 bits there).
+            let biased_exponent = binary_exp + 112 + F128_EXPONENT_BIAS;
+            if biased_exponent >= F128_MAX_BIASED_EXPONENT {
+                return Err("value overflows f128's exponent range".to_string());
             }
+            let mantissa_u128 = bigint_to_u128(&mantissa) - (1_u128 << 112);
+            return Ok((mantissa_u128, biased_exponent));
+        }
+        // bit_len < 113: narrow the exponent further, unless already at the
+        // subnormal floor, where fewer than 113 bits is expected and final.
+        if binary_exp > min_normal_binary_exp {
+            binary_exp -= 1;
+            continue;
+        }
+        if mantissa == BigInt::from(0) {
+            return Err("value underflows to zero at f128 subnormal precision".to_string());
+        }
+        return Ok((bigint_to_u128(&mantissa), 0));
+    }
+}
+
+// Fast path: if significand_dec * 10^decimal_exp is an exact dyadic rational
+// (its reduced denominator is a pure power of two) whose numerator fits in
+// f128's 113-bit significand, we can read the bits off directly with no
+// rounding and no search loop.
+fn exact_dyadic_bits(significand_dec: &BigInt, decimal_exp: i64) -> Option<(u128, i64)> {
+    let (num, den) = scaled_ratio(significand_dec, 0, decimal_exp);
+    let g = bigint_gcd(&num, &den);
+    let mut num = num / &g;
+    let den = den / &g;
+
+    let mut pow2 = 0_i64;
+    let mut remaining_den = den;
+    let two = BigInt::from(2);
+    while &remaining_den % &two == BigInt::from(0) {
+        remaining_den /= &two;
+        pow2 += 1;
+    }
+    if remaining_den != BigInt::from(1) {
+        return None; // denominator has an odd factor left: not exact, needs rounding
+    }
+
+    let bit_len = num.bits() as i64;
+    if bit_len > 113 {
+        return None; // exact, but more significant bits than f128's mantissa holds
+    }
+
+    let shift_up = 113 - bit_len;
+    num <<= shift_up as usize;
+    let binary_exp = -pow2 - shift_up;
 
-            format!("{}{}.{}", sign_str, quotient, decimal_digits)
+    let biased_exponent = binary_exp + 112 + F128_EXPONENT_BIAS;
+    if biased_exponent <= 0 || biased_exponent >= F128_MAX_BIASED_EXPONENT {
+        return None; // let the slow path produce the subnormal or the overflow error
+    }
+
+    let mantissa_u128 = bigint_to_u128(&num) - (1_u128 << 112);
+    Some((mantissa_u128, biased_exponent))
+}
+
+// Rounds significand*2^binary_exp to n_digits significant decimal digits
+// (round-half-to-even), returning the digits and the decimal exponent of the
+// leading digit (value ~= digits * 10^(decimal_exp - n_digits + 1)).
+fn round_to_n_digits(significand: &BigInt, binary_exp: i64, n_digits: u32) -> (BigInt, i64) {
+    let bit_len = significand.bits() as i64 + binary_exp;
+    let mut decimal_exp = ((bit_len - 1) as f64 * std::f64::consts::LOG10_2).floor() as i64;
+
+    loop {
+        let shift = n_digits as i64 - 1 - decimal_exp;
+        let (num, den) = scaled_ratio(significand, binary_exp, shift);
+        let (quotient, round_up) = divide_round_half_even(&num, &den);
+        let mut digits = quotient;
+        if round_up {
+            digits += BigInt::from(1);
+        }
+
+        if digits == BigInt::from(0) {
+            decimal_exp -= 1;
+            continue;
         }
+
+        let digit_count = digits.to_string().len() as i64;
+        if digit_count > n_digits as i64 {
+            decimal_exp += 1;
+        } else if digit_count < n_digits as i64 {
+            decimal_exp -= 1;
+        } else {
+            return (digits, decimal_exp);
+        }
+    }
+}
+
+fn format_f128_with_digits(x: f128, n_digits: u32) -> String {
+    let bits = x.to_bits();
+    let sign = (bits >> 127) != 0;
+    let exponent = ((bits >> 112) & 0x7FFF) as i64;
+    let mantissa = bits & 0x0000_FFFF_FFFF_FFFF_FFFF_FFFF_FFFF_FFFF;
+
+    // Subnormals (biased exponent 0) carry no implicit leading one and use
+    // the minimum exponent (1 - bias) rather than exponent - bias.
+    let (significand, binary_exp) = if exponent == 0 {
+        (BigInt::from(mantissa), 1 - F128_EXPONENT_BIAS - 112)
+    } else {
+        (
+            BigInt::from(1_u128 << 112) + BigInt::from(mantissa),
+            exponent - F128_EXPONENT_BIAS - 112,
+        )
+    };
+
+    let (digits, decimal_exp) = round_to_n_digits(&significand, binary_exp, n_digits);
+    let digits_str = digits.to_string();
+    let sign_str = if sign { "-" } else { "" };
+    // digit 0 (leftmost) sits in the 10^decimal_exp place, so decimal_exp + 1
+    // digits belong before the decimal point.
+    let point = decimal_exp + 1;
+    build_fixed_point_string(sign_str, &digits_str, point)
+}
+
+fn build_fixed_point_string(sign_str: &str, digits: &str, point: i64) -> String {
+    if point <= 0 {
+        format!("{}0.{}{}", sign_str, "0".repeat((-point) as usize), digits)
+    } else if (point as usize) >= digits.len() {
+        format!(
+            "{}{}{}.0",
+            sign_str,
+            digits,
+            "0".repeat(point as usize - digits.len())
+        )
+    } else {
+        let (head, tail) = digits.split_at(point as usize);
+        format!("{}{}.{}", sign_str, head, tail)
     }
 }
+
+// Computes significand * 2^binary_exp * 10^decimal_shift as an exact
+// (numerator, denominator) pair of non-negative BigInts.
+fn scaled_ratio(significand: &BigInt, binary_exp: i64, decimal_shift: i64) -> (BigInt, BigInt) {
+    let pow2 = binary_exp + decimal_shift;
+    let pow5 = decimal_shift;
+
+    let mut num = significand.clone();
+    let mut den = BigInt::from(1);
+
+    if pow5 >= 0 {
+        num *= bigint_pow5(pow5);
+    } else {
+        den *= bigint_pow5(-pow5);
+    }
+    if pow2 >= 0 {
+        num <<= pow2 as usize;
+    } else {
+        den <<= (-pow2) as usize;
+    }
+    (num, den)
+}
+
+fn bigint_pow5(exp: i64) -> BigInt {
+    let mut result = BigInt::from(1);
+    let five = BigInt::from(5);
+    for _ in 0..exp {
+        result *= &five;
+    }
+    result
+}
+
+fn bigint_gcd(a: &BigInt, b: &BigInt) -> BigInt {
+    let mut a = a.clone();
+    let mut b = b.clone();
+    while b != BigInt::from(0) {
+        let r = &a % &b;
+        a = b;
+        b = r;
+    }
+    a
+}
+
+fn divide_round_half_even(num: &BigInt, den: &BigInt) -> (BigInt, bool) {
+    let quotient = num / den;
+    let remainder = num - &quotient * den;
+    let twice_remainder = &remainder * BigInt::from(2);
+    match twice_remainder.cmp(den) {
+        Ordering::Greater => (quotient, true),
+        Ordering::Less => (quotient, false),
+        Ordering::Equal => {
+            let is_odd = (&quotient % 2) != BigInt::from(0);
+            (quotient, is_odd)
+        }
+    }
+}
+
+// significand is guaranteed to fit in 113 bits by every caller, so this
+// always fits in a u128.
+fn bigint_to_u128(n: &BigInt) -> u128 {
+    let (_, bytes) = n.to_bytes_be();
+    let mut buf = [0_u8; 16];
+    let start = 16 - bytes.len();
+    buf[start..].copy_from_slice(&bytes);
+    u128::from_be_bytes(buf)
+}
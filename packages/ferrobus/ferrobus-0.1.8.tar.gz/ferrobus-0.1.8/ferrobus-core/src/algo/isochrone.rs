@@ -3,8 +3,9 @@
 //! alternative approach to calculate isochrones using H3 hexagonal
 //! grid cells as a index.
 
-use geo::{MultiPolygon, Point, Polygon};
+use geo::{ConcaveHull, MultiPoint, MultiPolygon, Point, Polygon};
 use hashbrown::HashMap;
+use petgraph::graph::NodeIndex;
 use rayon::prelude::*;
 
 use h3o::{
@@ -12,7 +13,10 @@ use h3o::{
     geom::{ContainmentMode, SolventBuilder, TilerBuilder},
 };
 
-use crate::{Error, Time, TransitModel};
+use crate::routing::dijkstra::dijkstra_path_weights;
+use crate::routing::multimodal_routing::run_routing;
+use crate::routing::raptor::RaptorResult;
+use crate::{Error, MAX_CANDIDATE_STOPS, RaptorStopId, Time, TransitModel};
 use crate::{TransitPoint, multimodal_routing_one_to_many};
 
 /// Index for isochrone calculation covering a specific area
@@ -239,3 +243,179 @@ fn compute_reachable_cells(
         .collect();
     Ok(reached_cells)
 }
+
+/// Width of each contour band produced by [`isochrone`], in seconds
+const BAND_WIDTH: Time = 900; // 15 minutes
+
+/// A single reached stop or street node, with its earliest-arrival time
+#[derive(Debug, Clone, Copy)]
+pub struct ReachedPoint {
+    pub location: Point<f64>,
+    /// Seconds elapsed since `departure_time`
+    pub travel_time: Time,
+}
+
+/// One contour band of a reachability map (e.g. the 0-15 minute band)
+#[derive(Debug, Clone)]
+pub struct IsochroneBand {
+    /// Upper bound of this band, in seconds since `departure_time`
+    pub max_time: Time,
+    /// Concave hull over every point reached within `max_time`.
+    /// `None` if fewer than 3 points were reached within the band.
+    pub polygon: Option<Polygon<f64>>,
+}
+
+/// Full reachability map computed from a single origin: every stop and
+/// street node reached within `max_time`, plus a contour polygon per
+/// [`BAND_WIDTH`] band.
+#[derive(Debug, Clone)]
+pub struct Reachability {
+    pub points: Vec<ReachedPoint>,
+    pub bands: Vec<IsochroneBand>,
+}
+
+/// Builds a reachability map from `origin`: runs a one-to-many search to
+/// every stop, then walks outward from the origin and from every reached
+/// stop to cover the surrounding street nodes, bounded by the remaining
+/// time budget. The raw `(location, travel_time)` pairs are also binned into
+/// contour bands, each rendered as a concave hull over its reached points.
+///
+/// # Errors
+///
+/// Returns an error if the underlying routing search fails.
+pub fn isochrone(
+    transit_data: &TransitModel,
+    origin: &TransitPoint,
+    departure_time: Time,
+    max_time: Time,
+    max_transfers: usize,
+) -> Result<Reachability, Error> {
+    let stop_times = reachable_stop_times(transit_data, origin, departure_time, max_transfers);
+
+    let mut stop_to_node: HashMap<RaptorStopId, NodeIndex> = HashMap::new();
+    for (&node, &stop_id) in &transit_data.transit_data.node_to_stop {
+        stop_to_node.entry(stop_id).or_insert(node);
+    }
+
+    let mut points = Vec::new();
+    let mut node_time: HashMap<NodeIndex, Time> = HashMap::new();
+
+    for (stop_id, &elapsed) in stop_times.iter().enumerate() {
+        if elapsed > max_time {
+            continue;
+        }
+
+        points.push(ReachedPoint {
+            location: transit_data.transit_data.transit_stop_location(stop_id),
+            travel_time: elapsed,
+        });
+
+        if let Some(&node) = stop_to_node.get(&stop_id) {
+            let remaining = max_time - elapsed;
+            for (walk_node, walk_time) in dijkstra_path_weights(
+                transit_data.street_graph(),
+                node,
+                None,
+                Some(f64::from(remaining)),
+            ) {
+                let total = elapsed + walk_time;
+                node_time
+                    .entry(walk_node)
+                    .and_modify(|best| *best = (*best).min(total))
+                    .or_insert(total);
+            }
+        }
+    }
+
+    // Direct walking coverage from the origin itself (no transit leg)
+    for (walk_node, walk_time) in dijkstra_path_weights(
+        transit_data.street_graph(),
+        origin.node_id,
+        None,
+        Some(f64::from(max_time)),
+    ) {
+        node_time
+            .entry(walk_node)
+            .and_modify(|best| *best = (*best).min(walk_time))
+            .or_insert(walk_time);
+    }
+
+    for (node, travel_time) in node_time {
+        if travel_time > max_time {
+            continue;
+        }
+        if let Some(node_weight) = transit_data.street_graph().graph.node_weight(node) {
+            points.push(ReachedPoint {
+                location: node_weight.geometry,
+                travel_time,
+            });
+        }
+    }
+
+    let bands = build_bands(&points, max_time);
+
+    Ok(Reachability { points, bands })
+}
+
+/// Earliest-arrival time (seconds elapsed since `departure_time`) for every
+/// stop in the model, merged over the origin's candidate access stops
+fn reachable_stop_times(
+    transit_data: &TransitModel,
+    origin: &TransitPoint,
+    departure_time: Time,
+    max_transfers: usize,
+) -> Vec<Time> {
+    let algorithm = transit_data.meta.algorithm;
+    let data = &transit_data.transit_data;
+    let mut best = vec![Time::MAX; data.stops.len()];
+
+    for &(access_stop, access_time) in origin.nearest_stops.iter().take(MAX_CANDIDATE_STOPS) {
+        if let Ok(RaptorResult::AllTargets(times)) = run_routing(
+            algorithm,
+            data,
+            access_stop,
+            None,
+            departure_time + access_time,
+            max_transfers,
+        ) {
+            for (stop_id, target) in times.iter().enumerate() {
+                if target.is_reachable() {
+                    let elapsed = target.arrival_time - departure_time;
+                    if elapsed < best[stop_id] {
+                        best[stop_id] = elapsed;
+                    }
+                }
+            }
+        }
+    }
+
+    best
+}
+
+fn build_bands(points: &[ReachedPoint], max_time: Time) -> Vec<IsochroneBand> {
+    let mut bands = Vec::new();
+    let mut band_max = BAND_WIDTH;
+    while band_max < max_time {
+        bands.push(build_band(points, band_max));
+        band_max += BAND_WIDTH;
+    }
+    bands.push(build_band(points, max_time));
+    bands
+}
+
+/// Builds one contour band: a concave hull (alpha-shape-like) over every
+/// point reached within `band_max`
+fn build_band(points: &[ReachedPoint], band_max: Time) -> IsochroneBand {
+    let coords: Vec<Point<f64>> = points
+        .iter()
+        .filter(|p| p.travel_time <= band_max)
+        .map(|p| p.location)
+        .collect();
+
+    let polygon = (coords.len() >= 3).then(|| MultiPoint::new(coords).concave_hull(2.0));
+
+    IsochroneBand {
+        max_time: band_max,
+        polygon,
+    }
+}
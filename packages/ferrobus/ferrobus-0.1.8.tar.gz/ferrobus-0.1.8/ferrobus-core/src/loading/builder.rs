@@ -54,6 +54,8 @@ pub fn create_transit_model(config: &TransitModelConfig) -> Result<TransitModel,
         transit_data,
         crate::model::TransitModelMeta {
             max_transfer_time: config.max_transfer_time,
+            default_station_transfer_time: config.default_station_transfer_time,
+            algorithm: config.algorithm,
         },
     );
 
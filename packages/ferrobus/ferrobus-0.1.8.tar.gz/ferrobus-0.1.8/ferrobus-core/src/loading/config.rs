@@ -1,4 +1,5 @@
 use crate::Time;
+use crate::routing::RoutingAlgorithm;
 use std::path::PathBuf;
 
 /// Configuration for creating a transit model
@@ -12,6 +13,11 @@ pub struct TransitModelConfig {
     pub max_transfer_time: Time,
     /// Day of week for trips filtering
     pub date: Option<chrono::NaiveDate>,
+    /// Walk transfer time (seconds) synthesized between sibling platforms of the
+    /// same parent station when the feed does not give an explicit transfer
+    pub default_station_transfer_time: Time,
+    /// Routing engine the resulting model answers transit queries with
+    pub algorithm: RoutingAlgorithm,
 }
 
 impl Default for TransitModelConfig {
@@ -21,6 +27,8 @@ impl Default for TransitModelConfig {
             osm_path: PathBuf::new(),
             gtfs_dirs: vec![PathBuf::new()],
             date: None,
+            default_station_transfer_time: 180, // 3 minutes
+            algorithm: RoutingAlgorithm::default(),
         }
     }
 }
@@ -32,6 +40,8 @@ impl TransitModelConfig {
             osm_path,
             gtfs_dirs: Vec::new(),
             date: None,
+            default_station_transfer_time: 180,
+            algorithm: RoutingAlgorithm::default(),
         }
     }
 
@@ -40,4 +50,11 @@ impl TransitModelConfig {
         self.gtfs_dirs.push(dir);
         self
     }
+
+    /// Selects the routing engine the resulting model will use
+    #[must_use]
+    pub fn with_algorithm(mut self, algorithm: RoutingAlgorithm) -> Self {
+        self.algorithm = algorithm;
+        self
+    }
 }
@@ -21,29 +21,148 @@ where
         .collect::<Vec<T>>())
 }
 
-/// Parse time string in HH:MM:SS format to seconds since midnight
-fn parse_time(time_str: &str) -> Result<u32, Error> {
-    let time_str = time_str.trim();
-    let bytes = time_str.as_bytes();
+/// A single row that failed to parse while reading a GTFS CSV file.
+#[derive(Debug)]
+pub struct RowError {
+    /// 1-based line number of the offending row, when the underlying CSV
+    /// error reports a position.
+    pub line: Option<u64>,
+    /// The `csv`/serde error raised while deserializing the row.
+    pub source: csv::Error,
+}
+
+impl std::fmt::Display for RowError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.line {
+            Some(line) => write!(f, "line {line}: {}", self.source),
+            None => write!(f, "{}", self.source),
+        }
+    }
+}
 
-    if bytes.len() == 8 && bytes[2] == b':' && bytes[5] == b':' {
-        if !(bytes[0].is_ascii_digit()
-            && bytes[1].is_ascii_digit()
-            && bytes[3].is_ascii_digit()
-            && bytes[4].is_ascii_digit()
-            && bytes[6].is_ascii_digit()
-            && bytes[7].is_ascii_digit())
-        {
-            return Err(Error::InvalidTimeFormat(time_str.to_string()));
+/// Like [`deserialize_gtfs_file`], but never silently drops malformed
+/// rows: every row that fails to deserialize is collected into the
+/// returned `Vec<RowError>` (with its line number) instead of being
+/// discarded, so a caller can tell a clean feed from one that's silently
+/// losing data.
+pub fn deserialize_gtfs_file_reporting<T>(path: &Path) -> Result<(Vec<T>, Vec<RowError>), Error>
+where
+    T: for<'de> serde::Deserialize<'de>,
+{
+    let file = File::open(path).map_err(|e| {
+        std::io::Error::new(
+            e.kind(),
+            format!("Failed to open file '{}': {}", path.display(), e),
+        )
+    })?;
+    let mut rows = Vec::new();
+    let mut errors = Vec::new();
+    for result in csv::Reader::from_reader(file).deserialize::<T>() {
+        match result {
+            Ok(row) => rows.push(row),
+            Err(source) => {
+                let line = source.position().map(csv::Position::line);
+                errors.push(RowError { line, source });
+            }
+        }
+    }
+    Ok((rows, errors))
+}
+
+/// Like [`deserialize_gtfs_file_reporting`], but aborts the whole load as
+/// soon as one row fails to deserialize, for callers that would rather
+/// reject a malformed feed outright than ingest it partially.
+pub fn deserialize_gtfs_file_strict<T>(path: &Path) -> Result<Vec<T>, Error>
+where
+    T: for<'de> serde::Deserialize<'de>,
+{
+    let file = File::open(path).map_err(|e| {
+        std::io::Error::new(
+            e.kind(),
+            format!("Failed to open file '{}': {}", path.display(), e),
+        )
+    })?;
+    let mut rows = Vec::new();
+    for result in csv::Reader::from_reader(file).deserialize::<T>() {
+        match result {
+            Ok(row) => rows.push(row),
+            Err(source) => {
+                let line = source.position().map(csv::Position::line);
+                let row_error = RowError { line, source };
+                return Err(Error::InvalidData(format!("{}: {row_error}", path.display())));
+            }
         }
+    }
+    Ok(rows)
+}
 
-        let hours = u32::from(bytes[0] - b'0') * 10 + u32::from(bytes[1] - b'0');
-        let minutes = u32::from(bytes[3] - b'0') * 10 + u32::from(bytes[4] - b'0');
-        let seconds = u32::from(bytes[6] - b'0') * 10 + u32::from(bytes[7] - b'0');
-        return Ok(hours * 3600 + minutes * 60 + seconds);
+/// Selects how a single GTFS CSV field is parsed, so each field picks the
+/// date/time format it actually needs instead of every deserializer
+/// hardcoding `%Y%m%d` and a fixed-width time parser.
+pub(super) enum GtfsConversion {
+    /// A calendar date, parsed with the given `strftime` format (GTFS
+    /// feeds use `%Y%m%d`).
+    Date(&'static str),
+    /// GTFS "service time": seconds since midnight, parsed from a
+    /// variable-width `H:MM:SS` string. When `allow_extended` is true,
+    /// hours may exceed 23, representing times after midnight on the
+    /// same service day (`25:30:00` is 1:30 AM the next calendar day) —
+    /// these must not be capped back into a 24-hour range.
+    ServiceTime { allow_extended: bool },
+    /// A timezone-aware timestamp, parsed with the given `strftime`
+    /// format.
+    TimestampTz(&'static str),
+}
+
+impl GtfsConversion {
+    fn parse_date(self, value: &str) -> Result<chrono::NaiveDate, Error> {
+        let Self::Date(format) = self else {
+            return Err(Error::InvalidData(format!(
+                "{value}: not a date conversion"
+            )));
+        };
+        chrono::NaiveDate::parse_from_str(value, format)
+            .map_err(|e| Error::InvalidData(format!("{value}: {e}")))
     }
 
-    Err(Error::InvalidTimeFormat(time_str.to_string()))
+    fn parse_service_time(self, value: &str) -> Result<u32, Error> {
+        let Self::ServiceTime { allow_extended } = self else {
+            return Err(Error::InvalidTimeFormat(value.to_string()));
+        };
+
+        let value = value.trim();
+        let mut parts = value.splitn(4, ':');
+        let (Some(h), Some(m), Some(s), None) =
+            (parts.next(), parts.next(), parts.next(), parts.next())
+        else {
+            return Err(Error::InvalidTimeFormat(value.to_string()));
+        };
+        let invalid = || Error::InvalidTimeFormat(value.to_string());
+        let hours: u32 = h.parse().map_err(|_| invalid())?;
+        let minutes: u32 = m.parse().map_err(|_| invalid())?;
+        let seconds: u32 = s.parse().map_err(|_| invalid())?;
+        if minutes >= 60 || seconds >= 60 {
+            return Err(invalid());
+        }
+        if !allow_extended && hours >= 24 {
+            return Err(invalid());
+        }
+        Ok(hours * 3600 + minutes * 60 + seconds)
+    }
+
+    #[allow(dead_code)]
+    fn parse_timestamp_tz(
+        self,
+        value: &str,
+    ) -> Result<chrono::DateTime<chrono::FixedOffset>, Error> {
+        let Self::TimestampTz(format) = self else {
+            return Err(Error::InvalidData(format!(
+                "{value}: not a timestamp-tz conversion"
+            )));
+        };
+        chrono::DateTime::parse_from_str(value, format)
+            .map_err(|e| Error::InvalidData(format!("{value}: {e}")))
+    }
 }
 
 pub(super) fn deserialize_gtfs_date<'de, D>(
@@ -56,7 +175,8 @@ where
     if date_str.is_empty() {
         Ok(None)
     } else {
-        chrono::NaiveDate::parse_from_str(&date_str, "%Y%m%d")
+        GtfsConversion::Date("%Y%m%d")
+            .parse_date(&date_str)
             .map(Some)
             .map_err(serde::de::Error::custom)
     }
@@ -67,5 +187,9 @@ where
     D: serde::Deserializer<'de>,
 {
     let time_str = String::deserialize(deserializer)?;
-    parse_time(&time_str).map_err(serde::de::Error::custom)
+    GtfsConversion::ServiceTime {
+        allow_extended: true,
+    }
+    .parse_service_time(&time_str)
+    .map_err(serde::de::Error::custom)
 }
@@ -1,14 +1,16 @@
 use chrono::{Datelike, Weekday};
-use geo::Point;
+use geo::{Distance, Haversine, Point};
 use hashbrown::{HashMap, HashSet};
 
 use super::{
     de::deserialize_gtfs_file,
-    raw_types::{FeedCalendarDates, FeedInfo, FeedService, FeedStop, FeedStopTime, FeedTrip},
+    raw_types::{
+        FeedCalendarDates, FeedInfo, FeedService, FeedShape, FeedStop, FeedStopTime, FeedTrip,
+    },
 };
 use crate::{
     Error, RaptorStopId, RouteId,
-    model::{PublicTransitData, Route, Stop, StopTime, Trip},
+    model::{LocationType, PublicTransitData, Route, ShapePoint, Stop, StopTime, Trip},
 };
 use crate::{loading::config::TransitModelConfig, model::FeedMeta};
 
@@ -27,6 +29,7 @@ struct RawGTFSData {
     services: Vec<FeedService>,
     feed_info: Vec<FeedInfo>,
     calendar_dates: Vec<FeedCalendarDates>,
+    shapes: Vec<FeedShape>,
 }
 
 fn load_raw_feed(config: &TransitModelConfig) -> Result<RawGTFSData, Error> {
@@ -36,6 +39,7 @@ fn load_raw_feed(config: &TransitModelConfig) -> Result<RawGTFSData, Error> {
     let mut services = Vec::new();
     let mut feed_info = Vec::new();
     let mut calendar_dates = Vec::new();
+    let mut shapes = Vec::new();
 
     for dir in &config.gtfs_dirs {
         stops.extend(deserialize_gtfs_file(&dir.join("stops.txt"))?);
@@ -45,12 +49,14 @@ fn load_raw_feed(config: &TransitModelConfig) -> Result<RawGTFSData, Error> {
         feed_info.extend(deserialize_gtfs_file(&dir.join("feed_info.txt")).unwrap_or_default());
         calendar_dates
             .extend(deserialize_gtfs_file(&dir.join("calendar_dates.txt")).unwrap_or_default());
+        shapes.extend(deserialize_gtfs_file(&dir.join("shapes.txt")).unwrap_or_default());
     }
 
     stops.shrink_to_fit();
     trips.shrink_to_fit();
     stop_times.shrink_to_fit();
     services.shrink_to_fit();
+    shapes.shrink_to_fit();
 
     Ok(RawGTFSData {
         stops,
@@ -59,6 +65,7 @@ fn load_raw_feed(config: &TransitModelConfig) -> Result<RawGTFSData, Error> {
         services,
         feed_info,
         calendar_dates,
+        shapes,
     })
 }
 
@@ -67,6 +74,7 @@ struct FilteredGTFSData {
     trips: Vec<FeedTrip>,
     stop_times: Vec<FeedStopTime>,
     feeds_meta: Vec<FeedMeta>,
+    shapes: Vec<FeedShape>,
 }
 
 fn filter_data_by_date(config: &TransitModelConfig, mut raw_data: RawGTFSData) -> FilteredGTFSData {
@@ -98,6 +106,7 @@ fn filter_data_by_date(config: &TransitModelConfig, mut raw_data: RawGTFSData) -
         trips: raw_data.trips,
         stop_times: raw_data.stop_times,
         feeds_meta,
+        shapes: raw_data.shapes,
     }
 }
 
@@ -170,6 +179,7 @@ fn process_transit_data(filtered_data: FilteredGTFSData) -> ProcessedTransitData
     let (stop_times, route_stops, routes, trips) =
         process_trip_stop_times(&filtered_data.stops, &filtered_data.trips, &trip_stop_times);
     let stops = create_stops_vector(filtered_data.stops);
+    let shapes = build_shapes(filtered_data.shapes);
 
     ProcessedTransitData {
         stop_times,
@@ -178,6 +188,7 @@ fn process_transit_data(filtered_data: FilteredGTFSData) -> ProcessedTransitData
         trips,
         stops,
         feeds_meta: filtered_data.feeds_meta,
+        shapes,
     }
 }
 
@@ -188,6 +199,45 @@ struct ProcessedTransitData {
     trips: Vec<Vec<Trip>>,
     stops: Vec<Stop>,
     feeds_meta: Vec<FeedMeta>,
+    shapes: HashMap<String, Vec<ShapePoint>>,
+}
+
+/// Groups raw `shapes.txt` rows by `shape_id`, sorts each group by
+/// `shape_pt_sequence`, and derives a cumulative `dist_traveled` for points
+/// whose feed omits `shape_dist_traveled`.
+fn build_shapes(shapes: Vec<FeedShape>) -> HashMap<String, Vec<ShapePoint>> {
+    let mut grouped: HashMap<String, Vec<FeedShape>> = HashMap::new();
+    for shape in shapes {
+        grouped.entry(shape.shape_id.clone()).or_default().push(shape);
+    }
+
+    grouped
+        .into_iter()
+        .map(|(shape_id, mut points)| {
+            points.sort_by_key(|p| p.shape_pt_sequence);
+
+            let mut cumulative = 0.0;
+            let mut prev: Option<Point> = None;
+            let points = points
+                .into_iter()
+                .map(|p| {
+                    let geometry = Point::new(p.shape_pt_lon, p.shape_pt_lat);
+                    let dist_traveled = p.shape_dist_traveled.unwrap_or_else(|| {
+                        if let Some(prev) = prev {
+                            cumulative += Haversine.distance(prev, geometry);
+                        }
+                        cumulative
+                    });
+                    prev = Some(geometry);
+                    ShapePoint {
+                        geometry,
+                        dist_traveled,
+                    }
+                })
+                .collect();
+            (shape_id, points)
+        })
+        .collect()
 }
 
 fn group_stop_times_by_trip(stop_times: Vec<FeedStopTime>) -> HashMap<String, Vec<FeedStopTime>> {
@@ -241,6 +291,7 @@ fn build_public_transit_data(processed_data: ProcessedTransitData) -> PublicTran
         node_to_stop: HashMap::new(),
         feeds_meta: processed_data.feeds_meta,
         trips: processed_data.trips,
+        shapes: processed_data.shapes,
     }
 }
 
@@ -268,6 +319,7 @@ fn build_route_trips(
             let trip_id = &trip_stop_times[0].trip_id;
             trip_data_map.get(trip_id.as_str()).map(|trip_data| Trip {
                 trip_id: trip_data.trip_id.clone(),
+                shape_id: (!trip_data.shape_id.is_empty()).then(|| trip_data.shape_id.clone()),
             })
         })
         .collect()
@@ -394,11 +446,33 @@ fn process_trip_stop_times<'a>(
 }
 
 fn create_stops_vector(stops: Vec<FeedStop>) -> Vec<Stop> {
+    let stop_id_map: HashMap<&str, usize> = stops
+        .iter()
+        .enumerate()
+        .map(|(i, s)| (s.stop_id.as_str(), i))
+        .collect();
+
+    // Resolve parent station ids up front so `Stop::parent_station` is a direct
+    // index into the final stops array rather than a raw GTFS id string.
+    let parent_indices: Vec<Option<usize>> = stops
+        .iter()
+        .map(|s| {
+            if s.parent_station.is_empty() {
+                None
+            } else {
+                stop_id_map.get(s.parent_station.as_str()).copied()
+            }
+        })
+        .collect();
+
     stops
         .into_iter()
-        .map(|s| Stop {
+        .zip(parent_indices)
+        .map(|(s, parent_station)| Stop {
             stop_id: s.stop_id,
             geometry: Point::new(s.stop_lon, s.stop_lat),
+            location_type: LocationType::from_gtfs(&s.location_type),
+            parent_station,
             routes_start: 0,
             routes_len: 0,
             transfers_start: 0,
@@ -31,6 +31,16 @@ pub struct FeedTrip {
     pub wheelchair_accessible: String,
 }
 
+#[derive(Debug, Deserialize, Default)]
+#[serde(default)]
+pub struct FeedShape {
+    pub shape_id: String,
+    pub shape_pt_lat: f64,
+    pub shape_pt_lon: f64,
+    pub shape_pt_sequence: u32,
+    pub shape_dist_traveled: Option<f64>,
+}
+
 #[derive(Debug, Deserialize, Default)]
 #[serde(default)]
 pub struct FeedRoute {
@@ -98,6 +98,13 @@ pub(crate) fn calculate_transfers(graph: &mut TransitModel) -> Result<(), Error>
         transfer_indices.insert(source_idx, (start_idx, count));
     }
 
+    merge_station_transfers(
+        transit_data,
+        &mut transfers,
+        &mut transfer_indices,
+        graph.meta.default_station_transfer_time,
+    );
+
     for (stop_id, (start, count)) in &transfer_indices {
         transit_data.stops[*stop_id].transfers_start = *start;
         transit_data.stops[*stop_id].transfers_len = *count;
@@ -111,3 +118,56 @@ pub(crate) fn calculate_transfers(graph: &mut TransitModel) -> Result<(), Error>
 
     Ok(())
 }
+
+/// Adds walk transfers between sibling platforms/entrances of the same parent
+/// station, using `default_station_transfer_time` wherever the street-network
+/// search above didn't already produce a transfer between them. A street-network
+/// transfer that exists is kept as is, whatever its duration.
+fn merge_station_transfers(
+    transit_data: &super::super::model::PublicTransitData,
+    transfers: &mut Vec<Transfer>,
+    transfer_indices: &mut HashMap<RaptorStopId, (usize, usize)>,
+    default_station_transfer_time: Time,
+) {
+    for siblings in transit_data.station_groups().values() {
+        for &from in siblings {
+            let (start, count) = transfer_indices.get(&from).copied().unwrap_or((0, 0));
+            let existing_targets: std::collections::HashSet<RaptorStopId> = transfers
+                [start..start + count]
+                .iter()
+                .map(|t| t.target_stop)
+                .collect();
+
+            let mut new_transfers = Vec::new();
+            for &to in siblings {
+                if to == from || existing_targets.contains(&to) {
+                    continue;
+                }
+                new_transfers.push(Transfer {
+                    target_stop: to,
+                    duration: default_station_transfer_time,
+                });
+            }
+
+            if new_transfers.is_empty() {
+                continue;
+            }
+
+            if count == 0 {
+                let new_start = transfers.len();
+                let new_count = new_transfers.len();
+                transfers.extend(new_transfers);
+                transfer_indices.insert(from, (new_start, new_count));
+            } else {
+                // Existing transfers for this stop are not necessarily at the
+                // tail of the vector, so relocate them alongside the new ones.
+                let mut combined: Vec<Transfer> = transfers[start..start + count].to_vec();
+                combined.extend(new_transfers);
+                let new_start = transfers.len();
+                let new_count = combined.len();
+                transfers.extend(combined);
+                transfer_indices.insert(from, (new_start, new_count));
+            }
+        }
+    }
+}
@@ -16,5 +16,6 @@ pub use transit_model::{TransitModel, TransitModelMeta, TransitPoint};
 pub use streets::StreetGraph;
 pub use transit::data::PublicTransitData;
 pub use transit::types::{
-    FeedMeta, RaptorStopId, Route, RouteId, Stop, StopTime, Time, Transfer, Trip,
+    FeedMeta, LocationType, RaptorStopId, Route, RouteId, ShapePoint, Stop, StopTime, Time,
+    Transfer, Trip,
 };
@@ -1,6 +1,6 @@
 //! Public transit data structure and methods to work with it
 
-use super::types::{FeedMeta, Route, Stop, StopTime, Transfer};
+use super::types::{FeedMeta, Route, ShapePoint, Stop, StopTime, Transfer};
 use crate::{
     model::transit::types::Trip,
     types::{RaptorStopId, RouteId},
@@ -30,6 +30,8 @@ pub struct PublicTransitData {
     pub feeds_meta: Vec<FeedMeta>,
     /// Trip IDs for each trip (indexed by route, then trip index)
     pub trips: Vec<Vec<Trip>>,
+    /// GTFS shape polylines (from `shapes.txt`), keyed by `shape_id`
+    pub shapes: HashMap<String, Vec<ShapePoint>>,
 }
 
 impl PublicTransitData {
@@ -61,6 +63,36 @@ impl PublicTransitData {
         }
     }
 
+    /// Returns the shape id and polyline vertices GTFS associates with a
+    /// trip, if the feed provides one in `shapes.txt`
+    pub fn trip_shape(&self, route_id: RouteId, trip_id: &str) -> Option<(&str, &[ShapePoint])> {
+        let trip = self
+            .trips
+            .get(route_id)?
+            .iter()
+            .find(|trip| trip.trip_id == trip_id)?;
+        let shape_id = trip.shape_id.as_deref()?;
+        self.shapes
+            .get(shape_id)
+            .map(|points| (shape_id, points.as_slice()))
+    }
+
+    /// Returns the index of a trip within `route_id`'s trip list, looked up
+    /// by its GTFS `trip_id`.
+    pub(crate) fn find_trip_idx(&self, route_id: RouteId, trip_id: &str) -> Option<usize> {
+        self.trips
+            .get(route_id)?
+            .iter()
+            .position(|trip| trip.trip_id == trip_id)
+    }
+
+    /// Scheduled arrival/departure times for a trip, aligned index-for-index
+    /// with `get_route_stops(route_id)`, looked up by GTFS `trip_id`.
+    pub(crate) fn trip_schedule(&self, route_id: RouteId, trip_id: &str) -> Option<&[StopTime]> {
+        let trip_idx = self.find_trip_idx(route_id, trip_id)?;
+        self.get_trip(route_id, trip_idx).ok()
+    }
+
     /// Get the real trip ID from route and trip index
     pub(crate) fn get_trip_id(&self, route_id: RouteId, trip_idx: usize) -> Option<&str> {
         self.trips
@@ -68,4 +100,16 @@ impl PublicTransitData {
             .and_then(|trips| trips.get(trip_idx))
             .map(|trip| trip.trip_id.as_str())
     }
+
+    /// Groups every stop that has a `parent_station` by that parent, giving the
+    /// set of sibling platforms/entrances belonging to each station.
+    pub(crate) fn station_groups(&self) -> HashMap<RaptorStopId, Vec<RaptorStopId>> {
+        let mut groups: HashMap<RaptorStopId, Vec<RaptorStopId>> = HashMap::new();
+        for (stop_idx, stop) in self.stops.iter().enumerate() {
+            if let Some(parent) = stop.parent_station {
+                groups.entry(parent).or_default().push(stop_idx);
+            }
+        }
+        groups
+    }
 }
@@ -31,6 +31,29 @@ pub struct Route {
     pub route_id: String,
 }
 
+/// GTFS `location_type` of a stop, as defined in `stops.txt`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LocationType {
+    /// A location where passengers board or disembark (GTFS code 0, the default)
+    #[default]
+    Stop,
+    /// A physical structure grouping one or more platforms/stops (GTFS code 1)
+    Station,
+    /// An entrance/exit to a station (GTFS code 2)
+    Entrance,
+}
+
+impl LocationType {
+    /// Parse the raw `location_type` column, defaulting to `Stop` for empty/unknown values
+    pub(crate) fn from_gtfs(raw: &str) -> Self {
+        match raw {
+            "1" => Self::Station,
+            "2" => Self::Entrance,
+            _ => Self::Stop,
+        }
+    }
+}
+
 /// Public transport stop
 #[derive(Debug, Clone)]
 pub struct Stop {
@@ -38,6 +61,10 @@ pub struct Stop {
     pub stop_id: String,
     /// Geographic coordinates of the stop
     pub geometry: Point<f64>,
+    /// GTFS station-hierarchy role of this stop
+    pub location_type: LocationType,
+    /// Parent station, if this stop is a platform/entrance belonging to one
+    pub parent_station: Option<RaptorStopId>,
     /// Index of the start of the route list in the general array
     pub(crate) routes_start: usize,
     ///) Number of routes through the stop
@@ -62,4 +89,17 @@ pub struct Transfer {
 #[derive(Debug, Clone)]
 pub struct Trip {
     pub trip_id: String,
+    /// GTFS `shape_id`, if the trip references a shape in `shapes.txt`
+    pub shape_id: Option<String>,
+}
+
+/// A single vertex of a GTFS shape polyline
+#[derive(Debug, Clone, Copy)]
+pub struct ShapePoint {
+    /// Geographic coordinates of the vertex
+    pub geometry: Point<f64>,
+    /// Cumulative distance traveled along the shape up to this point.
+    /// Taken from `shape_dist_traveled` when the feed provides it, otherwise
+    /// accumulated as the straight-line distance between consecutive points.
+    pub dist_traveled: f64,
 }
@@ -4,7 +4,9 @@ use hashbrown::HashMap;
 use petgraph::graph::NodeIndex;
 
 use crate::model::streets::IndexedPoint;
-use crate::{Error, RaptorStopId, Time, routing::dijkstra::dijkstra_path_weights};
+use crate::{
+    Error, RaptorStopId, Time, routing::RoutingAlgorithm, routing::dijkstra::dijkstra_path_weights,
+};
 use crate::{model::streets::StreetGraph, model::transit::data::PublicTransitData};
 use rstar::RTree;
 
@@ -21,6 +23,11 @@ pub struct TransitModel {
 #[derive(Debug)]
 pub struct TransitModelMeta {
     pub max_transfer_time: Time,
+    /// Walk transfer time (seconds) synthesized between sibling platforms of
+    /// the same parent station when the feed gives none explicitly
+    pub default_station_transfer_time: Time,
+    /// Routing engine used to answer transit queries against this model
+    pub algorithm: RoutingAlgorithm,
 }
 
 impl TransitModel {
@@ -215,6 +222,7 @@ mod tests {
             node_to_stop: HashMap::new(),
             feeds_meta: vec![],
             trips: vec![],
+            shapes: HashMap::new(),
         };
 
         // Map nodes to stops
@@ -226,6 +234,8 @@ mod tests {
             street_graph: street_network,
             meta: TransitModelMeta {
                 max_transfer_time: 1800, // 30 minutes
+                default_station_transfer_time: 180,
+                algorithm: RoutingAlgorithm::default(),
             },
         }
     }
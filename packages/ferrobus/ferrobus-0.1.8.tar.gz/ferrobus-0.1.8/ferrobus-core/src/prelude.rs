@@ -1,8 +1,11 @@
 pub use crate::MAX_CANDIDATE_STOPS;
 
-pub use crate::algo::{IsochroneIndex, calculate_isochrone};
+pub use crate::algo::{
+    IsochroneBand, IsochroneIndex, Reachability, ReachedPoint, calculate_isochrone, isochrone,
+};
 pub use crate::loading::{TransitModelConfig, create_transit_model};
 pub use crate::model::{PublicTransitData, TransitModel, TransitPoint};
+pub use crate::routing::RoutingAlgorithm;
 pub use crate::routing::multimodal_routing::{
     MultiModalResult, multimodal_routing, multimodal_routing_one_to_many,
 };
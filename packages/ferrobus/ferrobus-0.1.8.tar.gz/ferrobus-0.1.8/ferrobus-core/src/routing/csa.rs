@@ -0,0 +1,279 @@
+//! Connection Scan Algorithm (CSA) routing backend
+//!
+//! An alternative to RAPTOR for earliest-arrival queries. Every scheduled hop
+//! between two consecutive stops on a trip is flattened into a single
+//! `dep_time`-sorted array of [`Connection`]s and scanned once; because the
+//! scan settles every reachable stop along the way, the one-to-many case
+//! (`target: None`) costs no more than a single-target query, which makes
+//! this a better fit than RAPTOR for large one-to-many and range queries.
+
+use crate::model::Transfer;
+use crate::routing::raptor::{RaptorError, RaptorResult, TargetResult, validate_raptor_inputs};
+use crate::{PublicTransitData, RaptorStopId, Time};
+
+/// A single scheduled hop between two consecutive stops on a trip
+#[derive(Debug, Clone, Copy)]
+struct Connection {
+    dep_stop: RaptorStopId,
+    arr_stop: RaptorStopId,
+    dep_time: Time,
+    arr_time: Time,
+    /// Flat trip id, unique across all routes
+    trip: usize,
+}
+
+/// Flattened, `dep_time`-sorted connections array plus the trip count it was
+/// built from (so callers can size per-trip tracking arrays)
+struct ConnectionIndex {
+    connections: Vec<Connection>,
+    trip_count: usize,
+}
+
+/// Flattens every trip of every route into a single array of connections,
+/// sorted ascending by departure time
+fn build_connection_index(data: &PublicTransitData) -> ConnectionIndex {
+    let mut trip_offsets = Vec::with_capacity(data.routes.len());
+    let mut trip_count = 0;
+    for route in &data.routes {
+        trip_offsets.push(trip_count);
+        trip_count += route.num_trips;
+    }
+
+    let mut connections = Vec::new();
+    for (route_id, route) in data.routes.iter().enumerate() {
+        let Ok(stops) = data.get_route_stops(route_id) else {
+            continue;
+        };
+        for trip_idx in 0..route.num_trips {
+            let Ok(trip) = data.get_trip(route_id, trip_idx) else {
+                continue;
+            };
+            let flat_trip = trip_offsets[route_id] + trip_idx;
+            for i in 0..stops.len().saturating_sub(1) {
+                connections.push(Connection {
+                    dep_stop: stops[i],
+                    arr_stop: stops[i + 1],
+                    dep_time: trip[i].departure,
+                    arr_time: trip[i + 1].arrival,
+                    trip: flat_trip,
+                });
+            }
+        }
+    }
+
+    connections.sort_unstable_by_key(|c| c.dep_time);
+    ConnectionIndex {
+        connections,
+        trip_count,
+    }
+}
+
+/// Relaxes the direct footpaths out of `stop`, carrying forward `rounds` (the
+/// number of trips boarded so far) unchanged, since a footpath is not a trip
+fn relax_footpaths(
+    data: &PublicTransitData,
+    stop: RaptorStopId,
+    arrival: Time,
+    rounds: usize,
+    arr: &mut [Time],
+    transfers_used: &mut [usize],
+) -> Result<(), RaptorError> {
+    for &Transfer {
+        target_stop,
+        duration,
+        ..
+    } in data.get_stop_transfers(stop)?
+    {
+        let candidate = arrival.saturating_add(duration);
+        if candidate < arr[target_stop] {
+            arr[target_stop] = candidate;
+            transfers_used[target_stop] = rounds;
+        }
+    }
+    Ok(())
+}
+
+/// Runs a single earliest-arrival Connection Scan from `source`, settling the
+/// whole network in one pass.
+///
+/// This is the CSA counterpart to [`crate::routing::raptor::raptor`] and
+/// returns the same [`RaptorResult`], so callers can pick whichever engine
+/// suits their query shape without touching downstream code.
+///
+/// `max_transfers` caps the number of trips boarded: a
+/// trip is only boarded if doing so would keep the boarding count at or
+/// below `max_transfers + 1`.
+pub fn csa(
+    data: &PublicTransitData,
+    source: RaptorStopId,
+    target: Option<RaptorStopId>,
+    departure_time: Time,
+    max_transfers: usize,
+) -> Result<RaptorResult, RaptorError> {
+    validate_raptor_inputs(data, source, target, departure_time)?;
+
+    let max_rounds = max_transfers + 1;
+    let num_stops = data.stops.len();
+    let index = build_connection_index(data);
+
+    let mut arr = vec![Time::MAX; num_stops];
+    let mut transfers_used = vec![0usize; num_stops];
+    let mut trip_reached = vec![false; index.trip_count];
+    let mut trip_rounds = vec![0usize; index.trip_count];
+
+    arr[source] = departure_time;
+    relax_footpaths(
+        data,
+        source,
+        departure_time,
+        0,
+        &mut arr,
+        &mut transfers_used,
+    )?;
+
+    for conn in &index.connections {
+        if !trip_reached[conn.trip] {
+            if conn.dep_time < arr[conn.dep_stop] {
+                // Not reachable yet, and this trip hasn't been boarded before.
+                continue;
+            }
+            let boarding_round = transfers_used[conn.dep_stop] + 1;
+            if boarding_round > max_rounds {
+                // Boarding this trip here would exceed `max_transfers`; skip it. A later
+                // connection on the same trip may still become reachable within budget, e.g. via
+                // a stop reached through foot-paths from elsewhere.
+                continue;
+            }
+            trip_reached[conn.trip] = true;
+            trip_rounds[conn.trip] = boarding_round;
+        }
+
+        if conn.arr_time < arr[conn.arr_stop] {
+            arr[conn.arr_stop] = conn.arr_time;
+            transfers_used[conn.arr_stop] = trip_rounds[conn.trip];
+            relax_footpaths(
+                data,
+                conn.arr_stop,
+                conn.arr_time,
+                trip_rounds[conn.trip],
+                &mut arr,
+                &mut transfers_used,
+            )?;
+        }
+    }
+
+    if let Some(target_stop) = target {
+        Ok(RaptorResult::SingleTarget(TargetResult {
+            arrival_time: arr[target_stop],
+            transfers_used: transfers_used[target_stop],
+        }))
+    } else {
+        Ok(RaptorResult::AllTargets(
+            arr.iter()
+                .zip(transfers_used.iter())
+                .map(|(&arrival_time, &transfers_used)| TargetResult {
+                    arrival_time,
+                    transfers_used,
+                })
+                .collect(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::transit::types::{Route, Stop, Trip};
+    use hashbrown::HashMap;
+
+    /// Two routes, each a single trip with a single hop, chained through a
+    /// middle stop: `0 --routeA--> 1 --routeB--> 2`. Reaching stop 2 requires
+    /// boarding two trips, so it exercises the `max_transfers` cap.
+    fn create_two_leg_network() -> PublicTransitData {
+        let stop = |stop_id: &str| Stop {
+            stop_id: stop_id.to_string(),
+            geometry: geo::Point::new(0.0, 0.0),
+            location_type: Default::default(),
+            parent_station: None,
+            routes_start: 0,
+            routes_len: 0,
+            transfers_start: 0,
+            transfers_len: 0,
+        };
+
+        PublicTransitData {
+            routes: vec![
+                Route {
+                    num_trips: 1,
+                    num_stops: 2,
+                    stops_start: 0,
+                    trips_start: 0,
+                    route_id: "A".to_string(),
+                },
+                Route {
+                    num_trips: 1,
+                    num_stops: 2,
+                    stops_start: 2,
+                    trips_start: 2,
+                    route_id: "B".to_string(),
+                },
+            ],
+            route_stops: vec![0, 1, 1, 2],
+            stop_times: vec![
+                StopTime {
+                    arrival: 0,
+                    departure: 0,
+                },
+                StopTime {
+                    arrival: 100,
+                    departure: 100,
+                },
+                StopTime {
+                    arrival: 200,
+                    departure: 200,
+                },
+                StopTime {
+                    arrival: 300,
+                    departure: 300,
+                },
+            ],
+            stops: vec![stop("0"), stop("1"), stop("2")],
+            stop_routes: vec![],
+            transfers: vec![],
+            node_to_stop: HashMap::new(),
+            feeds_meta: vec![],
+            trips: vec![
+                vec![Trip {
+                    trip_id: "a1".to_string(),
+                    shape_id: None,
+                }],
+                vec![Trip {
+                    trip_id: "b1".to_string(),
+                    shape_id: None,
+                }],
+            ],
+            shapes: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn max_transfers_zero_cannot_board_a_second_trip() {
+        let data = create_two_leg_network();
+        let result = csa(&data, 0, Some(2), 0, 0).unwrap();
+        let RaptorResult::SingleTarget(target) = result else {
+            panic!("expected a single-target result");
+        };
+        assert!(!target.is_reachable());
+    }
+
+    #[test]
+    fn max_transfers_one_allows_the_second_trip() {
+        let data = create_two_leg_network();
+        let result = csa(&data, 0, Some(2), 0, 1).unwrap();
+        let RaptorResult::SingleTarget(target) = result else {
+            panic!("expected a single-target result");
+        };
+        assert_eq!(target.arrival_time, 300);
+        assert_eq!(target.transfers_used, 2);
+    }
+}
@@ -4,6 +4,7 @@ mod to_geojson;
 
 pub use detailed_journey::DetailedJourney;
 pub use journey_leg::WalkingLeg;
+pub use to_geojson::GeoJsonOptions;
 
 use crate::{
     Error, MAX_CANDIDATE_STOPS, RaptorStopId, Time, TransitModel,
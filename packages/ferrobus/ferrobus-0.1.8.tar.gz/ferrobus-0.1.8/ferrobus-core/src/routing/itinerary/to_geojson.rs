@@ -1,43 +1,117 @@
-use geo::{Coord, LineString, line_string};
+use std::collections::HashMap;
+
+use geo::{Coord, Distance, Haversine, LineString, Point, line_string};
 use geojson::{Feature, FeatureCollection, Geometry};
 use serde_json::json;
 
 use crate::{
-    Error, PublicTransitData, RaptorStopId, TransitModel,
+    Error, PublicTransitData, RaptorStopId, RouteId, Time, TransitModel,
+    model::ShapePoint,
     routing::{dijkstra::dijkstra_paths, raptor::JourneyLeg},
 };
 
 use super::DetailedJourney;
 
+/// Options controlling what [`DetailedJourney::to_geojson_with_options`] adds
+/// on top of the plain per-leg `LineString`/`Point` features.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct GeoJsonOptions {
+    /// Emit a `Point` feature for every boarded/alighted/intermediate stop
+    stop_points: bool,
+    /// Attach an itinerary summary to the `FeatureCollection`'s `foreign_members`
+    itinerary_summary: bool,
+}
+
+impl GeoJsonOptions {
+    /// Request a `Point` feature for every boarded/alighted/intermediate stop,
+    /// carrying `stop_name`, `stop_id`, scheduled `time`, and `leg_index`.
+    #[must_use]
+    pub fn with_stop_points(mut self) -> Self {
+        self.stop_points = true;
+        self
+    }
+
+    /// Request a top-level `foreign_members` itinerary summary (total
+    /// duration, number of transfers, per-mode time breakdown).
+    #[must_use]
+    pub fn with_itinerary_summary(mut self) -> Self {
+        self.itinerary_summary = true;
+        self
+    }
+}
+
 impl DetailedJourney {
     /// Converts the complete journey to a `GeoJSON` `FeatureCollection`.
     pub fn to_geojson(&self, transit_model: &TransitModel) -> FeatureCollection {
+        self.to_geojson_with_options(transit_model, GeoJsonOptions::default())
+    }
+
+    /// Converts the complete journey to a `GeoJSON` `FeatureCollection`,
+    /// optionally adding intermediate stop markers and an itinerary summary.
+    pub fn to_geojson_with_options(
+        &self,
+        transit_model: &TransitModel,
+        opts: GeoJsonOptions,
+    ) -> FeatureCollection {
         let transit_data = &transit_model.transit_data;
         let mut features = Vec::new();
+        let mut mode_durations: HashMap<&'static str, Time> = HashMap::new();
 
         if let Some(access) = &self.access_leg {
             features.push(access.to_feature("access_walk"));
+            *mode_durations.entry("walk").or_default() += access.duration;
         }
 
         if let Some(transit) = &self.transit_journey {
             for (idx, leg) in transit.legs.iter().enumerate() {
-                let feature = match leg {
-                    JourneyLeg::Transit { .. } => transit_leg_feature(transit_data, leg, idx),
-                    JourneyLeg::Transfer { .. } => transfer_leg_feature(transit_model, leg, idx),
-                    JourneyLeg::Waiting { .. } => waiting_leg_feature(transit_data, leg),
+                let (feature, mode, duration) = match leg {
+                    JourneyLeg::Transit {
+                        departure_time,
+                        arrival_time,
+                        ..
+                    } => (
+                        transit_leg_feature(transit_data, leg, idx),
+                        "transit",
+                        arrival_time - departure_time,
+                    ),
+                    JourneyLeg::Transfer { duration, .. } => {
+                        (transfer_leg_feature(transit_model, leg, idx), "transfer", *duration)
+                    }
+                    JourneyLeg::Waiting { duration, .. } => {
+                        (waiting_leg_feature(transit_data, leg, idx), "waiting", *duration)
+                    }
                 };
                 features.push(feature);
+                *mode_durations.entry(mode).or_default() += duration;
+
+                if opts.stop_points {
+                    features.extend(stop_point_features(transit_data, leg, idx));
+                }
             }
         }
 
         if let Some(egress) = &self.egress_leg {
             features.push(egress.to_feature("egress_walk"));
+            *mode_durations.entry("walk").or_default() += egress.duration;
         }
 
+        let foreign_members = opts.itinerary_summary.then(|| {
+            json!({
+                "itinerary_summary": {
+                    "total_duration": self.total_time,
+                    "transfers": self.transfers,
+                    "mode_durations": mode_durations,
+                }
+            })
+            .as_object()
+            .cloned()
+            .unwrap_or_default()
+        });
+
         FeatureCollection {
             features,
             bbox: None,
-            foreign_members: None,
+            foreign_members,
         }
     }
 
@@ -47,6 +121,101 @@ impl DetailedJourney {
     }
 }
 
+/// Builds a `Point` `Feature` for a single boarded/alighted/intermediate
+/// stop, carrying the properties a clickable map marker needs. `time` is
+/// `None` when the leg doesn't carry an absolute scheduled time for the stop.
+fn stop_point_feature(
+    transit_data: &PublicTransitData,
+    stop: RaptorStopId,
+    time: Option<Time>,
+    leg_index: usize,
+) -> Feature {
+    let point = transit_data.transit_stop_location(stop);
+    let value = json!({
+        "type": "Feature",
+        "geometry": Geometry::new((&point).into()),
+        "properties": {
+            "stop_id": transit_data.stops.get(stop).map(|s| s.stop_id.as_str()),
+            "stop_name": transit_data.transit_stop_name(stop),
+            "time": time,
+            "leg_index": leg_index,
+        }
+    });
+    Feature::from_json_value(value).expect("Failed to create feature from valid JSON")
+}
+
+/// Returns the stop markers a single journey leg contributes: the endpoints
+/// it boards/alights at, plus any intermediate stops on a transit leg whose
+/// schedule is known.
+fn stop_point_features(
+    transit_data: &PublicTransitData,
+    leg: &JourneyLeg,
+    leg_idx: usize,
+) -> Vec<Feature> {
+    match leg {
+        JourneyLeg::Transit {
+            route_id,
+            trip_id,
+            from_stop,
+            departure_time,
+            to_stop,
+            arrival_time,
+        } => {
+            let mut points = vec![stop_point_feature(
+                transit_data,
+                *from_stop,
+                Some(*departure_time),
+                leg_idx,
+            )];
+
+            if let Ok(route_stops) = transit_data.get_route_stops(*route_id)
+                && let Some(schedule) = transit_data.trip_schedule(*route_id, trip_id)
+                && let (Some(start_idx), Some(end_idx)) = (
+                    route_stops.iter().position(|&s| s == *from_stop),
+                    route_stops.iter().position(|&s| s == *to_stop),
+                )
+            {
+                let range: Vec<_> = if start_idx < end_idx {
+                    (start_idx + 1..end_idx).collect()
+                } else {
+                    (end_idx + 1..start_idx).rev().collect()
+                };
+                for idx in range {
+                    if let Some(stop_time) = schedule.get(idx) {
+                        points.push(stop_point_feature(
+                            transit_data,
+                            route_stops[idx],
+                            Some(stop_time.arrival),
+                            leg_idx,
+                        ));
+                    }
+                }
+            }
+
+            points.push(stop_point_feature(
+                transit_data,
+                *to_stop,
+                Some(*arrival_time),
+                leg_idx,
+            ));
+            points
+        }
+        JourneyLeg::Transfer {
+            from_stop,
+            departure_time,
+            to_stop,
+            arrival_time,
+            ..
+        } => vec![
+            stop_point_feature(transit_data, *from_stop, Some(*departure_time), leg_idx),
+            stop_point_feature(transit_data, *to_stop, Some(*arrival_time), leg_idx),
+        ],
+        JourneyLeg::Waiting { at_stop, .. } => {
+            vec![stop_point_feature(transit_data, *at_stop, None, leg_idx)]
+        }
+    }
+}
+
 fn transit_leg_feature(
     transit_data: &PublicTransitData,
     leg: &JourneyLeg,
@@ -69,25 +238,16 @@ fn transit_leg_feature(
             .unwrap_or_default();
         let to_name = transit_data.transit_stop_name(*to_stop).unwrap_or_default();
 
-        let mut coords: Vec<Coord<f64>> = vec![from_loc.into()];
-        if let Ok(route_stops) = transit_data.get_route_stops(*route_id)
-            && let (Some(start_idx), Some(end_idx)) = (
-                route_stops.iter().position(|&s| s == *from_stop),
-                route_stops.iter().position(|&s| s == *to_stop),
-            )
-        {
-            let range: Vec<_> = if start_idx < end_idx {
-                (start_idx + 1..end_idx).collect()
-            } else {
-                (end_idx + 1..start_idx).rev().collect()
-            };
-            for idx in range {
-                let stop_loc = transit_data.transit_stop_location(route_stops[idx]);
-                coords.push(stop_loc.into());
-            }
-        }
-        coords.push(to_loc.into());
-        let line: LineString<_> = coords.into();
+        let shape = transit_data.trip_shape(*route_id, trip_id);
+        let (line, shape_id) = match shape.and_then(|(shape_id, points)| {
+            shape_slice_between(points, from_loc, to_loc).map(|line| (line, shape_id))
+        }) {
+            Some((line, shape_id)) => (line, Some(shape_id)),
+            None => (
+                stop_chain_line(transit_data, *route_id, *from_stop, from_loc, *to_stop, to_loc),
+                None,
+            ),
+        };
 
         let value = json!({
             "type": "Feature",
@@ -97,6 +257,7 @@ fn transit_leg_feature(
                 "leg_index": leg_idx,
                 "route_id": transit_data.routes[*route_id].route_id,
                 "trip_id": trip_id,
+                "shape_id": shape_id,
                 "from_name": from_name,
                 "to_name": to_name,
                 "departure_time": departure_time,
@@ -111,6 +272,74 @@ fn transit_leg_feature(
     }
 }
 
+/// Reconstructs a transit leg's geometry by walking `get_route_stops` and
+/// connecting stop *locations* with straight segments; used when the trip
+/// has no usable `shapes.txt` geometry.
+fn stop_chain_line(
+    transit_data: &PublicTransitData,
+    route_id: RouteId,
+    from_stop: RaptorStopId,
+    from_loc: Point<f64>,
+    to_stop: RaptorStopId,
+    to_loc: Point<f64>,
+) -> LineString<f64> {
+    let mut coords: Vec<Coord<f64>> = vec![from_loc.into()];
+    if let Ok(route_stops) = transit_data.get_route_stops(route_id)
+        && let (Some(start_idx), Some(end_idx)) = (
+            route_stops.iter().position(|&s| s == from_stop),
+            route_stops.iter().position(|&s| s == to_stop),
+        )
+    {
+        let range: Vec<_> = if start_idx < end_idx {
+            (start_idx + 1..end_idx).collect()
+        } else {
+            (end_idx + 1..start_idx).rev().collect()
+        };
+        for idx in range {
+            let stop_loc = transit_data.transit_stop_location(route_stops[idx]);
+            coords.push(stop_loc.into());
+        }
+    }
+    coords.push(to_loc.into());
+    coords.into()
+}
+
+/// Slices a GTFS shape polyline between the points nearest to `from` and
+/// `to`, so a transit leg can carry the true ride geometry instead of a
+/// straight line between stops. Returns `None` when the shape has fewer
+/// than two points (nothing meaningful to slice).
+fn shape_slice_between(
+    shape: &[ShapePoint],
+    from: Point<f64>,
+    to: Point<f64>,
+) -> Option<LineString<f64>> {
+    if shape.len() < 2 {
+        return None;
+    }
+    let from_idx = nearest_shape_point_index(shape, from)?;
+    let to_idx = nearest_shape_point_index(shape, to)?;
+
+    let (start, end) = (from_idx.min(to_idx), from_idx.max(to_idx));
+    let mut coords: Vec<Coord<f64>> = shape[start..=end]
+        .iter()
+        .map(|point| point.geometry.into())
+        .collect();
+    if from_idx > to_idx {
+        coords.reverse();
+    }
+    Some(coords.into())
+}
+
+/// Index of the shape vertex nearest `target` by straight-line distance.
+fn nearest_shape_point_index(shape: &[ShapePoint], target: Point<f64>) -> Option<usize> {
+    shape
+        .iter()
+        .enumerate()
+        .map(|(idx, point)| (idx, Haversine.distance(target, point.geometry)))
+        .min_by(|(_, a), (_, b)| a.total_cmp(b))
+        .map(|(idx, _)| idx)
+}
+
 fn transfer_leg_feature(transit_model: &TransitModel, leg: &JourneyLeg, leg_idx: usize) -> Feature {
     if let JourneyLeg::Transfer {
         from_stop,
@@ -214,7 +443,11 @@ fn create_direct_line_geometry(
     Geometry::new((&direct_line).into())
 }
 
-fn waiting_leg_feature(transit_data: &PublicTransitData, leg: &JourneyLeg) -> Feature {
+fn waiting_leg_feature(
+    transit_data: &PublicTransitData,
+    leg: &JourneyLeg,
+    leg_idx: usize,
+) -> Feature {
     if let JourneyLeg::Waiting { at_stop, duration } = leg {
         let geom = transit_data.transit_stop_location(*at_stop);
         let value = json!({
@@ -222,6 +455,7 @@ fn waiting_leg_feature(transit_data: &PublicTransitData, leg: &JourneyLeg) -> Fe
             "geometry": Geometry::new((&geom).into()),
             "properties": {
                 "leg_type": "waiting",
+                "leg_index": leg_idx,
                 "duration": duration,
             }
         });
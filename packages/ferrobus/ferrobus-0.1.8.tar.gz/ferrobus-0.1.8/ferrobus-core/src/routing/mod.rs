@@ -1,6 +1,21 @@
+pub(crate) mod csa;
 pub(crate) mod dijkstra;
 pub(crate) mod raptor;
 
 pub mod itinerary;
 pub mod multimodal_routing;
 pub mod pareto;
+
+/// Which routing engine a [`crate::TransitModel`] uses to answer transit queries.
+///
+/// `Raptor` (the default) is a round-based label-setting search, well suited
+/// to single-source/single-target and one-to-many queries. `Csa` precomputes
+/// a departure-time-sorted connections array and settles every stop in one
+/// scan, which pays off for large one-to-many and range queries where
+/// rebuilding RAPTOR's round structure repeatedly would be wasted work.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RoutingAlgorithm {
+    #[default]
+    Raptor,
+    Csa,
+}
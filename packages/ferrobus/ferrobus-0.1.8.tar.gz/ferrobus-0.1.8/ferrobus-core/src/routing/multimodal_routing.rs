@@ -1,11 +1,31 @@
 use hashbrown::HashMap;
 
 use crate::{
-    Error, MAX_CANDIDATE_STOPS, Time, TransitModel,
+    Error, MAX_CANDIDATE_STOPS, PublicTransitData, RaptorStopId, Time, TransitModel,
     model::TransitPoint,
-    routing::raptor::{RaptorResult, raptor},
+    routing::RoutingAlgorithm,
+    routing::csa::csa,
+    routing::raptor::{RaptorError, RaptorResult, raptor},
 };
 
+/// Runs the earliest-arrival search with whichever engine the model is
+/// configured to use
+pub(crate) fn run_routing(
+    algorithm: RoutingAlgorithm,
+    transit_data: &PublicTransitData,
+    source: RaptorStopId,
+    target: Option<RaptorStopId>,
+    departure_time: Time,
+    max_transfers: usize,
+) -> Result<RaptorResult, RaptorError> {
+    match algorithm {
+        RoutingAlgorithm::Raptor => {
+            raptor(transit_data, source, target, departure_time, max_transfers)
+        }
+        RoutingAlgorithm::Csa => csa(transit_data, source, target, departure_time, max_transfers),
+    }
+}
+
 /// Combined multimodal route result
 #[derive(Debug, Clone)]
 pub struct MultiModalResult {
@@ -73,6 +93,7 @@ pub fn multimodal_routing(
         return Err(Error::InvalidData("Invalid departure time".to_string()));
     }
 
+    let algorithm = transit_data.meta.algorithm;
     let transit_data = &transit_data.transit_data;
     let direct_walking = start.walking_time_to(end);
 
@@ -94,7 +115,8 @@ pub fn multimodal_routing(
                 continue;
             }
 
-            if let Ok(result) = raptor(
+            if let Ok(result) = run_routing(
+                algorithm,
                 transit_data,
                 access_stop,
                 Some(egress_stop),
@@ -163,14 +185,16 @@ pub fn multimodal_routing_one_to_many(
     departure_time: Time,
     max_transfers: usize,
 ) -> Result<Vec<Option<MultiModalResult>>, Error> {
+    let algorithm = transit_data.meta.algorithm;
     let transit_data = &transit_data.transit_data;
     let mut results = vec![None; targets.len()];
 
-    // Run RAPTOR to all stops for each initial access point
+    // Run the configured routing engine to all stops for each initial access point
     let mut transit_results = HashMap::new();
 
     for &(access_stop, access_time) in start.nearest_stops.iter().take(MAX_CANDIDATE_STOPS) {
-        if let Ok(RaptorResult::AllTargets(times)) = raptor(
+        if let Ok(RaptorResult::AllTargets(times)) = run_routing(
+            algorithm,
             transit_data,
             access_stop,
             None,
@@ -7,7 +7,7 @@ mod regular;
 mod traced;
 
 // Re-export main interfaces
-pub(crate) use common::{RaptorError, RaptorResult};
+pub(crate) use common::{RaptorError, RaptorResult, TargetResult, validate_raptor_inputs};
 pub(crate) use range::{RaptorRangeJourney, rraptor};
 pub(crate) use regular::raptor;
 pub(crate) use traced::{Journey, JourneyLeg, TracedRaptorResult, traced_raptor};
@@ -7,6 +7,45 @@ use serde_json::Value;
 
 use crate::{Anchor, Draft, Error, Resolved, Resolver, Segments};
 
+/// A read-only view over a single node of a parsed JSON document.
+///
+/// This is a preparatory step only, not a completed decoupling. The one
+/// caller inside [`InnerResourcePtr::pointer`] that walks array/object
+/// segments now calls through this trait (via UFCS) instead of directly
+/// on `Value`, but `Value` is still the only type that implements it —
+/// there is no `simd-json` (or other) implementation yet. `Resource`,
+/// `ResourceRef`, `JsonSchemaResource`, and `InnerResourcePtr` itself are
+/// all still concretely typed over `serde_json::Value`; none of them is
+/// generic over `JsonDocument`. Making that so would also mean touching
+/// [`Draft::id_of`] and [`Draft::anchors`], which live in the draft
+/// module and aren't part of this crate subset.
+pub(crate) trait JsonDocument {
+    /// Borrow this node as an array, if it is one.
+    fn as_array(&self) -> Option<&[Self]>
+    where
+        Self: Sized;
+    /// Borrow the value at `key`, if this node is an object containing it.
+    fn get(&self, key: &str) -> Option<&Self>;
+    /// Iterate over this node's keys, if it is an object.
+    fn as_object_keys(&self) -> Option<Box<dyn Iterator<Item = &str> + '_>>;
+}
+
+impl JsonDocument for Value {
+    fn as_array(&self) -> Option<&[Value]> {
+        Value::as_array(self).map(Vec::as_slice)
+    }
+
+    fn get(&self, key: &str) -> Option<&Value> {
+        Value::get(self, key)
+    }
+
+    fn as_object_keys(&self) -> Option<Box<dyn Iterator<Item = &str> + '_>> {
+        Value::as_object(self).map(|map| {
+            Box::new(map.keys().map(String::as_str)) as Box<dyn Iterator<Item = &str> + '_>
+        })
+    }
+}
+
 pub(crate) trait JsonSchemaResource {
     fn contents(&self) -> &Value;
     fn draft(&self) -> Draft;
@@ -179,7 +218,7 @@ impl InnerResourcePtr {
             .decode_utf8()
             .map_err(|err| Error::invalid_percent_encoding(original_pointer, err))?;
         for segment in pointer.split('/') {
-            if let Some(array) = contents.as_array() {
+            if let Some(array) = JsonDocument::as_array(contents) {
                 let idx = segment
                     .parse::<usize>()
                     .map_err(|err| Error::invalid_array_index(original_pointer, segment, err))?;
@@ -191,7 +230,7 @@ impl InnerResourcePtr {
                 segments.push(idx);
             } else {
                 let segment = unescape_segment(segment);
-                if let Some(next) = contents.get(segment.as_ref()) {
+                if let Some(next) = JsonDocument::get(contents, segment.as_ref()) {
                     contents = next;
                 } else {
                     return Err(Error::pointer_to_nowhere(original_pointer));
@@ -211,6 +250,82 @@ impl InnerResourcePtr {
         }
         Ok(Resolved::new(contents, resolver, self.draft()))
     }
+
+    /// Resolves a Relative JSON Pointer — a string of the form
+    /// `<N><json-pointer>` or `<N>#` — against `base_pointer`, the
+    /// absolute RFC 6901 pointer (in the same form [`Self::pointer`]
+    /// accepts) describing where resolution currently stands. `N` is how
+    /// many levels to ascend from `base_pointer` before applying the
+    /// trailing `/`-pointer.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `N` has a disallowed leading zero, if `N`
+    /// exceeds the depth of `base_pointer`, or if the remaining pointer
+    /// doesn't resolve. The trailing `#` form — which per spec yields the
+    /// ancestor's own key or array index as a value, rather than
+    /// descending further — is also reported as an error here: answering
+    /// it would need [`Resolved`] to carry a synthesized value instead of
+    /// only borrowing into the document, and `Resolved` lives in the
+    /// resolver module, which this change doesn't touch.
+    pub(crate) fn relative_pointer<'r>(
+        &'r self,
+        base_pointer: &str,
+        relative: &str,
+        resolver: Resolver<'r>,
+    ) -> Result<Resolved<'r>, Error> {
+        let (levels, rest) = parse_relative_level(relative)
+            .ok_or_else(|| Error::pointer_to_nowhere(relative))?;
+
+        let base = base_pointer.strip_prefix('/').unwrap_or(base_pointer);
+        let components: Vec<&str> = if base.is_empty() {
+            Vec::new()
+        } else {
+            base.split('/').collect()
+        };
+        if levels > components.len() {
+            return Err(Error::pointer_to_nowhere(relative));
+        }
+        let ancestor = &components[..components.len() - levels];
+
+        if rest == "#" || rest.contains('#') || (!rest.is_empty() && !rest.starts_with('/')) {
+            return Err(Error::pointer_to_nowhere(relative));
+        }
+        if ancestor.is_empty() && rest.is_empty() {
+            return Ok(Resolved::new(self.contents(), resolver, self.draft()));
+        }
+
+        // `rest` is empty or already starts with `/` (checked above), so prefixing each
+        // ancestor component with `/` and leaving `ancestor.is_empty()` contribute nothing
+        // avoids the doubled leading slash that `format!("/{}{}", ancestor.join("/"), rest)`
+        // would produce when ascending all the way to the document root.
+        let mut absolute = String::new();
+        for component in ancestor {
+            absolute.push('/');
+            absolute.push_str(component);
+        }
+        absolute.push_str(rest);
+        self.pointer(&absolute, resolver)
+    }
+}
+
+/// Splits a Relative JSON Pointer into its leading ascent count and the
+/// trailing `/`-pointer or `#`. Rejects a missing count and a count with
+/// a disallowed leading zero (`01` is invalid; `0` is the only digit
+/// string allowed to start with `0`).
+fn parse_relative_level(token: &str) -> Option<(usize, &str)> {
+    let digit_end = token
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(token.len());
+    if digit_end == 0 {
+        return None;
+    }
+    let digits = &token[..digit_end];
+    if digits.len() > 1 && digits.starts_with('0') {
+        return None;
+    }
+    let levels = digits.parse().ok()?;
+    Some((levels, &token[digit_end..]))
 }
 
 impl JsonSchemaResource for InnerResourcePtr {
@@ -406,6 +521,69 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_relative_pointer_ascend_to_root() {
+        let value = Arc::pin(json!({
+            "properties": {
+                "foo": {"type": "string"},
+                "bar": {"type": "array"}
+            }
+        }));
+        let ptr = InnerResourcePtr::new(std::ptr::addr_of!(*value), Draft::Draft202012);
+        let registry = create_test_registry();
+        let resolver = registry
+            .try_resolver("http://example.com")
+            .expect("Invalid base URI");
+
+        // From `/properties/foo`, ascending 2 levels reaches the document root, then
+        // `/properties/bar` descends back down from there. Regression test for a path-join
+        // bug that produced `//properties/bar` (and thus a lookup failure) in this case.
+        let resolved = ptr
+            .relative_pointer("/properties/foo", "2/properties/bar", resolver)
+            .expect("Lookup failed");
+        assert_eq!(resolved.contents(), &json!({"type": "array"}));
+    }
+
+    #[test]
+    fn test_relative_pointer_ascend_partial() {
+        let value = Arc::pin(json!({
+            "properties": {
+                "foo": {"type": "string"},
+                "bar": {"type": "array"}
+            }
+        }));
+        let ptr = InnerResourcePtr::new(std::ptr::addr_of!(*value), Draft::Draft202012);
+        let registry = create_test_registry();
+        let resolver = registry
+            .try_resolver("http://example.com")
+            .expect("Invalid base URI");
+
+        // From `/properties/foo/type`, ascending 1 level lands on `/properties/foo`, then
+        // `/type` descends back down to where it started.
+        let resolved = ptr
+            .relative_pointer("/properties/foo/type", "1/type", resolver)
+            .expect("Lookup failed");
+        assert_eq!(resolved.contents(), &json!("string"));
+    }
+
+    #[test]
+    fn test_relative_pointer_n_exceeds_depth() {
+        let value = Arc::pin(json!({
+            "properties": {
+                "foo": {"type": "string"}
+            }
+        }));
+        let ptr = InnerResourcePtr::new(std::ptr::addr_of!(*value), Draft::Draft202012);
+        let registry = create_test_registry();
+        let resolver = registry
+            .try_resolver("http://example.com")
+            .expect("Invalid base URI");
+
+        // `/properties/foo` is only 2 levels deep; ascending 3 has nowhere to go.
+        let result = ptr.relative_pointer("/properties/foo", "3/type", resolver);
+        assert!(result.is_err());
+    }
+
     #[test]
     fn test_unknown_property() {
         let registry = create_test_registry();
@@ -10,7 +10,7 @@ use pyo3::exceptions::{PyValueError, PyIOError, PyRuntimeError};
 pub enum OxidizeError {
     InvalidInput { message: String, context: String },
     FileError { path: String, error: String },
-    XmlParseError { position: Option<usize>, message: String },
+    XmlParseError { position: Option<u64>, message: String },
     MemoryError { message: String },
     IoError { message: String },
 }
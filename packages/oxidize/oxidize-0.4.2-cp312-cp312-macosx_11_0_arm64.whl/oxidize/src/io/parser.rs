@@ -3,10 +3,14 @@
 //! Provides hybrid streaming XML parsing with parallel batch processing for high performance
 //! extraction of repeated XML elements.
 
-use quick_xml::Reader;
+use quick_xml::{NsReader, Reader};
 use quick_xml::events::Event;
+use quick_xml::name::ResolveResult;
+use std::borrow::Cow;
 use std::io::{BufRead, Write};
 use std::collections::VecDeque;
+use std::collections::{BTreeMap, BTreeSet};
+use std::ops::Range;
 use std::path::PathBuf;
 use rayon::prelude::*;
 use crate::io::xml_utils::get_xml_node;
@@ -22,6 +26,262 @@ const MAX_ELEMENT_SIZE: usize = 10_000_000;   // Maximum element size (10MB)
 const MAX_ATTRIBUTE_COUNT: usize = 1000;      // Maximum attributes per element
 const MAX_ATTRIBUTE_SIZE: usize = 65536;      // Maximum attribute value size (64KB)
 
+/// Structural summary of the JSON values produced for every `target_element`
+/// parsed so far, folded by `hybrid_stream_infer_schema` instead of writing
+/// one JSON object per element. Tracks, at each object position, which keys
+/// were observed on every object seen there (`required`) versus only some
+/// (optional), and which JSON types each key/position has taken on (polymorphic
+/// fields collapse to `{"type": [...]}`). `merge` is commutative and
+/// associative, so per-batch accumulators produced by Rayon can be reduced in
+/// any order before `to_schema` serializes the result once at the end.
+#[derive(Debug, Default, Clone)]
+struct SchemaAccumulator {
+    /// JSON types observed at this position ("null", "boolean", "integer", "number", "string", "object", "array").
+    types: BTreeSet<&'static str>,
+    /// Per-key accumulators for object-typed values observed at this position.
+    properties: BTreeMap<String, SchemaAccumulator>,
+    /// Keys present on every object observed at this position so far, `None` until the first object arrives.
+    always_present: Option<BTreeSet<String>>,
+    /// Accumulator folding every item of any array observed at this position.
+    items: Option<Box<SchemaAccumulator>>,
+}
+
+impl SchemaAccumulator {
+    /// Fold a single JSON value into this accumulator.
+    fn observe(&mut self, value: &serde_json::Value) {
+        match value {
+            serde_json::Value::Null => {
+                self.types.insert("null");
+            }
+            serde_json::Value::Bool(_) => {
+                self.types.insert("boolean");
+            }
+            serde_json::Value::Number(n) => {
+                self.types.insert(if n.is_i64() || n.is_u64() { "integer" } else { "number" });
+            }
+            serde_json::Value::String(_) => {
+                self.types.insert("string");
+            }
+            serde_json::Value::Array(elements) => {
+                self.types.insert("array");
+                let item_accumulator = self.items.get_or_insert_with(Box::default);
+                for element in elements {
+                    item_accumulator.observe(element);
+                }
+            }
+            serde_json::Value::Object(map) => {
+                self.types.insert("object");
+                let keys: BTreeSet<String> = map.keys().cloned().collect();
+                self.always_present = Some(match self.always_present.take() {
+                    Some(existing) => existing.intersection(&keys).cloned().collect(),
+                    None => keys,
+                });
+                for (key, value) in map {
+                    self.properties.entry(key.clone()).or_default().observe(value);
+                }
+            }
+        }
+    }
+
+    /// Commutatively and associatively combine two accumulators covering the same position.
+    fn merge(mut self, other: SchemaAccumulator) -> Self {
+        self.types.extend(other.types);
+
+        self.always_present = match (self.always_present.take(), other.always_present) {
+            (Some(a), Some(b)) => Some(a.intersection(&b).cloned().collect()),
+            (Some(a), None) | (None, Some(a)) => Some(a),
+            (None, None) => None,
+        };
+
+        self.items = match (self.items.take(), other.items) {
+            (Some(a), Some(b)) => Some(Box::new(a.merge(*b))),
+            (Some(a), None) | (None, Some(a)) => Some(a),
+            (None, None) => None,
+        };
+
+        for (key, accumulator) in other.properties {
+            match self.properties.remove(&key) {
+                Some(existing) => {
+                    self.properties.insert(key, existing.merge(accumulator));
+                }
+                None => {
+                    self.properties.insert(key, accumulator);
+                }
+            }
+        }
+
+        self
+    }
+
+    /// Serialize this accumulator as a Draft-07 JSON Schema fragment.
+    fn to_schema(&self) -> serde_json::Value {
+        let mut schema = serde_json::Map::new();
+
+        match self.types.len() {
+            0 => {}
+            1 => {
+                schema.insert(
+                    "type".to_string(),
+                    serde_json::Value::String((*self.types.iter().next().unwrap()).to_string()),
+                );
+            }
+            _ => {
+                let types = self.types.iter().map(|t| serde_json::Value::String(t.to_string())).collect();
+                schema.insert("type".to_string(), serde_json::Value::Array(types));
+            }
+        }
+
+        if self.types.contains("object") && !self.properties.is_empty() {
+            let properties = self
+                .properties
+                .iter()
+                .map(|(key, accumulator)| (key.clone(), accumulator.to_schema()))
+                .collect();
+            schema.insert("properties".to_string(), serde_json::Value::Object(properties));
+
+            if let Some(required) = &self.always_present {
+                if !required.is_empty() {
+                    let required = required.iter().cloned().map(serde_json::Value::String).collect();
+                    schema.insert("required".to_string(), serde_json::Value::Array(required));
+                }
+            }
+        }
+
+        if self.types.contains("array") {
+            if let Some(item_accumulator) = &self.items {
+                schema.insert("items".to_string(), item_accumulator.to_schema());
+            }
+        }
+
+        serde_json::Value::Object(schema)
+    }
+}
+
+/// Resource limits enforced while parsing, guarding against malicious or
+/// accidentally pathological XML (deeply nested bombs, oversized elements or
+/// attributes). Defaults match the module's historical hardcoded constants;
+/// callers parsing legitimately deep/large documents can raise them, and
+/// callers in hostile environments can tighten them, via `HybridStreamParserBuilder`.
+#[derive(Debug, Clone, Copy)]
+pub struct ParserLimits {
+    pub max_element_depth: usize,
+    pub max_element_size: usize,
+    pub max_attribute_count: usize,
+    pub max_attribute_size: usize,
+}
+
+impl Default for ParserLimits {
+    fn default() -> Self {
+        Self {
+            max_element_depth: MAX_ELEMENT_DEPTH,
+            max_element_size: MAX_ELEMENT_SIZE,
+            max_attribute_count: MAX_ATTRIBUTE_COUNT,
+            max_attribute_size: MAX_ATTRIBUTE_SIZE,
+        }
+    }
+}
+
+/// Builds a `HybridStreamParser` with a configurable batch size and `ParserLimits`,
+/// defaulting both to today's hardcoded values.
+pub struct HybridStreamParserBuilder {
+    batch_size: usize,
+    limits: ParserLimits,
+}
+
+impl HybridStreamParserBuilder {
+    pub fn new() -> Self {
+        Self {
+            batch_size: DEFAULT_BATCH_SIZE,
+            limits: ParserLimits::default(),
+        }
+    }
+
+    pub fn batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size;
+        self
+    }
+
+    pub fn limits(mut self, limits: ParserLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    pub fn max_element_depth(mut self, max_element_depth: usize) -> Self {
+        self.limits.max_element_depth = max_element_depth;
+        self
+    }
+
+    pub fn max_element_size(mut self, max_element_size: usize) -> Self {
+        self.limits.max_element_size = max_element_size;
+        self
+    }
+
+    pub fn max_attribute_count(mut self, max_attribute_count: usize) -> Self {
+        self.limits.max_attribute_count = max_attribute_count;
+        self
+    }
+
+    pub fn max_attribute_size(mut self, max_attribute_size: usize) -> Self {
+        self.limits.max_attribute_size = max_attribute_size;
+        self
+    }
+
+    pub fn build<'a>(self) -> HybridStreamParser<'a> {
+        HybridStreamParser::with_limits(self.batch_size, self.limits)
+    }
+}
+
+impl Default for HybridStreamParserBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Tracks XML nesting depth against a configured limit, guarding against XML
+/// bombs. `enter`/`leave` are symmetric: every `enter()` paired with a matching
+/// `leave()` keeps `current_depth` exact, and `leave()` saturates instead of
+/// underflowing if a caller's open/close tags are ever unbalanced.
+#[derive(Debug, Clone, Copy)]
+struct DepthLimiter {
+    current_depth: usize,
+    max_depth_reached: usize,
+    max_depth: usize,
+}
+
+impl DepthLimiter {
+    fn new(max_depth: usize) -> Self {
+        Self {
+            current_depth: 0,
+            max_depth_reached: 0,
+            max_depth,
+        }
+    }
+
+    /// Enter one more level of nesting, erroring if that breaches `max_depth`
+    fn enter(&mut self) -> Result<(), OxidizeError> {
+        self.current_depth += 1;
+        if self.current_depth > self.max_depth_reached {
+            self.max_depth_reached = self.current_depth;
+        }
+
+        if self.current_depth > self.max_depth {
+            return Err(OxidizeError::InvalidInput {
+                message: format!("XML nesting too deep: {} exceeds limit of {}",
+                    self.current_depth, self.max_depth),
+                context: "Potential XML bomb attack detected".to_string(),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Leave one level of nesting. Saturating: an unbalanced close tag (more
+    /// `leave()`s than `enter()`s) cannot drive `current_depth` below zero.
+    fn leave(&mut self) {
+        self.current_depth = self.current_depth.saturating_sub(1);
+    }
+}
+
 // Helper function to write tag with attributes to a buffer
 fn write_tag_with_attributes(buf: &mut Vec<u8>, e: &quick_xml::events::BytesStart, self_closing: bool) {
     buf.extend_from_slice(b"<");
@@ -60,53 +320,76 @@ fn write_closing_tag(buf: &mut Vec<u8>, e: &quick_xml::events::BytesEnd) {
 }
 
 /// Hybrid streaming parser that uses quick_xml for streaming and parallel processing for batches
-pub struct HybridStreamParser {
+///
+/// Generic over `'a` so the slice-reader path (`hybrid_stream_parse_slice`) can
+/// queue elements that borrow directly out of the source `&'a [u8]` instead of
+/// allocating a `String` per element; the `BufRead`-backed paths always queue
+/// owned text and so are happy with any `'a` (in practice `'static`, since a
+/// streamed reader's bytes don't outlive the read call).
+pub struct HybridStreamParser<'a> {
     batch_size: usize,
-    element_queue: VecDeque<String>,
+    limits: ParserLimits,
+    // Each queued element carries its source byte range when provenance tracking is
+    // enabled (`None` otherwise), so it can be stamped onto the output JSON as
+    // `__byte_range` without threading a parallel queue alongside this one.
+    element_queue: VecDeque<(Cow<'a, str>, Option<Range<u64>>)>,
     // Reusable buffer to reduce allocations
     temp_buffer: Vec<u8>,
     // Security tracking
-    current_depth: usize,
-    max_depth_reached: usize,
+    depth_limiter: DepthLimiter,
 }
 
-impl HybridStreamParser {
+impl<'a> HybridStreamParser<'a> {
     pub fn new(batch_size: usize) -> Self {
+        Self::with_limits(batch_size, ParserLimits::default())
+    }
+
+    pub fn with_limits(batch_size: usize, limits: ParserLimits) -> Self {
         Self {
             batch_size,
+            depth_limiter: DepthLimiter::new(limits.max_element_depth),
+            limits,
             element_queue: VecDeque::new(),
             temp_buffer: Vec::new(), // Start small, grow naturally
-            current_depth: 0,
-            max_depth_reached: 0,
         }
     }
 
-    /// Add a complete element to the queue
-    fn queue_element(&mut self, element_xml: String) {
-        self.element_queue.push_back(element_xml);
+    /// The configured maximum size (in bytes) of a single extracted element
+    pub fn max_element_size(&self) -> usize {
+        self.limits.max_element_size
     }
-    
+
+    /// Add a complete element to the queue, along with its source byte range
+    /// (`None` unless provenance tracking is enabled for this parse)
+    fn queue_element(&mut self, element_xml: Cow<'a, str>, byte_range: Option<Range<u64>>) {
+        self.element_queue.push_back((element_xml, byte_range));
+    }
+
     /// Create and queue a self-closing element using reusable buffer
-    fn queue_self_closing_element(&mut self, e: &quick_xml::events::BytesStart) -> Result<(), OxidizeError> {
+    fn queue_self_closing_element(
+        &mut self,
+        e: &quick_xml::events::BytesStart,
+        byte_range: Option<Range<u64>>,
+    ) -> Result<(), OxidizeError> {
         // Security check: validate attributes
         self.validate_element_security(e)?;
-        
+
         self.temp_buffer.clear();
         write_self_closing_tag(&mut self.temp_buffer, e);
-        
+
         // Security check: element size
-        if self.temp_buffer.len() > MAX_ELEMENT_SIZE {
+        if self.temp_buffer.len() > self.limits.max_element_size {
             return Err(OxidizeError::MemoryError {
-                message: format!("Element too large: {} bytes exceeds limit of {}", 
-                    self.temp_buffer.len(), MAX_ELEMENT_SIZE),
+                message: format!("Element too large: {} bytes exceeds limit of {}",
+                    self.temp_buffer.len(), self.limits.max_element_size),
             });
         }
-        
+
         // Use mem::take to avoid clone - takes ownership of buffer contents
         if let Ok(element_str) = String::from_utf8(std::mem::take(&mut self.temp_buffer)) {
-            self.queue_element(element_str);
+            self.queue_element(Cow::Owned(element_str), byte_range);
         }
-        
+
         Ok(())
     }
     
@@ -114,49 +397,27 @@ impl HybridStreamParser {
     fn validate_element_security(&self, e: &quick_xml::events::BytesStart) -> Result<(), OxidizeError> {
         // Check attribute count
         let attr_count = e.attributes().size_hint().0;
-        if attr_count > MAX_ATTRIBUTE_COUNT {
+        if attr_count > self.limits.max_attribute_count {
             return Err(OxidizeError::InvalidInput {
-                message: format!("Too many attributes: {} exceeds limit of {}", 
-                    attr_count, MAX_ATTRIBUTE_COUNT),
+                message: format!("Too many attributes: {} exceeds limit of {}",
+                    attr_count, self.limits.max_attribute_count),
                 context: "Potential XML bomb attack detected".to_string(),
             });
         }
-        
+
         // Check individual attribute sizes
         for attr_result in e.attributes() {
             if let Ok(attr) = attr_result {
-                if attr.value.len() > MAX_ATTRIBUTE_SIZE {
+                if attr.value.len() > self.limits.max_attribute_size {
                     return Err(OxidizeError::InvalidInput {
-                        message: format!("Attribute value too large: {} bytes exceeds limit of {}", 
-                            attr.value.len(), MAX_ATTRIBUTE_SIZE),
+                        message: format!("Attribute value too large: {} bytes exceeds limit of {}",
+                            attr.value.len(), self.limits.max_attribute_size),
                         context: "Potential XML bomb attack detected".to_string(),
                     });
                 }
             }
         }
-        
-        Ok(())
-    }
-    
-    /// Check and update depth tracking for security
-    fn check_depth(&mut self, increment: bool) -> Result<(), OxidizeError> {
-        if increment {
-            self.current_depth += 1;
-            if self.current_depth > self.max_depth_reached {
-                self.max_depth_reached = self.current_depth;
-            }
-            
-            if self.current_depth > MAX_ELEMENT_DEPTH {
-                return Err(OxidizeError::InvalidInput {
-                    message: format!("XML nesting too deep: {} exceeds limit of {}", 
-                        self.current_depth, MAX_ELEMENT_DEPTH),
-                    context: "Potential XML bomb attack detected".to_string(),
-                });
-            }
-        } else if self.current_depth > 0 {
-            self.current_depth -= 1;
-        }
-        
+
         Ok(())
     }
 
@@ -184,19 +445,39 @@ impl HybridStreamParser {
         }
 
         let batch_size = self.batch_size.min(self.element_queue.len());
-        
+
         // Process elements directly from the queue without intermediate collection
         let batch_elements: Vec<_> = self.element_queue.drain(..batch_size).collect();
-        
+
         // Process in parallel using Rayon
         let results = batch_elements
             .par_iter()
-            .filter_map(|xml_str| {
+            .filter_map(|(xml_str, byte_range)| {
                 // Parse each element
                 match get_xml_node(xml_str) {
                     Ok(node) => {
                         // Convert XmlNode to serde_json::Value
-                        let value = node.to_json();
+                        let mut value = node.to_json();
+                        // Stamp on the source byte range for provenance, when tracked
+                        if let Some(range) = byte_range {
+                            let byte_range_json =
+                                serde_json::json!({ "start": range.start, "end": range.end });
+                            match value.as_object_mut() {
+                                Some(obj) => {
+                                    obj.insert("__byte_range".to_string(), byte_range_json);
+                                }
+                                None => {
+                                    // `to_json` returns a bare `Null`/`String` for an
+                                    // attribute-less element (empty or text-only), which has
+                                    // no object to attach a sibling key to. Wrap it so the
+                                    // byte range still survives instead of being dropped.
+                                    value = serde_json::json!({
+                                        "__value": value,
+                                        "__byte_range": byte_range_json,
+                                    });
+                                }
+                            }
+                        }
                         // Serialize to string
                         match serde_json::to_string(&value) {
                             Ok(json) => Some(json),
@@ -207,7 +488,7 @@ impl HybridStreamParser {
                 }
             })
             .collect();
-            
+
         results
     }
 
@@ -224,9 +505,55 @@ impl HybridStreamParser {
         all_results
     }
 
+    /// Process a batch of elements in parallel, folding each into a `SchemaAccumulator`
+    /// instead of serializing to JSON strings. Mirrors `process_batch`'s Rayon fan-out
+    /// so schema inference over a feed stays as parallel as emitting it.
+    fn process_batch_schema(&mut self) -> SchemaAccumulator {
+        if self.element_queue.is_empty() {
+            return SchemaAccumulator::default();
+        }
+
+        let batch_size = self.batch_size.min(self.element_queue.len());
+        let batch_elements: Vec<_> = self.element_queue.drain(..batch_size).collect();
+
+        batch_elements
+            .par_iter()
+            .filter_map(|(xml_str, _byte_range)| match get_xml_node(xml_str) {
+                Ok(node) => {
+                    let mut accumulator = SchemaAccumulator::default();
+                    accumulator.observe(&node.to_json());
+                    Some(accumulator)
+                }
+                Err(_) => None,
+            })
+            .reduce(SchemaAccumulator::default, SchemaAccumulator::merge)
+    }
+
+    /// Fold a schema batch into `accumulator` if the queue is full. `SchemaAccumulator::merge`
+    /// is a commutative monoid, so it doesn't matter which order batches arrive in.
+    pub fn process_batch_schema_if_full(&mut self, accumulator: SchemaAccumulator) -> SchemaAccumulator {
+        if self.element_queue.len() >= self.batch_size {
+            accumulator.merge(self.process_batch_schema())
+        } else {
+            accumulator
+        }
+    }
+
+    /// Fold any remaining queued elements into `accumulator` at end of stream.
+    pub fn flush_schema(&mut self, mut accumulator: SchemaAccumulator) -> SchemaAccumulator {
+        while !self.element_queue.is_empty() {
+            accumulator = accumulator.merge(self.process_batch_schema());
+        }
+        accumulator
+    }
+
     /// Public method to queue self-closing elements (used by the main parsing function)
-    pub fn queue_self_closing_element_public(&mut self, e: &quick_xml::events::BytesStart) -> Result<(), OxidizeError> {
-        self.queue_self_closing_element(e)
+    pub fn queue_self_closing_element_public(
+        &mut self,
+        e: &quick_xml::events::BytesStart,
+        byte_range: Option<Range<u64>>,
+    ) -> Result<(), OxidizeError> {
+        self.queue_self_closing_element(e, byte_range)
     }
 
     /// Public method to validate element security (used by the main parsing function)
@@ -234,14 +561,27 @@ impl HybridStreamParser {
         self.validate_element_security(e)
     }
 
-    /// Public method to check depth (used by the main parsing function)
-    pub fn check_depth_public(&mut self, increment: bool) -> Result<(), OxidizeError> {
-        self.check_depth(increment)
+    /// Public method to enter one more level of nesting (used by the main parsing
+    /// function); errors if that breaches the configured `max_element_depth`
+    pub fn enter_depth_public(&mut self) -> Result<(), OxidizeError> {
+        self.depth_limiter.enter()
+    }
+
+    /// Public method to leave one level of nesting (used by the main parsing
+    /// function); saturating, so an unbalanced close tag cannot underflow
+    pub fn leave_depth_public(&mut self) {
+        self.depth_limiter.leave()
     }
 
     /// Public method to queue element (used by the main parsing function)
-    pub fn queue_element_public(&mut self, element_xml: String) {
-        self.queue_element(element_xml)
+    pub fn queue_element_public(&mut self, element_xml: String, byte_range: Option<Range<u64>>) {
+        self.queue_element(Cow::Owned(element_xml), byte_range)
+    }
+
+    /// Public method to queue an element borrowed directly from the source buffer,
+    /// without allocating (used by `hybrid_stream_parse_slice`)
+    pub fn queue_borrowed_element_public(&mut self, element_xml: &'a str, byte_range: Option<Range<u64>>) {
+        self.queue_element(Cow::Borrowed(element_xml), byte_range)
     }
 }
 
@@ -318,14 +658,52 @@ pub fn validate_inputs(target_element: &str, batch_size: usize) -> Result<(), Ox
 
 /// Stream parse file using quick_xml and process batches in parallel
 pub fn hybrid_stream_parse<R: BufRead, W: Write>(
+    reader: R,
+    writer: W,
+    target_element: &str,
+    batch_size: usize,
+) -> Result<usize, OxidizeError> {
+    hybrid_stream_parse_impl(reader, writer, target_element, batch_size, false, ParserLimits::default())
+}
+
+/// Like `hybrid_stream_parse`, but records each extracted element's source byte
+/// range and stamps it onto the output JSON as a `__byte_range` field (`{"start":
+/// ..., "end": ...}`). This lets downstream consumers seek back into the original
+/// file to re-read a specific element without re-scanning the whole multi-gigabyte
+/// feed, at the cost of one extra `buffer_position()` call per event.
+pub fn hybrid_stream_parse_with_provenance<R: BufRead, W: Write>(
+    reader: R,
+    writer: W,
+    target_element: &str,
+    batch_size: usize,
+) -> Result<usize, OxidizeError> {
+    hybrid_stream_parse_impl(reader, writer, target_element, batch_size, true, ParserLimits::default())
+}
+
+/// Like `hybrid_stream_parse`, but with configurable `ParserLimits` instead of
+/// the module's hardcoded defaults - for callers parsing legitimately deep or
+/// large documents, or tightening limits further in hostile environments.
+pub fn hybrid_stream_parse_with_limits<R: BufRead, W: Write>(
+    reader: R,
+    writer: W,
+    target_element: &str,
+    batch_size: usize,
+    limits: ParserLimits,
+) -> Result<usize, OxidizeError> {
+    hybrid_stream_parse_impl(reader, writer, target_element, batch_size, false, limits)
+}
+
+fn hybrid_stream_parse_impl<R: BufRead, W: Write>(
     reader: R,
     mut writer: W,
     target_element: &str,
     batch_size: usize,
+    record_provenance: bool,
+    limits: ParserLimits,
 ) -> Result<usize, OxidizeError> {
     // Validate inputs first
     validate_inputs(target_element, batch_size)?;
-    let mut parser = HybridStreamParser::new(batch_size);
+    let mut parser = HybridStreamParser::with_limits(batch_size, limits);
     let mut xml_reader = Reader::from_reader(reader);
     xml_reader.trim_text(true);
 
@@ -334,23 +712,31 @@ pub fn hybrid_stream_parse<R: BufRead, W: Write>(
     let mut in_target = false;
     let mut depth = 0;
     let mut total_count = 0;
+    let mut element_start_offset: Option<u64> = None;
 
     let target_bytes = target_element.as_bytes();
 
     loop {
+        // Position before consuming the next event is that event's start offset
+        let event_start_pos = xml_reader.buffer_position() as u64;
+
         match xml_reader.read_event_into(&mut buf) {
             Ok(Event::Start(ref e)) => {
                 if e.name().as_ref() == target_bytes {
                     // Security check for target element
                     parser.validate_element_security_public(e)?;
-                    parser.check_depth_public(true)?;
-                    
+                    parser.enter_depth_public()?;
+
                     in_target = true;
                     depth = 1;
                     element_buf.clear();
+                    // The trimming reader skips whitespace before a tag in the same call that
+                    // returns it, so the tag's start is recovered from its end: `<` content `>`
+                    element_start_offset = record_provenance
+                        .then(|| xml_reader.buffer_position() as u64 - (e.len() as u64 + 2));
                     write_opening_tag(&mut element_buf, e);
                 } else if in_target {
-                    parser.check_depth_public(true)?;
+                    parser.enter_depth_public()?;
                     depth += 1;
                     write_opening_tag(&mut element_buf, e);
                 }
@@ -360,22 +746,25 @@ pub fn hybrid_stream_parse<R: BufRead, W: Write>(
                     write_closing_tag(&mut element_buf, e);
 
                     depth -= 1;
-                    parser.check_depth_public(false)?;
-                    
+                    parser.leave_depth_public();
+
                     if depth == 0 && e.name().as_ref() == target_bytes {
                         // Complete element found - security check size
-                        if element_buf.len() > MAX_ELEMENT_SIZE {
+                        if element_buf.len() > parser.max_element_size() {
                             return Err(OxidizeError::MemoryError {
-                                message: format!("Element too large: {} bytes exceeds limit of {}", 
-                                    element_buf.len(), MAX_ELEMENT_SIZE),
+                                message: format!("Element too large: {} bytes exceeds limit of {}",
+                                    element_buf.len(), parser.max_element_size()),
                             });
                         }
-                        
+
                         in_target = false;
 
+                        let byte_range = element_start_offset
+                            .map(|start| start..(xml_reader.buffer_position() as u64));
+
                         // Convert to string and queue - use mem::take to avoid clone
                         if let Ok(element_str) = String::from_utf8(std::mem::take(&mut element_buf)) {
-                            parser.queue_element_public(element_str);
+                            parser.queue_element_public(element_str, byte_range);
                         }
 
                         // Process batch if queue is full
@@ -387,8 +776,14 @@ pub fn hybrid_stream_parse<R: BufRead, W: Write>(
             }
             Ok(Event::Empty(ref e)) => {
                 if e.name().as_ref() == target_bytes {
+                    let byte_range = record_provenance.then(|| {
+                        let end = xml_reader.buffer_position() as u64;
+                        // `<` content `/>`
+                        end - (e.len() as u64 + 3)..end
+                    });
+
                     // Use optimized method for self-closing elements with security checks
-                    parser.queue_self_closing_element_public(e)?;
+                    parser.queue_self_closing_element_public(e, byte_range)?;
 
                     // Process batch if queue is full
                     parser.process_batch_if_full(&mut writer, &mut total_count)?;
@@ -401,7 +796,7 @@ pub fn hybrid_stream_parse<R: BufRead, W: Write>(
             }
             Ok(Event::Eof) => break,
             Err(e) => return Err(OxidizeError::XmlParseError {
-                position: Some(xml_reader.buffer_position()),
+                position: Some(xml_reader.buffer_position() as u64),
                 message: format!("Failed to parse XML: {}", e),
             }),
             _ => {} // Ignore other events
@@ -424,56 +819,600 @@ pub fn hybrid_stream_parse<R: BufRead, W: Write>(
     Ok(total_count)
 }
 
-// Constants are already public above
+/// Whether a resolved element namespace matches the requested target namespace:
+/// `None` requests an element with no namespace bound (`ResolveResult::Unbound`);
+/// `Some(uri)` requests an element bound to exactly that namespace URI. An
+/// unrecognized prefix (`ResolveResult::Unknown`) never matches either way.
+fn namespace_matches(resolved: &ResolveResult, target_namespace: Option<&str>) -> bool {
+    match (resolved, target_namespace) {
+        (ResolveResult::Unbound, None) => true,
+        (ResolveResult::Bound(ns), Some(target)) => ns.as_ref() == target.as_bytes(),
+        _ => false,
+    }
+}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use std::io::Cursor;
+/// Namespace-aware variant of `hybrid_stream_parse`: matches the target element
+/// by its resolved `(namespace_uri, local_name)` instead of a raw byte
+/// comparison, so `<ns:Item>` and an `<Item>` under a default namespace resolve
+/// to the same logical target when both are bound to the requested URI; an
+/// `<Item>` with no namespace in scope matches only a `None` namespace. Swaps in
+/// `quick_xml::NsReader` and calls `resolve_element` on every `Start`/`Empty`/
+/// matching `End` to get there; `hybrid_stream_parse` keeps the raw-bytes fast
+/// path for callers that don't need namespace resolution, since resolving a
+/// qualified name on every event isn't free.
+pub fn hybrid_stream_parse_ns<R: BufRead, W: Write>(
+    reader: R,
+    mut writer: W,
+    target: (Option<&str>, &str),
+    batch_size: usize,
+) -> Result<usize, OxidizeError> {
+    let (target_namespace, target_local_name) = target;
+    validate_inputs(target_local_name, batch_size)?;
 
-    #[test]
-    fn test_hybrid_stream_parser_new() {
-        let parser = HybridStreamParser::new(100);
-        assert_eq!(parser.batch_size, 100);
-        assert!(parser.element_queue.is_empty());
-    }
+    let mut parser = HybridStreamParser::new(batch_size);
+    let mut xml_reader = NsReader::from_reader(reader);
+    xml_reader.trim_text(true);
 
-    #[test]
-    fn test_basic_xml_parsing() {
-        let xml = r#"<?xml version="1.0"?>
-<root>
-    <Item id="1"><Value>100</Value></Item>
-    <Item id="2"><Value>200</Value></Item>
-</root>"#;
+    let mut buf = Vec::new();
+    let mut element_buf = Vec::new();
+    let mut in_target = false;
+    let mut depth = 0;
+    let mut total_count = 0;
 
-        let reader = Cursor::new(xml.as_bytes());
-        let mut output = Vec::new();
+    let target_local_bytes = target_local_name.as_bytes();
 
-        let result = hybrid_stream_parse(reader, &mut output, "Item", 10);
-        assert!(result.is_ok());
-        assert_eq!(result.unwrap(), 2);
+    loop {
+        match xml_reader.read_event_into(&mut buf) {
+            Ok(Event::Start(ref e)) => {
+                if !in_target {
+                    let (resolved_ns, local) = xml_reader.resolve_element(e.name());
+                    if local.as_ref() == target_local_bytes && namespace_matches(&resolved_ns, target_namespace) {
+                        // Security check for target element
+                        parser.validate_element_security_public(e)?;
+                        parser.enter_depth_public()?;
+
+                        in_target = true;
+                        depth = 1;
+                        element_buf.clear();
+                        write_opening_tag(&mut element_buf, e);
+                        continue;
+                    }
+                }
+                if in_target {
+                    parser.enter_depth_public()?;
+                    depth += 1;
+                    write_opening_tag(&mut element_buf, e);
+                }
+            }
+            Ok(Event::End(ref e)) => {
+                if in_target {
+                    write_closing_tag(&mut element_buf, e);
 
-        let output_str = String::from_utf8(output).unwrap();
-        assert!(output_str.contains("\"id\":\"1\""));
-        assert!(output_str.contains("\"Value\":\"100\""));
-        assert!(output_str.contains("\"id\":\"2\""));
-    }
+                    depth -= 1;
+                    parser.leave_depth_public();
+
+                    if depth == 0 {
+                        let (resolved_ns, local) = xml_reader.resolve_element(e.name());
+                        if local.as_ref() == target_local_bytes && namespace_matches(&resolved_ns, target_namespace) {
+                            // Complete element found - security check size
+                            if element_buf.len() > parser.max_element_size() {
+                                return Err(OxidizeError::MemoryError {
+                                    message: format!("Element too large: {} bytes exceeds limit of {}",
+                                        element_buf.len(), parser.max_element_size()),
+                                });
+                            }
+
+                            in_target = false;
+
+                            // Convert to string and queue - use mem::take to avoid clone
+                            if let Ok(element_str) = String::from_utf8(std::mem::take(&mut element_buf)) {
+                                parser.queue_element_public(element_str, None);
+                            }
+
+                            // Process batch if queue is full
+                            parser.process_batch_if_full(&mut writer, &mut total_count)?;
+
+                            element_buf.clear();
+                        }
+                    }
+                }
+            }
+            Ok(Event::Empty(ref e)) => {
+                let (resolved_ns, local) = xml_reader.resolve_element(e.name());
+                if local.as_ref() == target_local_bytes && namespace_matches(&resolved_ns, target_namespace) {
+                    // Use optimized method for self-closing elements with security checks
+                    parser.queue_self_closing_element_public(e, None)?;
 
-    #[test]
-    fn test_empty_xml() {
-        let xml = "<?xml version=\"1.0\"?><root></root>";
-        let reader = Cursor::new(xml.as_bytes());
-        let mut output = Vec::new();
+                    // Process batch if queue is full
+                    parser.process_batch_if_full(&mut writer, &mut total_count)?;
+                }
+            }
+            Ok(Event::Text(ref e)) => {
+                if in_target {
+                    element_buf.extend_from_slice(e.as_ref());
+                }
+            }
+            Ok(Event::Eof) => break,
+            Err(e) => return Err(OxidizeError::XmlParseError {
+                position: Some(xml_reader.buffer_position() as u64),
+                message: format!("Failed to parse XML: {}", e),
+            }),
+            _ => {} // Ignore other events
+        }
 
-        let result = hybrid_stream_parse(reader, &mut output, "Item", 10);
-        assert!(result.is_ok());
-        assert_eq!(result.unwrap(), 0);
-        assert!(output.is_empty());
+        buf.clear();
     }
 
-    #[test]
-    fn test_batch_size_variations() {
-        let xml = r#"<?xml version="1.0"?>
+    // Process remaining elements
+    let final_results = parser.flush();
+    total_count += final_results.len();
+
+    for json_line in final_results {
+        writeln!(writer, "{}", json_line)
+            .map_err(|e| OxidizeError::IoError {
+                message: format!("Failed to write final JSON output: {}", e),
+            })?;
+    }
+
+    Ok(total_count)
+}
+
+/// Infers a Draft-07 JSON Schema describing every `target_element` in `reader`,
+/// instead of emitting one JSON object per element. Reuses the same parsing
+/// loop as `hybrid_stream_parse`, but folds each element's `node.to_json()`
+/// value into a `SchemaAccumulator` rather than serializing and writing it;
+/// `process_batch_schema_if_full`/`flush_schema` keep the same Rayon-batched
+/// shape as `process_batch_if_full`/`flush`, so schema inference over a large
+/// feed parallelizes the same way emitting it does.
+pub fn hybrid_stream_infer_schema<R: BufRead>(
+    reader: R,
+    target_element: &str,
+    batch_size: usize,
+) -> Result<serde_json::Value, OxidizeError> {
+    validate_inputs(target_element, batch_size)?;
+
+    let mut parser = HybridStreamParser::new(batch_size);
+    let mut xml_reader = Reader::from_reader(reader);
+    xml_reader.trim_text(true);
+
+    let mut buf = Vec::new();
+    let mut element_buf = Vec::new();
+    let mut in_target = false;
+    let mut depth = 0;
+    let mut accumulator = SchemaAccumulator::default();
+
+    let target_bytes = target_element.as_bytes();
+
+    loop {
+        match xml_reader.read_event_into(&mut buf) {
+            Ok(Event::Start(ref e)) => {
+                if !in_target && e.name().as_ref() == target_bytes {
+                    // Security check for target element
+                    parser.validate_element_security_public(e)?;
+                    parser.enter_depth_public()?;
+
+                    in_target = true;
+                    depth = 1;
+                    element_buf.clear();
+                    write_opening_tag(&mut element_buf, e);
+                    continue;
+                }
+                if in_target {
+                    parser.enter_depth_public()?;
+                    depth += 1;
+                    write_opening_tag(&mut element_buf, e);
+                }
+            }
+            Ok(Event::End(ref e)) => {
+                if in_target {
+                    write_closing_tag(&mut element_buf, e);
+
+                    depth -= 1;
+                    parser.leave_depth_public();
+
+                    if depth == 0 && e.name().as_ref() == target_bytes {
+                        if element_buf.len() > parser.max_element_size() {
+                            return Err(OxidizeError::MemoryError {
+                                message: format!("Element too large: {} bytes exceeds limit of {}",
+                                    element_buf.len(), parser.max_element_size()),
+                            });
+                        }
+
+                        in_target = false;
+
+                        if let Ok(element_str) = String::from_utf8(std::mem::take(&mut element_buf)) {
+                            parser.queue_element_public(element_str, None);
+                        }
+
+                        accumulator = parser.process_batch_schema_if_full(accumulator);
+
+                        element_buf.clear();
+                    }
+                }
+            }
+            Ok(Event::Empty(ref e)) => {
+                if e.name().as_ref() == target_bytes {
+                    parser.queue_self_closing_element_public(e, None)?;
+                    accumulator = parser.process_batch_schema_if_full(accumulator);
+                }
+            }
+            Ok(Event::Text(ref e)) => {
+                if in_target {
+                    element_buf.extend_from_slice(e.as_ref());
+                }
+            }
+            Ok(Event::Eof) => break,
+            Err(e) => return Err(OxidizeError::XmlParseError {
+                position: Some(xml_reader.buffer_position() as u64),
+                message: format!("Failed to parse XML: {}", e),
+            }),
+            _ => {} // Ignore other events
+        }
+
+        buf.clear();
+    }
+
+    accumulator = parser.flush_schema(accumulator);
+
+    Ok(accumulator.to_schema())
+}
+
+/// Zero-copy variant of `hybrid_stream_parse` for XML that's already fully
+/// resident in memory (e.g. an mmap'd index file). Mirrors upstream quick-xml's
+/// own `IoReader`/`SliceReader` split: `Reader::from_reader` works the same way
+/// over a `&[u8]` as over a `BufRead`, but here each extracted element borrows
+/// its text straight out of `input` via `Cow::Borrowed` instead of copying into
+/// `element_buf` and allocating a `String` per element, since `input` already
+/// holds the whole document and there's no reader state that would otherwise be
+/// lost by not re-synthesizing it.
+pub fn hybrid_stream_parse_slice<'a, W: Write>(
+    input: &'a [u8],
+    mut writer: W,
+    target_element: &str,
+    batch_size: usize,
+) -> Result<usize, OxidizeError> {
+    // Validate inputs first
+    validate_inputs(target_element, batch_size)?;
+    let mut parser: HybridStreamParser<'a> = HybridStreamParser::new(batch_size);
+    let mut xml_reader = Reader::from_reader(input);
+    xml_reader.trim_text(true);
+
+    let mut buf = Vec::new();
+    let mut in_target = false;
+    let mut depth = 0;
+    let mut total_count = 0;
+    let mut element_start: usize = 0;
+
+    let target_bytes = target_element.as_bytes();
+
+    loop {
+        match xml_reader.read_event_into(&mut buf) {
+            Ok(Event::Start(ref e)) => {
+                if e.name().as_ref() == target_bytes {
+                    // Security check for target element
+                    parser.validate_element_security_public(e)?;
+                    parser.enter_depth_public()?;
+
+                    in_target = true;
+                    depth = 1;
+                    // The trimming reader skips whitespace before a tag in the same call that
+                    // returns it, so the tag's start is recovered from its end: `<` content `>`
+                    element_start = xml_reader.buffer_position() as usize - (e.len() + 2);
+                } else if in_target {
+                    parser.enter_depth_public()?;
+                    depth += 1;
+                }
+            }
+            Ok(Event::End(ref e)) => {
+                if in_target {
+                    depth -= 1;
+                    parser.leave_depth_public();
+
+                    if depth == 0 && e.name().as_ref() == target_bytes {
+                        let event_end = xml_reader.buffer_position() as usize;
+
+                        // Security check size
+                        if event_end - element_start > parser.max_element_size() {
+                            return Err(OxidizeError::MemoryError {
+                                message: format!("Element too large: {} bytes exceeds limit of {}",
+                                    event_end - element_start, parser.max_element_size()),
+                            });
+                        }
+
+                        in_target = false;
+
+                        // Borrow the element's raw source bytes directly rather than
+                        // re-synthesizing them into an owned String
+                        if let Ok(element_str) = std::str::from_utf8(&input[element_start..event_end]) {
+                            parser.queue_borrowed_element_public(
+                                element_str,
+                                Some(element_start as u64..event_end as u64),
+                            );
+                        }
+
+                        // Process batch if queue is full
+                        parser.process_batch_if_full(&mut writer, &mut total_count)?;
+                    }
+                }
+            }
+            Ok(Event::Empty(ref e)) => {
+                if e.name().as_ref() == target_bytes {
+                    // Security check for target element
+                    parser.validate_element_security_public(e)?;
+
+                    let event_end = xml_reader.buffer_position() as usize;
+                    // `<` content `/>`
+                    let event_start = event_end - (e.len() + 3);
+                    if event_end - event_start > parser.max_element_size() {
+                        return Err(OxidizeError::MemoryError {
+                            message: format!("Element too large: {} bytes exceeds limit of {}",
+                                event_end - event_start, parser.max_element_size()),
+                        });
+                    }
+
+                    // Borrow the whole self-closing tag directly from `input` - this is
+                    // the common case the zero-copy path is for
+                    if let Ok(element_str) = std::str::from_utf8(&input[event_start..event_end]) {
+                        parser.queue_borrowed_element_public(
+                            element_str,
+                            Some(event_start as u64..event_end as u64),
+                        );
+                    }
+
+                    // Process batch if queue is full
+                    parser.process_batch_if_full(&mut writer, &mut total_count)?;
+                }
+            }
+            Ok(Event::Eof) => break,
+            Err(e) => return Err(OxidizeError::XmlParseError {
+                position: Some(xml_reader.buffer_position() as u64),
+                message: format!("Failed to parse XML: {}", e),
+            }),
+            // Text (and everything else) needs no handling here: target element text
+            // is part of the raw slice borrowed above, not accumulated incrementally
+            _ => {}
+        }
+
+        buf.clear();
+    }
+
+    // Process remaining elements
+    let final_results = parser.flush();
+    total_count += final_results.len();
+
+    for json_line in final_results {
+        writeln!(writer, "{}", json_line)
+            .map_err(|e| OxidizeError::IoError {
+                message: format!("Failed to write final JSON output: {}", e),
+            })?;
+    }
+
+    Ok(total_count)
+}
+
+// Constants are already public above
+
+/// Process a batch of elements in parallel, running the Rayon work on a blocking
+/// thread so the async reactor driving `hybrid_stream_parse_async` is never
+/// blocked. Takes and returns the parser by value since `HybridStreamParser`
+/// isn't `Clone` and `spawn_blocking`'s closure needs `'static` ownership of it
+/// (hence the `'static` parser: the async path always queues owned text, never
+/// the borrowed `hybrid_stream_parse_slice` elements).
+#[cfg(feature = "async")]
+async fn process_batch_if_full_async<W: tokio::io::AsyncWrite + Unpin>(
+    parser: HybridStreamParser<'static>,
+    writer: &mut W,
+    total_count: &mut usize,
+) -> Result<HybridStreamParser<'static>, OxidizeError> {
+    use tokio::io::AsyncWriteExt;
+
+    if parser.element_queue.len() < parser.batch_size {
+        return Ok(parser);
+    }
+
+    let (parser, results) = tokio::task::spawn_blocking(move || {
+        let mut parser = parser;
+        let results = parser.process_batch();
+        (parser, results)
+    })
+    .await
+    .map_err(|e| OxidizeError::IoError {
+        message: format!("Batch processing task panicked: {}", e),
+    })?;
+
+    *total_count += results.len();
+
+    for json_line in results {
+        writer.write_all(json_line.as_bytes()).await.map_err(|e| OxidizeError::IoError {
+            message: format!("Failed to write JSON output: {}", e),
+        })?;
+        writer.write_all(b"\n").await.map_err(|e| OxidizeError::IoError {
+            message: format!("Failed to write JSON output: {}", e),
+        })?;
+    }
+
+    Ok(parser)
+}
+
+/// Async counterpart to `hybrid_stream_parse` for embedding in Tokio-based services
+/// (HTTP upload handlers, S3 streaming) that need to parse huge PyPI XML feeds
+/// without dedicating a blocking thread to the whole stream. Mirrors the sync
+/// version's event loop and security checks exactly, but reads through
+/// `quick_xml`'s `read_event_into_async` and offloads each batch's Rayon-parallel
+/// parse/serialize work (`HybridStreamParser::process_batch`) to
+/// `tokio::task::spawn_blocking` so the reactor stays free while a batch runs.
+#[cfg(feature = "async")]
+pub async fn hybrid_stream_parse_async<R, W>(
+    reader: R,
+    mut writer: W,
+    target_element: &str,
+    batch_size: usize,
+) -> Result<usize, OxidizeError>
+where
+    R: tokio::io::AsyncBufRead + Unpin,
+    W: tokio::io::AsyncWrite + Unpin,
+{
+    use tokio::io::AsyncWriteExt;
+
+    // Validate inputs first
+    validate_inputs(target_element, batch_size)?;
+    let mut parser = HybridStreamParser::new(batch_size);
+    let mut xml_reader = Reader::from_reader(reader);
+    xml_reader.trim_text(true);
+
+    let mut buf = Vec::new();
+    let mut element_buf = Vec::new();
+    let mut in_target = false;
+    let mut depth = 0;
+    let mut total_count = 0;
+
+    let target_bytes = target_element.as_bytes();
+
+    loop {
+        match xml_reader.read_event_into_async(&mut buf).await {
+            Ok(Event::Start(ref e)) => {
+                if e.name().as_ref() == target_bytes {
+                    // Security check for target element
+                    parser.validate_element_security_public(e)?;
+                    parser.enter_depth_public()?;
+
+                    in_target = true;
+                    depth = 1;
+                    element_buf.clear();
+                    write_opening_tag(&mut element_buf, e);
+                } else if in_target {
+                    parser.enter_depth_public()?;
+                    depth += 1;
+                    write_opening_tag(&mut element_buf, e);
+                }
+            }
+            Ok(Event::End(ref e)) => {
+                if in_target {
+                    write_closing_tag(&mut element_buf, e);
+
+                    depth -= 1;
+                    parser.leave_depth_public();
+
+                    if depth == 0 && e.name().as_ref() == target_bytes {
+                        // Complete element found - security check size
+                        if element_buf.len() > parser.max_element_size() {
+                            return Err(OxidizeError::MemoryError {
+                                message: format!("Element too large: {} bytes exceeds limit of {}",
+                                    element_buf.len(), parser.max_element_size()),
+                            });
+                        }
+
+                        in_target = false;
+
+                        // Convert to string and queue - use mem::take to avoid clone
+                        if let Ok(element_str) = String::from_utf8(std::mem::take(&mut element_buf)) {
+                            parser.queue_element_public(element_str, None);
+                        }
+
+                        // Process batch if queue is full, offloaded to a blocking thread
+                        parser = process_batch_if_full_async(parser, &mut writer, &mut total_count).await?;
+
+                        element_buf.clear();
+                    }
+                }
+            }
+            Ok(Event::Empty(ref e)) => {
+                if e.name().as_ref() == target_bytes {
+                    // Use optimized method for self-closing elements with security checks
+                    parser.queue_self_closing_element_public(e, None)?;
+
+                    // Process batch if queue is full, offloaded to a blocking thread
+                    parser = process_batch_if_full_async(parser, &mut writer, &mut total_count).await?;
+                }
+            }
+            Ok(Event::Text(ref e)) => {
+                if in_target {
+                    element_buf.extend_from_slice(e.as_ref());
+                }
+            }
+            Ok(Event::Eof) => break,
+            Err(e) => return Err(OxidizeError::XmlParseError {
+                position: Some(xml_reader.buffer_position() as u64),
+                message: format!("Failed to parse XML: {}", e),
+            }),
+            _ => {} // Ignore other events
+        }
+
+        buf.clear();
+    }
+
+    // Process remaining elements on a blocking thread, same reasoning as batches above
+    let (_parser, final_results) = tokio::task::spawn_blocking(move || {
+        let mut parser = parser;
+        let results = parser.flush();
+        (parser, results)
+    })
+    .await
+    .map_err(|e| OxidizeError::IoError {
+        message: format!("Final batch processing task panicked: {}", e),
+    })?;
+    total_count += final_results.len();
+
+    for json_line in final_results {
+        writer.write_all(json_line.as_bytes()).await.map_err(|e| OxidizeError::IoError {
+            message: format!("Failed to write final JSON output: {}", e),
+        })?;
+        writer.write_all(b"\n").await.map_err(|e| OxidizeError::IoError {
+            message: format!("Failed to write final JSON output: {}", e),
+        })?;
+    }
+
+    Ok(total_count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn test_hybrid_stream_parser_new() {
+        let parser = HybridStreamParser::new(100);
+        assert_eq!(parser.batch_size, 100);
+        assert!(parser.element_queue.is_empty());
+    }
+
+    #[test]
+    fn test_basic_xml_parsing() {
+        let xml = r#"<?xml version="1.0"?>
+<root>
+    <Item id="1"><Value>100</Value></Item>
+    <Item id="2"><Value>200</Value></Item>
+</root>"#;
+
+        let reader = Cursor::new(xml.as_bytes());
+        let mut output = Vec::new();
+
+        let result = hybrid_stream_parse(reader, &mut output, "Item", 10);
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), 2);
+
+        let output_str = String::from_utf8(output).unwrap();
+        assert!(output_str.contains("\"id\":\"1\""));
+        assert!(output_str.contains("\"Value\":\"100\""));
+        assert!(output_str.contains("\"id\":\"2\""));
+    }
+
+    #[test]
+    fn test_empty_xml() {
+        let xml = "<?xml version=\"1.0\"?><root></root>";
+        let reader = Cursor::new(xml.as_bytes());
+        let mut output = Vec::new();
+
+        let result = hybrid_stream_parse(reader, &mut output, "Item", 10);
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), 0);
+        assert!(output.is_empty());
+    }
+
+    #[test]
+    fn test_batch_size_variations() {
+        let xml = r#"<?xml version="1.0"?>
 <root>
     <R id="1"/>
     <R id="2"/>
@@ -602,4 +1541,282 @@ mod tests {
         assert!(output_str.contains("\"Child\""));
         assert!(output_str.contains("\"GrandChild\""));
     }
+
+    #[test]
+    fn test_provenance_byte_ranges() {
+        let xml = r#"<?xml version="1.0"?>
+<root>
+    <Item id="1"><Value>100</Value></Item>
+    <Item id="2"/>
+</root>"#;
+
+        let reader = Cursor::new(xml.as_bytes());
+        let mut output = Vec::new();
+
+        let result = hybrid_stream_parse_with_provenance(reader, &mut output, "Item", 10);
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), 2);
+
+        let output_str = String::from_utf8(output).unwrap();
+        for line in output_str.lines() {
+            let value: serde_json::Value = serde_json::from_str(line).unwrap();
+            let range = &value["__byte_range"];
+            let start = range["start"].as_u64().unwrap();
+            let end = range["end"].as_u64().unwrap();
+            assert!(start < end);
+            // The byte range should point back at the literal "<Item" opening tag
+            assert!(xml.as_bytes()[start as usize..end as usize].starts_with(b"<Item"));
+        }
+
+        // Without provenance tracking, no __byte_range field is emitted
+        let reader = Cursor::new(xml.as_bytes());
+        let mut output = Vec::new();
+        hybrid_stream_parse(reader, &mut output, "Item", 10).unwrap();
+        let output_str = String::from_utf8(output).unwrap();
+        assert!(!output_str.contains("__byte_range"));
+    }
+
+    #[test]
+    fn test_provenance_byte_ranges_on_bare_leaf_elements() {
+        // No attributes and no children, so `XmlNode::to_json` produces a bare
+        // `String`/`Null` rather than an object: there's no object to attach
+        // `__byte_range` to directly, and it must not be silently dropped.
+        let xml = r#"<?xml version="1.0"?>
+<root>
+    <Item>100</Item>
+    <Item></Item>
+</root>"#;
+
+        let reader = Cursor::new(xml.as_bytes());
+        let mut output = Vec::new();
+
+        let result = hybrid_stream_parse_with_provenance(reader, &mut output, "Item", 10);
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), 2);
+
+        let output_str = String::from_utf8(output).unwrap();
+        for line in output_str.lines() {
+            let value: serde_json::Value = serde_json::from_str(line).unwrap();
+            let range = &value["__byte_range"];
+            let start = range["start"].as_u64().unwrap();
+            let end = range["end"].as_u64().unwrap();
+            assert!(start < end);
+            assert!(xml.as_bytes()[start as usize..end as usize].starts_with(b"<Item"));
+        }
+    }
+
+    #[test]
+    fn test_slice_parsing_matches_streamed() {
+        let xml = r#"<?xml version="1.0"?>
+<root>
+    <Item id="1"><Value>100</Value></Item>
+    <Item id="2" status="active"/>
+</root>"#;
+
+        let mut slice_output = Vec::new();
+        let slice_count = hybrid_stream_parse_slice(xml.as_bytes(), &mut slice_output, "Item", 10).unwrap();
+        assert_eq!(slice_count, 2);
+
+        let mut streamed_output = Vec::new();
+        let streamed_count =
+            hybrid_stream_parse(Cursor::new(xml.as_bytes()), &mut streamed_output, "Item", 10).unwrap();
+        assert_eq!(slice_count, streamed_count);
+
+        let slice_str = String::from_utf8(slice_output).unwrap();
+        assert!(slice_str.contains("\"id\":\"1\""));
+        assert!(slice_str.contains("\"Value\":\"100\""));
+        assert!(slice_str.contains("\"id\":\"2\""));
+        assert!(slice_str.contains("\"status\":\"active\""));
+    }
+
+    #[test]
+    fn test_builder_defaults_match_hardcoded_limits() {
+        let parser = HybridStreamParserBuilder::new().build();
+        assert_eq!(parser.max_element_size(), MAX_ELEMENT_SIZE);
+        assert_eq!(parser.batch_size, DEFAULT_BATCH_SIZE);
+    }
+
+    #[test]
+    fn test_builder_configures_limits_and_batch_size() {
+        let parser = HybridStreamParserBuilder::new()
+            .batch_size(5)
+            .max_element_size(128)
+            .max_attribute_count(2)
+            .max_attribute_size(16)
+            .max_element_depth(3)
+            .build();
+        assert_eq!(parser.batch_size, 5);
+        assert_eq!(parser.max_element_size(), 128);
+    }
+
+    #[test]
+    fn test_custom_depth_limit_rejects_deep_nesting() {
+        let xml = r#"<?xml version="1.0"?>
+<root>
+    <Item><A><B><C>too deep</C></B></A></Item>
+</root>"#;
+
+        let reader = Cursor::new(xml.as_bytes());
+        let mut output = Vec::new();
+        let limits = ParserLimits {
+            max_element_depth: 2,
+            ..ParserLimits::default()
+        };
+
+        let result = hybrid_stream_parse_with_limits(reader, &mut output, "Item", 10, limits);
+        assert!(matches!(result, Err(OxidizeError::InvalidInput { .. })));
+    }
+
+    #[test]
+    fn test_custom_depth_limit_allows_default_otherwise() {
+        let xml = r#"<?xml version="1.0"?>
+<root>
+    <Item><A><B><C>fine</C></B></A></Item>
+</root>"#;
+
+        let reader = Cursor::new(xml.as_bytes());
+        let mut output = Vec::new();
+        let result = hybrid_stream_parse(reader, &mut output, "Item", 10);
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), 1);
+    }
+
+    #[test]
+    fn test_ns_matches_prefixed_target_namespace() {
+        let xml = r#"<?xml version="1.0"?>
+<root xmlns:ns="http://example.com/ns">
+    <ns:Item id="1"/>
+    <Item id="2"/>
+</root>"#;
+
+        let reader = Cursor::new(xml.as_bytes());
+        let mut output = Vec::new();
+
+        let result = hybrid_stream_parse_ns(
+            reader,
+            &mut output,
+            (Some("http://example.com/ns"), "Item"),
+            10,
+        );
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), 1);
+
+        let output_str = String::from_utf8(output).unwrap();
+        assert!(output_str.contains("\"id\":\"1\""));
+        assert!(!output_str.contains("\"id\":\"2\""));
+    }
+
+    #[test]
+    fn test_ns_matches_default_namespace() {
+        let xml = r#"<?xml version="1.0"?>
+<root>
+    <Item xmlns="http://example.com/ns" id="1"/>
+</root>"#;
+
+        let reader = Cursor::new(xml.as_bytes());
+        let mut output = Vec::new();
+
+        let result = hybrid_stream_parse_ns(
+            reader,
+            &mut output,
+            (Some("http://example.com/ns"), "Item"),
+            10,
+        );
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), 1);
+    }
+
+    #[test]
+    fn test_ns_none_requires_unbound_element() {
+        let xml = r#"<?xml version="1.0"?>
+<root xmlns:ns="http://example.com/ns">
+    <ns:Item id="1"/>
+    <Item id="2"/>
+</root>"#;
+
+        let reader = Cursor::new(xml.as_bytes());
+        let mut output = Vec::new();
+
+        let result = hybrid_stream_parse_ns(reader, &mut output, (None, "Item"), 10);
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), 1);
+
+        let output_str = String::from_utf8(output).unwrap();
+        assert!(output_str.contains("\"id\":\"2\""));
+    }
+
+    #[test]
+    fn test_raw_bytes_fast_path_unaffected_by_namespaces() {
+        // hybrid_stream_parse should keep matching on the raw qualified name
+        // regardless of namespace declarations, since it never resolves them.
+        let xml = r#"<?xml version="1.0"?>
+<root xmlns:ns="http://example.com/ns">
+    <ns:Item id="1"/>
+    <Item id="2"/>
+</root>"#;
+
+        let reader = Cursor::new(xml.as_bytes());
+        let mut output = Vec::new();
+
+        let result = hybrid_stream_parse(reader, &mut output, "Item", 10);
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), 1);
+
+        let output_str = String::from_utf8(output).unwrap();
+        assert!(output_str.contains("\"id\":\"2\""));
+    }
+
+    #[test]
+    fn test_infer_schema_required_vs_optional_keys() {
+        let xml = r#"<?xml version="1.0"?>
+<root>
+    <Item id="1"><Name>Alice</Name><Note>hi</Note></Item>
+    <Item id="2"><Name>Bob</Name></Item>
+</root>"#;
+
+        let reader = Cursor::new(xml.as_bytes());
+        let schema = hybrid_stream_infer_schema(reader, "Item", 10).unwrap();
+
+        assert_eq!(schema["type"], "object");
+        let required = schema["required"].as_array().unwrap();
+        assert!(required.iter().any(|v| v == "id"));
+        assert!(required.iter().any(|v| v == "Name"));
+        assert!(!required.iter().any(|v| v == "Note"));
+        assert!(schema["properties"]["Note"]["type"] == "string");
+    }
+
+    #[test]
+    fn test_infer_schema_polymorphic_field_collapses_to_type_array() {
+        let xml = r#"<?xml version="1.0"?>
+<root>
+    <Item><Value>100</Value></Item>
+    <Item><Value>not-a-number</Value></Item>
+</root>"#;
+
+        let reader = Cursor::new(xml.as_bytes());
+        let schema = hybrid_stream_infer_schema(reader, "Item", 10).unwrap();
+
+        let value_type = schema["properties"]["Value"]["type"].as_array().unwrap();
+        assert!(value_type.iter().any(|v| v == "integer"));
+        assert!(value_type.iter().any(|v| v == "string"));
+    }
+
+    #[test]
+    fn test_infer_schema_merges_across_batches() {
+        // Two elements, batch size 1, forces two separate Rayon batches whose
+        // accumulators must be merged together in the final schema.
+        let xml = r#"<?xml version="1.0"?>
+<root>
+    <Item id="1"/>
+    <Item name="solo"/>
+</root>"#;
+
+        let reader = Cursor::new(xml.as_bytes());
+        let schema = hybrid_stream_infer_schema(reader, "Item", 1).unwrap();
+
+        assert!(schema["properties"]["id"]["type"] == "string");
+        assert!(schema["properties"]["name"]["type"] == "string");
+        // Neither key appears on every Item, so neither is required.
+        assert!(schema.get("required").is_none());
+    }
 }
\ No newline at end of file
@@ -0,0 +1,183 @@
+// Author: Dylan Jones
+// Date:   2025-06-15
+
+//! Automatic BPM/beat-grid analysis, populating `averagebpm` and `tempos`
+//! from the audio file at `PyTrack.location`.
+
+use super::errors::XmlError;
+use super::xml::PyTempo;
+use pyo3::prelude::*;
+use symphonia::core::audio::SampleBuffer;
+use symphonia::core::codecs::DECODER_TYPE_ANY;
+use symphonia::core::errors::Error as SymphoniaError;
+use symphonia::core::formats::FormatOptions;
+use symphonia::core::io::MediaSourceStream;
+use symphonia::core::meta::MetadataOptions;
+use symphonia::core::probe::Hint;
+
+const WINDOW_SIZE: usize = 1024;
+const HOP_SIZE: usize = 512;
+const MIN_BPM: f64 = 60.0;
+const MAX_BPM: f64 = 180.0;
+
+fn decode_mono_pcm(path: &str) -> PyResult<(Vec<f32>, u32)> {
+    let file = std::fs::File::open(path).map_err(|e| XmlError::new_err(e.to_string()))?;
+    let source = MediaSourceStream::new(Box::new(file), Default::default());
+
+    let mut hint = Hint::new();
+    if let Some(ext) = std::path::Path::new(path).extension().and_then(|e| e.to_str()) {
+        hint.with_extension(ext);
+    }
+
+    let probed = symphonia::default::get_probe()
+        .format(&hint, source, &FormatOptions::default(), &MetadataOptions::default())
+        .map_err(|e| XmlError::new_err(format!("Failed to probe audio file: {e}")))?;
+
+    let mut format = probed.format;
+    let track = format
+        .tracks()
+        .iter()
+        .find(|t| t.codec_params.codec != symphonia::core::codecs::CODEC_TYPE_NULL)
+        .ok_or_else(|| XmlError::new_err("No decodable audio track found"))?;
+    let track_id = track.id;
+    let sample_rate = track
+        .codec_params
+        .sample_rate
+        .ok_or_else(|| XmlError::new_err("Audio track has no sample rate"))?;
+
+    let mut decoder = symphonia::default::get_codecs()
+        .make(&track.codec_params, &Default::default())
+        .map_err(|e| XmlError::new_err(format!("Unsupported codec: {e}")))?;
+
+    let _ = DECODER_TYPE_ANY;
+    let mut samples: Vec<f32> = Vec::new();
+    loop {
+        let packet = match format.next_packet() {
+            Ok(packet) => packet,
+            Err(SymphoniaError::IoError(_)) | Err(SymphoniaError::ResetRequired) => break,
+            Err(e) => return Err(XmlError::new_err(format!("Demuxer error: {e}"))),
+        };
+        if packet.track_id() != track_id {
+            continue;
+        }
+        match decoder.decode(&packet) {
+            Ok(decoded) => {
+                let spec = *decoded.spec();
+                let mut buf = SampleBuffer::<f32>::new(decoded.capacity() as u64, spec);
+                buf.copy_interleaved_ref(decoded);
+                let channels = spec.channels.count().max(1);
+                for frame in buf.samples().chunks(channels) {
+                    let mono = frame.iter().sum::<f32>() / channels as f32;
+                    samples.push(mono);
+                }
+            }
+            Err(SymphoniaError::DecodeError(_)) => continue,
+            Err(e) => return Err(XmlError::new_err(format!("Decode error: {e}"))),
+        }
+    }
+
+    Ok((samples, sample_rate))
+}
+
+/// Spectral-flux onset envelope: per-frame sum of positive magnitude-spectrum
+/// differences across consecutive STFT windows.
+fn onset_envelope(samples: &[f32]) -> Vec<f64> {
+    if samples.len() < WINDOW_SIZE {
+        return Vec::new();
+    }
+
+    let mut prev_spectrum: Vec<f64> = vec![0.0; WINDOW_SIZE / 2];
+    let mut envelope = Vec::new();
+    let mut start = 0;
+    while start + WINDOW_SIZE <= samples.len() {
+        let spectrum = magnitude_spectrum(&samples[start..start + WINDOW_SIZE]);
+        let flux: f64 = spectrum
+            .iter()
+            .zip(prev_spectrum.iter())
+            .map(|(&mag, &prev)| (mag - prev).max(0.0))
+            .sum();
+        envelope.push(flux);
+        prev_spectrum = spectrum;
+        start += HOP_SIZE;
+    }
+    envelope
+}
+
+/// Naive DFT magnitude spectrum (first half of bins) of a windowed frame.
+fn magnitude_spectrum(frame: &[f32]) -> Vec<f64> {
+    let n = frame.len();
+    let mut windowed = vec![0.0_f64; n];
+    for (i, &s) in frame.iter().enumerate() {
+        // Hann window
+        let w = 0.5 - 0.5 * (2.0 * std::f64::consts::PI * i as f64 / (n - 1) as f64).cos();
+        windowed[i] = f64::from(s) * w;
+    }
+
+    let mut magnitudes = vec![0.0_f64; n / 2];
+    for (k, mag) in magnitudes.iter_mut().enumerate() {
+        let mut re = 0.0;
+        let mut im = 0.0;
+        for (i, &x) in windowed.iter().enumerate() {
+            let angle = -2.0 * std::f64::consts::PI * k as f64 * i as f64 / n as f64;
+            re += x * angle.cos();
+            im += x * angle.sin();
+        }
+        *mag = re.hypot(im);
+    }
+    magnitudes
+}
+
+/// Autocorrelate the onset envelope over the plausible BPM range and return
+/// the winning tempo.
+fn estimate_bpm(envelope: &[f64], sample_rate: u32) -> Option<f64> {
+    if envelope.is_empty() {
+        return None;
+    }
+    let frame_rate = sample_rate as f64 / HOP_SIZE as f64;
+    let min_lag = (frame_rate * 60.0 / MAX_BPM).round() as usize;
+    let max_lag = (frame_rate * 60.0 / MIN_BPM).round() as usize;
+    if max_lag >= envelope.len() || min_lag == 0 {
+        return None;
+    }
+
+    let mut best_lag = min_lag;
+    let mut best_score = f64::MIN;
+    for lag in min_lag..=max_lag {
+        let mut score = 0.0;
+        for i in 0..envelope.len() - lag {
+            score += envelope[i] * envelope[i + lag];
+        }
+        if score > best_score {
+            best_score = score;
+            best_lag = lag;
+        }
+    }
+
+    Some(frame_rate * 60.0 / best_lag as f64)
+}
+
+#[pymethods]
+impl super::xml::PyTrack {
+    /// Decode the audio at `location`, estimate tempo via spectral-flux onset
+    /// detection and autocorrelation, and populate `averagebpm`/`tempos`.
+    ///
+    /// Replaces any existing beat grid with a single constant-tempo `PyTempo`
+    /// starting at the beginning of the track.
+    pub fn analyze_bpm(&mut self, py: Python) -> PyResult<Option<f64>> {
+        let (samples, sample_rate) = decode_mono_pcm(&self.location)?;
+        let envelope = onset_envelope(&samples);
+        let bpm = estimate_bpm(&envelope, sample_rate);
+
+        if let Some(bpm) = bpm {
+            self.averagebpm = Some(bpm);
+            let tempo = PyTempo {
+                inizio: 0.0,
+                bpm,
+                metro: "4/4".to_string(),
+                battito: 1,
+            };
+            self.tempos = vec![Py::new(py, tempo)?];
+        }
+        Ok(bpm)
+    }
+}
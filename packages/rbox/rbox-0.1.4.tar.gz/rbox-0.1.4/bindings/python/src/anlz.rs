@@ -3,6 +3,7 @@
 
 use pyo3::prelude::*;
 use rbox::anlz::anlz::*;
+use rbox::anlz::render;
 
 use super::errors::AnlzError;
 use super::traits::IntoPy;
@@ -624,4 +625,50 @@ impl PyAnlz {
         }
         Ok(None)
     }
+
+    /// Rasterizes the track's waveform into an RGBA pixel buffer, preferring the
+    /// color-detail section (`PWV5`) and falling back to the 3-band section
+    /// (`PWV7`) if that's all the file has. `band_colors` is `(low, mid, high)`
+    /// and is only used for the 3-band fallback; it defaults to Rekordbox's own
+    /// dark blue/amber/white palette.
+    #[pyo3(signature = (width, height, band_colors=None))]
+    pub fn render_waveform_rgba(
+        &mut self,
+        width: u32,
+        height: u32,
+        band_colors: Option<((u8, u8, u8), (u8, u8, u8), (u8, u8, u8))>,
+    ) -> PyResult<Vec<u8>> {
+        if let Some(detail) = self.anlz.get_waveform_color_detail() {
+            return Ok(render::render_waveform_color_detail(&detail.data, width, height));
+        }
+        if let Some(detail) = self.anlz.get_waveform_3band_detail() {
+            let (low, mid, high) = band_colors.unwrap_or(((0, 0, 139), (255, 165, 0), (255, 255, 255)));
+            return Ok(render::render_waveform_3band_detail(
+                &detail.data,
+                width,
+                height,
+                low,
+                mid,
+                high,
+            ));
+        }
+        Err(PyErr::new::<AnlzError, _>(
+            "no waveform color or 3-band detail section present",
+        ))
+    }
+
+    /// Same as `render_waveform_rgba`, but writes the result straight to a PNG at `path`.
+    #[pyo3(signature = (path, width, height, band_colors=None))]
+    pub fn render_waveform_png(
+        &mut self,
+        path: &str,
+        width: u32,
+        height: u32,
+        band_colors: Option<((u8, u8, u8), (u8, u8, u8), (u8, u8, u8))>,
+    ) -> PyResult<()> {
+        let rgba = self.render_waveform_rgba(width, height, band_colors)?;
+        render::write_png(path, width, height, &rgba)
+            .map_err(|e| PyErr::new::<AnlzError, _>(e.to_string()))?;
+        Ok(())
+    }
 }
@@ -0,0 +1,126 @@
+// Author: Dylan Jones
+// Date:   2025-06-18
+
+//! Bulk `PyTrack` creation from a [beets](https://beets.io) music library.
+
+use super::errors::XmlError;
+use super::xml::PyTrack;
+use diesel::prelude::*;
+use diesel::sql_types::{Double, Integer, Nullable, Text};
+use pyo3::prelude::*;
+use pyo3::types::PyType;
+
+#[derive(QueryableByName)]
+struct BeetsItem {
+    #[diesel(sql_type = Integer)]
+    id: i32,
+    #[diesel(sql_type = Nullable<Text>)]
+    title: Option<String>,
+    #[diesel(sql_type = Nullable<Text>)]
+    artist: Option<String>,
+    #[diesel(sql_type = Nullable<Text>)]
+    album: Option<String>,
+    #[diesel(sql_type = Nullable<Text>)]
+    genre: Option<String>,
+    #[diesel(sql_type = Nullable<Integer>)]
+    year: Option<i32>,
+    #[diesel(sql_type = Nullable<Double>)]
+    bpm: Option<f64>,
+    #[diesel(sql_type = Nullable<Integer>)]
+    bitrate: Option<i32>,
+    #[diesel(sql_type = Nullable<Integer>)]
+    length: Option<i32>,
+    #[diesel(sql_type = Nullable<Text>)]
+    path: Option<String>,
+    #[diesel(sql_type = Nullable<Text>)]
+    comments: Option<String>,
+    #[diesel(sql_type = Nullable<Text>)]
+    label: Option<String>,
+    #[diesel(sql_type = Nullable<Integer>)]
+    disc: Option<i32>,
+    #[diesel(sql_type = Nullable<Integer>)]
+    track: Option<i32>,
+}
+
+/// Percent-encode a local path into a `file://localhost/` URI, mirroring the
+/// encoding `RekordboxXml` uses for `Track.location`.
+fn location_from_path(path: &str) -> String {
+    let mut out = String::from("file://localhost/");
+    for byte in path.replace('\\', "/").bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'_' | b'-' | b'.' | b':' | b'/' => {
+                out.push(byte as char);
+            }
+            _ => out.push_str(&format!("%{byte:02X}")),
+        }
+    }
+    out
+}
+
+#[pymethods]
+impl PyTrack {
+    /// Import every row of a beets `library.db` `items` table as `PyTrack`
+    /// objects, ready to be added to a `RekordboxXml`.
+    ///
+    /// `trackid`s are derived from the beets item id (`"beets-<id>"`) so
+    /// re-importing the same library produces stable ids.
+    #[classmethod]
+    pub fn from_beets_library(
+        _cls: &Bound<'_, PyType>,
+        py: Python,
+        db_path: &str,
+    ) -> PyResult<Vec<Py<PyTrack>>> {
+        let mut conn = SqliteConnection::establish(db_path)
+            .map_err(|e| XmlError::new_err(format!("Failed to open beets library: {e}")))?;
+
+        let items = diesel::sql_query(
+            "SELECT id, title, artist, album, genre, year, bpm, bitrate, length, path, \
+             comments, label, disc, track FROM items",
+        )
+        .load::<BeetsItem>(&mut conn)
+        .map_err(|e| XmlError::new_err(format!("Failed to query beets items: {e}")))?;
+
+        let mut tracks = Vec::with_capacity(items.len());
+        for item in items {
+            let location = item.path.map_or_else(String::new, |p| location_from_path(&p));
+            let track = PyTrack {
+                trackid: format!("beets-{}", item.id),
+                location,
+                name: item.title,
+                artist: item.artist,
+                composer: None,
+                album: item.album,
+                grouping: None,
+                genre: item.genre,
+                kind: None,
+                size: None,
+                totaltime: item.length,
+                discnumber: item.disc,
+                tracknumber: item.track,
+                year: item.year,
+                averagebpm: item.bpm,
+                datemodified: None,
+                dateadded: None,
+                bitrate: item.bitrate,
+                samplerate: None,
+                comments: item.comments,
+                playcount: None,
+                lastplayed: None,
+                rating: None,
+                remixer: None,
+                tonality: None,
+                label: item.label,
+                mix: None,
+                color: None,
+                tempos: Vec::new(),
+                position_marks: Vec::new(),
+                musicbrainz_id: None,
+                name_sort: None,
+                artist_sort: None,
+                album_sort: None,
+            };
+            tracks.push(Py::new(py, track)?);
+        }
+        Ok(tracks)
+    }
+}
@@ -0,0 +1,171 @@
+// Author: Dylan Jones
+// Date:   2025-06-01
+
+//! Import/export of standard CD cue sheets (`.cue`) for a track's position
+//! marks. Tempos are neither read from nor written to the cue sheet.
+
+use super::errors::XmlError;
+use super::xml::{PyPositionMark, PyTrack};
+use pyo3::prelude::*;
+
+/// Number of frames per second in a cue sheet timecode (`MM:SS:FF`).
+const FRAMES_PER_SECOND: f64 = 75.0;
+
+fn parse_timecode(s: &str) -> PyResult<f64> {
+    let parts: Vec<&str> = s.trim().split(':').collect();
+    if parts.len() != 3 {
+        return Err(XmlError::new_err(format!("Invalid cue timecode: '{s}'")));
+    }
+    let minutes: f64 = parts[0]
+        .parse()
+        .map_err(|_| XmlError::new_err(format!("Invalid cue timecode: '{s}'")))?;
+    let seconds: f64 = parts[1]
+        .parse()
+        .map_err(|_| XmlError::new_err(format!("Invalid cue timecode: '{s}'")))?;
+    let frames: f64 = parts[2]
+        .parse()
+        .map_err(|_| XmlError::new_err(format!("Invalid cue timecode: '{s}'")))?;
+    Ok(minutes * 60.0 + seconds + frames / FRAMES_PER_SECOND)
+}
+
+fn format_timecode(seconds: f64) -> String {
+    let total_frames = (seconds * FRAMES_PER_SECOND).round() as i64;
+    let frames = total_frames % 75;
+    let total_seconds = total_frames / 75;
+    let secs = total_seconds % 60;
+    let mins = total_seconds / 60;
+    format!("{mins:02}:{secs:02}:{frames:02}")
+}
+
+/// Strip a quoted cue sheet field, e.g. `"My Track"` -> `My Track`.
+fn unquote(s: &str) -> String {
+    s.trim().trim_matches('"').to_string()
+}
+
+#[pymethods]
+impl PyTrack {
+    /// Populate `position_marks` by parsing a standard CD cue sheet; `tempos`
+    /// is left empty.
+    ///
+    /// Only the `FILE`, `TRACK`, `INDEX`, `TITLE` and `PERFORMER` lines are
+    /// interpreted: a track's `TITLE` becomes the position mark's name, or its
+    /// `PERFORMER` when it has no `TITLE`. `REM` lines are ignored.
+    #[staticmethod]
+    pub fn from_cue(py: Python, trackid: &str, cue_text: &str) -> PyResult<Py<PyTrack>> {
+        let mut location = String::new();
+        let mut marks: Vec<Py<PyPositionMark>> = Vec::new();
+        let mut current_title: Option<String> = None;
+        let mut track_num: i32 = 0;
+
+        for line in cue_text.lines() {
+            let line = line.trim();
+            if let Some(rest) = line.strip_prefix("FILE ") {
+                // FILE "path" WAVE
+                if let Some(end) = rest.rfind('"') {
+                    if let Some(start) = rest[..end].find('"') {
+                        location = rest[start + 1..end].to_string();
+                    }
+                }
+            } else if let Some(rest) = line.strip_prefix("TRACK ") {
+                let fields: Vec<&str> = rest.split_whitespace().collect();
+                if let Some(num_str) = fields.first() {
+                    track_num = num_str.parse().unwrap_or(track_num + 1);
+                }
+                current_title = None;
+            } else if let Some(rest) = line.strip_prefix("TITLE ") {
+                current_title = Some(unquote(rest));
+            } else if let Some(rest) = line.strip_prefix("PERFORMER ") {
+                if current_title.is_none() {
+                    current_title = Some(unquote(rest));
+                }
+            } else if let Some(rest) = line.strip_prefix("INDEX ") {
+                let fields: Vec<&str> = rest.split_whitespace().collect();
+                if fields.len() != 2 {
+                    continue;
+                }
+                let index_num: i32 = fields[0].parse().unwrap_or(0);
+                let start = parse_timecode(fields[1])?;
+                // INDEX 00 is the pre-gap; only INDEX 01+ becomes a cue point
+                if index_num == 0 {
+                    continue;
+                }
+                let name = current_title
+                    .clone()
+                    .unwrap_or_else(|| format!("Track {track_num}"));
+                let mark = PyPositionMark {
+                    name,
+                    mark_type: 0,
+                    start,
+                    end: None,
+                    num: track_num - 1,
+                };
+                marks.push(Py::new(py, mark)?);
+            }
+        }
+
+        let track = PyTrack {
+            trackid: trackid.to_string(),
+            location,
+            name: None,
+            artist: None,
+            composer: None,
+            album: None,
+            grouping: None,
+            genre: None,
+            kind: None,
+            size: None,
+            totaltime: None,
+            discnumber: None,
+            tracknumber: None,
+            year: None,
+            averagebpm: None,
+            datemodified: None,
+            dateadded: None,
+            bitrate: None,
+            samplerate: None,
+            comments: None,
+            playcount: None,
+            lastplayed: None,
+            rating: None,
+            remixer: None,
+            tonality: None,
+            label: None,
+            mix: None,
+            color: None,
+            tempos: Vec::new(),
+            position_marks: marks,
+            musicbrainz_id: None,
+            name_sort: None,
+            artist_sort: None,
+            album_sort: None,
+        };
+        Py::new(py, track)
+    }
+
+    /// Render this track's `position_marks` as a standard CD cue sheet, one
+    /// `TRACK` per mark in order of `start`; `tempos` are not written.
+    pub fn to_cue(&self, py: Python) -> PyResult<String> {
+        let mut out = String::new();
+        out.push_str(&format!("FILE \"{}\" WAVE\n", self.location));
+
+        let mut marks: Vec<&Py<PyPositionMark>> = self.position_marks.iter().collect();
+        marks.sort_by(|a, b| {
+            a.borrow(py)
+                .start
+                .partial_cmp(&b.borrow(py).start)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+
+        for (i, mark) in marks.iter().enumerate() {
+            let mark = mark.borrow(py);
+            let track_num = i + 1;
+            out.push_str(&format!("  TRACK {track_num:02} AUDIO\n"));
+            out.push_str(&format!("    TITLE \"{}\"\n", mark.name));
+            out.push_str(&format!(
+                "    INDEX 01 {}\n",
+                format_timecode(mark.start)
+            ));
+        }
+        Ok(out)
+    }
+}
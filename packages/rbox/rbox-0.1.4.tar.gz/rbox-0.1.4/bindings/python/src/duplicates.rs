@@ -0,0 +1,121 @@
+// Author: Dylan Jones
+// Date:   2025-06-29
+
+//! Duplicate-track detection with a configurable bitflag set of fields that
+//! must match.
+
+use super::xml::{PyRekordboxXml, PyTrack};
+use pyo3::prelude::*;
+use std::collections::HashMap;
+
+/// Bitflags selecting which `PyTrack` fields must match for two tracks to be
+/// considered duplicates.
+pub const CRITERIA_TITLE: u32 = 1;
+pub const CRITERIA_ARTIST: u32 = 2;
+pub const CRITERIA_ALBUM: u32 = 4;
+pub const CRITERIA_YEAR: u32 = 8;
+pub const CRITERIA_LENGTH: u32 = 16;
+pub const CRITERIA_BITRATE: u32 = 32;
+pub const CRITERIA_TONALITY: u32 = 64;
+
+/// Lowercase, trim, and strip punctuation for a normalized comparison key.
+fn normalize(s: &str) -> String {
+    s.to_lowercase()
+        .chars()
+        .filter(|c| c.is_alphanumeric() || c.is_whitespace())
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn bucket_key(track: &PyTrack, criteria: u32) -> String {
+    let mut parts = Vec::new();
+    if criteria & CRITERIA_TITLE != 0 {
+        parts.push(normalize(track.name.as_deref().unwrap_or("")));
+    }
+    if criteria & CRITERIA_ARTIST != 0 {
+        parts.push(normalize(track.artist.as_deref().unwrap_or("")));
+    }
+    if criteria & CRITERIA_ALBUM != 0 {
+        parts.push(normalize(track.album.as_deref().unwrap_or("")));
+    }
+    if criteria & CRITERIA_YEAR != 0 {
+        parts.push(track.year.map_or_else(String::new, |y| y.to_string()));
+    }
+    if criteria & CRITERIA_BITRATE != 0 {
+        parts.push(track.bitrate.map_or_else(String::new, |b| b.to_string()));
+    }
+    if criteria & CRITERIA_TONALITY != 0 {
+        parts.push(normalize(track.tonality.as_deref().unwrap_or("")));
+    }
+    parts.join("\u{1}")
+}
+
+fn lengths_match(a: &PyTrack, b: &PyTrack, tolerance_secs: u32) -> bool {
+    match (a.totaltime, b.totaltime) {
+        (Some(a), Some(b)) => (a - b).unsigned_abs() <= tolerance_secs,
+        _ => false,
+    }
+}
+
+#[pymethods]
+impl PyRekordboxXml {
+    /// Group tracks considered duplicates under the given `criteria`
+    /// bitflags (`TITLE=1, ARTIST=2, ALBUM=4, YEAR=8, LENGTH=16, BITRATE=32,
+    /// TONALITY=64`). `LENGTH` is compared with `length_tolerance_secs`
+    /// (default 2s) rather than requiring an exact match.
+    #[pyo3(signature = (criteria, length_tolerance_secs=None))]
+    pub fn find_duplicates(
+        &mut self,
+        py: Python,
+        criteria: u32,
+        length_tolerance_secs: Option<u32>,
+    ) -> PyResult<Vec<Vec<Py<PyTrack>>>> {
+        let tolerance = length_tolerance_secs.unwrap_or(2);
+
+        let mut tracks = Vec::new();
+        for i in 0.. {
+            match self.get_track(i)? {
+                Some(track) => tracks.push(track.clone_ref(py)),
+                None => break,
+            }
+        }
+
+        let mut buckets: HashMap<String, Vec<usize>> = HashMap::new();
+        for (i, track) in tracks.iter().enumerate() {
+            let key = bucket_key(&track.borrow(py), criteria);
+            buckets.entry(key).or_default().push(i);
+        }
+
+        let mut groups = Vec::new();
+        for indices in buckets.into_values() {
+            if indices.len() < 2 {
+                continue;
+            }
+            if criteria & CRITERIA_LENGTH == 0 {
+                groups.push(indices.iter().map(|&i| tracks[i].clone_ref(py)).collect());
+                continue;
+            }
+            // LENGTH is a fuzzy criterion: re-partition the bucket so only
+            // tracks within tolerance of one another end up together.
+            let mut remaining = indices;
+            while let Some(seed) = remaining.pop() {
+                let mut group = vec![seed];
+                remaining.retain(|&i| {
+                    if lengths_match(&tracks[seed].borrow(py), &tracks[i].borrow(py), tolerance) {
+                        group.push(i);
+                        false
+                    } else {
+                        true
+                    }
+                });
+                if group.len() >= 2 {
+                    groups.push(group.iter().map(|&i| tracks[i].clone_ref(py)).collect());
+                }
+            }
+        }
+
+        Ok(groups)
+    }
+}
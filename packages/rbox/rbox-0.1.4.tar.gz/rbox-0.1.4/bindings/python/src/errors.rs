@@ -38,3 +38,10 @@ create_exception!(
     Error,
     "Exception raised for errors that are related to the Rekordbox MySetting handler."
 );
+
+create_exception!(
+    rbox,
+    FingerprintError,
+    Error,
+    "Exception raised for errors that are related to audio fingerprinting."
+);
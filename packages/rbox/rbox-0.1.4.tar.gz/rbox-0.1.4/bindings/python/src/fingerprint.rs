@@ -0,0 +1,27 @@
+// Author: Dylan Jones
+// Date:   2025-08-16
+
+use pyo3::prelude::*;
+use rbox::fingerprint::Fingerprint;
+
+use super::errors::FingerprintError;
+
+#[pyclass(unsendable)]
+#[derive(Clone)]
+pub struct PyFingerprint {
+    pub(crate) fingerprint: Fingerprint,
+}
+
+#[pymethods]
+impl PyFingerprint {
+    #[staticmethod]
+    pub fn compute(path: &str) -> PyResult<Self> {
+        let fingerprint =
+            Fingerprint::compute(path).map_err(|e| PyErr::new::<FingerprintError, _>(e.to_string()))?;
+        Ok(PyFingerprint { fingerprint })
+    }
+
+    pub fn compare(&self, other: &PyFingerprint) -> f32 {
+        self.fingerprint.compare(&other.fingerprint)
+    }
+}
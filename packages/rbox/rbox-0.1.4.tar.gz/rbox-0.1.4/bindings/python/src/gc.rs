@@ -0,0 +1,65 @@
+// Author: Dylan Jones
+// Date:   2025-07-01
+
+//! Garbage-collection of tracks that are no longer referenced by any
+//! playlist node.
+
+use super::xml::{PyPlaylistNode, PyRekordboxXml, PyTrack};
+use pyo3::prelude::*;
+use std::collections::HashSet;
+
+/// Recursively collect every `PlaylistTrack.key` referenced anywhere in the
+/// playlist tree.
+fn collect_referenced_keys(py: Python, node: &PyPlaylistNode, keys: &mut HashSet<String>) {
+    if let Some(tracks) = &node.tracks {
+        for track in tracks {
+            keys.insert(track.borrow(py).key.clone());
+        }
+    }
+    if let Some(nodes) = &node.nodes {
+        for child in nodes {
+            collect_referenced_keys(py, &child.borrow(py), keys);
+        }
+    }
+}
+
+fn is_referenced(track: &PyTrack, referenced: &HashSet<String>) -> bool {
+    referenced.contains(&track.trackid) || referenced.contains(&track.location)
+}
+
+#[pymethods]
+impl PyRekordboxXml {
+    /// Return every track not referenced (by trackid or location) from any
+    /// node in `root_playlist`.
+    pub fn find_orphan_tracks(&mut self, py: Python) -> PyResult<Vec<Py<PyTrack>>> {
+        let mut referenced = HashSet::new();
+        collect_referenced_keys(py, &self.root_playlist.borrow(py), &mut referenced);
+
+        let mut orphans = Vec::new();
+        for i in 0.. {
+            let track = match self.get_track(i)? {
+                Some(track) => track.clone_ref(py),
+                None => break,
+            };
+            if !is_referenced(&track.borrow(py), &referenced) {
+                orphans.push(track);
+            }
+        }
+        Ok(orphans)
+    }
+
+    /// Remove every track not referenced by any playlist node.
+    ///
+    /// With `dry_run=True`, only reports the count that would be removed.
+    /// Returns the number of tracks removed (or that would be removed).
+    #[pyo3(signature = (dry_run=false))]
+    pub fn prune_orphan_tracks(&mut self, py: Python, dry_run: bool) -> PyResult<usize> {
+        let orphans = self.find_orphan_tracks(py)?;
+        if !dry_run {
+            for track in &orphans {
+                self.remove_track(py, &track.borrow(py).trackid)?;
+            }
+        }
+        Ok(orphans.len())
+    }
+}
@@ -1,18 +1,26 @@
 // Author: Dylan Jones
 // Date:   2025-07-06
 
+use pyo3::exceptions::{PyStopAsyncIteration, PyStopIteration};
 use pyo3::prelude::*;
 
+/// A boxed Rust iterator, e.g. a filter/map chain over a mirror index, that isn't required to
+/// know its exact remaining length up front. `__length_hint__` reports `size_hint()`'s lower
+/// bound, which CPython's protocol already treats as an estimate rather than an exact count.
+type BoxedIter<T> = Box<dyn Iterator<Item = T> + Send>;
+
 #[pyclass]
 pub struct PyStrIter {
-    pub inner: std::vec::IntoIter<String>,
+    pub inner: BoxedIter<String>,
 }
 
 impl PyStrIter {
     pub fn new(items: Vec<String>) -> Self {
-        Self {
-            inner: items.into_iter(),
-        }
+        Self::from_boxed(Box::new(items.into_iter()))
+    }
+
+    pub fn from_boxed(inner: BoxedIter<String>) -> Self {
+        Self { inner }
     }
 }
 
@@ -25,17 +33,37 @@ impl PyStrIter {
     pub fn __next__(mut slf: PyRefMut<'_, Self>) -> Option<String> {
         slf.inner.next()
     }
+
+    pub fn __length_hint__(slf: PyRef<'_, Self>) -> usize {
+        slf.inner.size_hint().0
+    }
 }
 
 #[pyclass]
 pub struct PyObjectIter {
-    pub inner: std::vec::IntoIter<PyObject>,
+    pub inner: BoxedIter<PyObject>,
+    /// Value to raise as the `StopIteration` argument once `inner` is exhausted, so a caller
+    /// delegating to this iterator via `yield from` receives a completion value.
+    pub final_value: Option<PyObject>,
+    done: bool,
 }
 
 impl PyObjectIter {
     pub fn new(items: Vec<PyObject>) -> Self {
+        Self::from_boxed(Box::new(items.into_iter()))
+    }
+
+    pub fn with_final(items: Vec<PyObject>, final_value: PyObject) -> Self {
+        let mut iter = Self::new(items);
+        iter.final_value = Some(final_value);
+        iter
+    }
+
+    pub fn from_boxed(inner: BoxedIter<PyObject>) -> Self {
         Self {
-            inner: items.into_iter(),
+            inner,
+            final_value: None,
+            done: false,
         }
     }
 }
@@ -46,20 +74,146 @@ impl PyObjectIter {
         slf
     }
 
-    pub fn __next__(mut slf: PyRefMut<'_, Self>) -> Option<PyObject> {
-        slf.inner.next()
+    pub fn __next__(mut slf: PyRefMut<'_, Self>) -> PyResult<PyObject> {
+        if let Some(item) = slf.inner.next() {
+            return Ok(item);
+        }
+        if slf.done {
+            return Err(PyStopIteration::new_err(()));
+        }
+        slf.done = true;
+        let final_value = slf.final_value.take();
+        Err(PyStopIteration::new_err((final_value,)))
+    }
+
+    pub fn __length_hint__(slf: PyRef<'_, Self>) -> usize {
+        slf.inner.size_hint().0
+    }
+}
+
+/// A pre-resolved Python awaitable: the first drive through `__await__`'s iterator protocol
+/// immediately raises `StopIteration(value)`, so `await` on it resolves to `value` without
+/// suspending. Used by the `Async*Iter` types to hand items to `async for` without an event loop.
+#[pyclass]
+pub struct ImmediateFuture {
+    value: Option<PyObject>,
+}
+
+impl ImmediateFuture {
+    fn ready(value: PyObject) -> Self {
+        Self { value: Some(value) }
+    }
+}
+
+#[pymethods]
+impl ImmediateFuture {
+    pub fn __await__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    pub fn __iter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    pub fn __next__(mut slf: PyRefMut<'_, Self>) -> PyResult<PyObject> {
+        match slf.value.take() {
+            Some(value) => Err(PyStopIteration::new_err((value,))),
+            None => Err(PyStopIteration::new_err(())),
+        }
+    }
+}
+
+#[pyclass]
+pub struct AsyncPyStrIter {
+    pub inner: BoxedIter<String>,
+}
+
+impl AsyncPyStrIter {
+    pub fn new(items: Vec<String>) -> Self {
+        Self::from_boxed(Box::new(items.into_iter()))
+    }
+
+    pub fn from_boxed(inner: BoxedIter<String>) -> Self {
+        Self { inner }
+    }
+}
+
+#[pymethods]
+impl AsyncPyStrIter {
+    pub fn __aiter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    pub fn __anext__(mut slf: PyRefMut<'_, Self>, py: Python<'_>) -> PyResult<ImmediateFuture> {
+        match slf.inner.next() {
+            Some(item) => Ok(ImmediateFuture::ready(item.into_py(py))),
+            None => Err(PyStopAsyncIteration::new_err(())),
+        }
+    }
+
+    pub fn __length_hint__(slf: PyRef<'_, Self>) -> usize {
+        slf.inner.size_hint().0
+    }
+}
+
+#[pyclass]
+pub struct AsyncPyObjectIter {
+    pub inner: BoxedIter<PyObject>,
+}
+
+impl AsyncPyObjectIter {
+    pub fn new(items: Vec<PyObject>) -> Self {
+        Self::from_boxed(Box::new(items.into_iter()))
+    }
+
+    pub fn from_boxed(inner: BoxedIter<PyObject>) -> Self {
+        Self { inner }
+    }
+}
+
+#[pymethods]
+impl AsyncPyObjectIter {
+    pub fn __aiter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    pub fn __anext__(mut slf: PyRefMut<'_, Self>) -> PyResult<ImmediateFuture> {
+        match slf.inner.next() {
+            Some(item) => Ok(ImmediateFuture::ready(item)),
+            None => Err(PyStopAsyncIteration::new_err(())),
+        }
+    }
+
+    pub fn __length_hint__(slf: PyRef<'_, Self>) -> usize {
+        slf.inner.size_hint().0
     }
 }
 
 #[pyclass]
 pub struct PyItemsIter {
-    pub inner: std::vec::IntoIter<(String, PyObject)>,
+    pub inner: BoxedIter<(String, PyObject)>,
+    /// Value to raise as the `StopIteration` argument once `inner` is exhausted, so a caller
+    /// delegating to this iterator via `yield from` receives a completion value.
+    pub final_value: Option<PyObject>,
+    done: bool,
 }
 
 impl PyItemsIter {
     pub fn new(items: Vec<(String, PyObject)>) -> Self {
+        Self::from_boxed(Box::new(items.into_iter()))
+    }
+
+    pub fn with_final(items: Vec<(String, PyObject)>, final_value: PyObject) -> Self {
+        let mut iter = Self::new(items);
+        iter.final_value = Some(final_value);
+        iter
+    }
+
+    pub fn from_boxed(inner: BoxedIter<(String, PyObject)>) -> Self {
         Self {
-            inner: items.into_iter(),
+            inner,
+            final_value: None,
+            done: false,
         }
     }
 }
@@ -70,7 +224,19 @@ impl PyItemsIter {
         slf
     }
 
-    pub fn __next__(mut slf: PyRefMut<'_, Self>) -> Option<(String, PyObject)> {
-        slf.inner.next()
+    pub fn __next__(mut slf: PyRefMut<'_, Self>) -> PyResult<(String, PyObject)> {
+        if let Some(item) = slf.inner.next() {
+            return Ok(item);
+        }
+        if slf.done {
+            return Err(PyStopIteration::new_err(()));
+        }
+        slf.done = true;
+        let final_value = slf.final_value.take();
+        Err(PyStopIteration::new_err((final_value,)))
+    }
+
+    pub fn __length_hint__(slf: PyRef<'_, Self>) -> usize {
+        slf.inner.size_hint().0
     }
 }
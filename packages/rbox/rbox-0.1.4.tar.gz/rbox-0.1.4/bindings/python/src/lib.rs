@@ -1,12 +1,25 @@
 // Author: Dylan Jones
 // Date:   2025-05-01
 
+mod analysis;
 mod anlz;
+mod beets;
+mod cue;
+mod duplicates;
 mod errors;
+mod fingerprint;
+mod gc;
 mod iter;
+mod m3u8;
 mod masterdb;
+mod merge;
+mod musicbrainz;
 mod py_models;
+mod query;
+mod scan;
+mod search;
 mod settings;
+mod sources;
 mod traits;
 mod xml;
 
@@ -117,10 +130,17 @@ fn _rbox(m: &Bound<PyModule>) -> PyResult<()> {
 
     m.add_class::<settings::PySetting>()?;
 
+    m.add_class::<fingerprint::PyFingerprint>()?;
+    m.add_class::<sources::PySource>()?;
+
     m.add("Error", m.py().get_type::<errors::Error>())?;
     m.add("DatabaseError", m.py().get_type::<errors::DatabaseError>())?;
     m.add("AnlzError", m.py().get_type::<errors::AnlzError>())?;
     m.add("XmlError", m.py().get_type::<errors::XmlError>())?;
     m.add("SettingError", m.py().get_type::<errors::SettingError>())?;
+    m.add(
+        "FingerprintError",
+        m.py().get_type::<errors::FingerprintError>(),
+    )?;
     Ok(())
 }
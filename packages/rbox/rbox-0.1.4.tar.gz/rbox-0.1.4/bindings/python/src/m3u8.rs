@@ -0,0 +1,177 @@
+// Author: Dylan Jones
+// Date:   2025-06-08
+
+//! Export/import of the `PlaylistNode` tree to extended M3U/M3U8 playlists.
+
+use super::errors::XmlError;
+use super::xml::{PyPlaylistNode, PyRekordboxXml};
+use pyo3::prelude::*;
+use std::fs;
+use std::path::Path;
+
+fn track_display_name(py: Python, xml: &mut PyRekordboxXml, key: &str, key_type: u16) -> (String, String, i32) {
+    let track = if key_type == 0 {
+        xml.get_track_by_id(py, key).ok().flatten().cloned()
+    } else {
+        xml.get_track_by_location(py, key).ok().flatten().cloned()
+    };
+    match track {
+        Some(t) => {
+            let t = t.borrow(py);
+            let artist = t.artist.clone().unwrap_or_default();
+            let name = t.name.clone().unwrap_or_default();
+            (t.location.clone(), format!("{artist} - {name}"), t.totaltime.unwrap_or(0))
+        }
+        None => (key.to_string(), key.to_string(), -1),
+    }
+}
+
+fn decode_file_uri(location: &str) -> String {
+    location
+        .strip_prefix("file://localhost/")
+        .or_else(|| location.strip_prefix("file://"))
+        .unwrap_or(location)
+        .to_string()
+}
+
+fn write_playlist_m3u8(
+    py: Python,
+    xml: &mut PyRekordboxXml,
+    node: &PyPlaylistNode,
+    output_path: &Path,
+) -> PyResult<()> {
+    let mut out = String::from("#EXTM3U\n");
+    if let Some(tracks) = &node.tracks {
+        let key_type = node.key_type.unwrap_or(0);
+        for track in tracks {
+            let key = track.borrow(py).key.clone();
+            let (location, display, totaltime) = track_display_name(py, xml, &key, key_type);
+            out.push_str(&format!("#EXTINF:{totaltime},{display}\n"));
+            out.push_str(&decode_file_uri(&location));
+            out.push('\n');
+        }
+    }
+    fs::write(output_path, out).map_err(|e| XmlError::new_err(e.to_string()))
+}
+
+fn export_node(
+    py: Python,
+    xml: &mut PyRekordboxXml,
+    node: &PyPlaylistNode,
+    dir: &Path,
+    count: &mut usize,
+) -> PyResult<()> {
+    if node.node_type == 1 {
+        let file_name = format!("{}.m3u8", node.name);
+        write_playlist_m3u8(py, xml, node, &dir.join(file_name))?;
+        *count += 1;
+        return Ok(());
+    }
+
+    let sub_dir = dir.join(&node.name);
+    fs::create_dir_all(&sub_dir).map_err(|e| XmlError::new_err(e.to_string()))?;
+    if let Some(nodes) = &node.nodes {
+        for child in nodes {
+            export_node(py, xml, &child.borrow(py), &sub_dir, count)?;
+        }
+    }
+    Ok(())
+}
+
+#[pymethods]
+impl PyRekordboxXml {
+    /// Export the playlist tree as extended M3U8 files under `output_dir`.
+    ///
+    /// Folders in the tree become directories; each leaf playlist becomes one
+    /// `<name>.m3u8` file. Returns the number of `.m3u8` files written.
+    pub fn to_m3u8(&mut self, py: Python, output_dir: &str) -> PyResult<usize> {
+        let root = self.root_playlist.clone_ref(py);
+        let root = root.borrow(py);
+        let dir = Path::new(output_dir);
+        fs::create_dir_all(dir).map_err(|e| XmlError::new_err(e.to_string()))?;
+        let mut count = 0;
+        if let Some(nodes) = &root.nodes {
+            for child in nodes {
+                export_node(py, self, &child.borrow(py), dir, &mut count)?;
+            }
+        }
+        Ok(count)
+    }
+
+    /// Import a single extended M3U8 playlist as a new playlist node under
+    /// `root_playlist` (or the folder at `parent_path`).
+    ///
+    /// Tracks are matched against the collection by decoded file path; any
+    /// entry with no match is skipped.
+    pub fn from_m3u8(
+        &mut self,
+        py: Python,
+        path: &str,
+        name: &str,
+        parent_path: Vec<String>,
+    ) -> PyResult<usize> {
+        let content = fs::read_to_string(path).map_err(|e| XmlError::new_err(e.to_string()))?;
+
+        let mut locations = Vec::new();
+        for line in content.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with("#EXTM3U") || line.starts_with("#EXTINF") {
+                continue;
+            }
+            locations.push(line.to_string());
+        }
+
+        let new_node = PyPlaylistNode {
+            name: name.to_string(),
+            node_type: 1,
+            key_type: Some(1), // Location
+            tracks: Some(Vec::new()),
+            nodes: Some(Vec::new()),
+            node_path: parent_path.clone(),
+        };
+        let py_node = Py::new(py, new_node)?;
+
+        let mut imported = 0;
+        {
+            let mut node_mut = py_node.borrow_mut(py);
+            for file_path in &locations {
+                if self
+                    .get_track_by_location(py, file_path)
+                    .ok()
+                    .flatten()
+                    .is_some()
+                {
+                    node_mut.new_track(py, file_path)?;
+                    imported += 1;
+                }
+            }
+        }
+
+        let parent = self.find_node_by_path(py, &parent_path)?;
+        parent
+            .borrow_mut(py)
+            .nodes
+            .get_or_insert_with(Vec::new)
+            .push(py_node);
+
+        Ok(imported)
+    }
+}
+
+impl PyRekordboxXml {
+    /// Resolve a playlist folder by its path of node names, starting at the root.
+    fn find_node_by_path(&mut self, py: Python, path: &[String]) -> PyResult<Py<PyPlaylistNode>> {
+        let mut current = self.root_playlist.clone_ref(py);
+        for name in path {
+            let next = {
+                let node = current.borrow(py);
+                node.nodes
+                    .as_ref()
+                    .and_then(|nodes| nodes.iter().find(|n| &n.borrow(py).name == name))
+                    .map(|n| n.clone_ref(py))
+            };
+            current = next.ok_or_else(|| XmlError::new_err(format!("Playlist folder not found: {name}")))?;
+        }
+        Ok(current)
+    }
+}
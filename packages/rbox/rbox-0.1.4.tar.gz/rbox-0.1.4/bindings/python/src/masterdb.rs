@@ -1,12 +1,17 @@
 // Author: Dylan Jones
 // Date:   2025-05-01
 
+use std::collections::HashMap;
+use std::path::PathBuf;
+
 use pyo3::prelude::*;
 use pyo3::types::{PyDict, PyList, PyString, PyType};
 use rbox::masterdb::{enums::PlaylistType, models::*, MasterDb};
 
 use super::errors::DatabaseError;
+use super::fingerprint::PyFingerprint;
 use super::py_models::*;
+use super::sources::{provider_from_str, PySource};
 use super::traits::{FromPy, IntoPy};
 
 #[pyclass(unsendable)]
@@ -511,6 +516,50 @@ impl PyMasterDb {
         Ok(())
     }
 
+    pub fn update_content_folder_path(&mut self, id: &str, folder_path: &str) -> PyResult<()> {
+        self.db
+            .update_content_folder_path(id, folder_path)
+            .map_err(|e| PyErr::new::<DatabaseError, _>(e.to_string()))?;
+        Ok(())
+    }
+
+    pub fn find_duplicates(&mut self, threshold: f32) -> PyResult<Vec<Vec<String>>> {
+        self.db
+            .find_duplicate_content(threshold)
+            .map_err(|e| PyErr::new::<DatabaseError, _>(e.to_string()))
+    }
+
+    #[pyo3(signature = (known_fingerprints, search_roots))]
+    pub fn relink_missing(
+        &mut self,
+        known_fingerprints: HashMap<String, PyRef<'_, PyFingerprint>>,
+        search_roots: Vec<String>,
+    ) -> PyResult<usize> {
+        let known_fingerprints = known_fingerprints
+            .into_iter()
+            .map(|(id, fp)| (id, fp.fingerprint.clone()))
+            .collect();
+        let search_roots: Vec<PathBuf> = search_roots.into_iter().map(PathBuf::from).collect();
+
+        self.db
+            .relink_missing_content(&known_fingerprints, &search_roots)
+            .map_err(|e| PyErr::new::<DatabaseError, _>(e.to_string()))
+    }
+
+    pub fn content_sources(&mut self, content_id: &str) -> PyResult<Vec<PySource>> {
+        let sources = self
+            .db
+            .content_sources(content_id)
+            .map_err(|e| PyErr::new::<DatabaseError, _>(e.to_string()))?;
+        Ok(sources.into_iter().map(PySource::from).collect())
+    }
+
+    pub fn add_content_source(&mut self, content_id: &str, provider: &str, url: &str) -> PyResult<()> {
+        self.db
+            .add_content_source(content_id, provider_from_str(provider), url.to_string())
+            .map_err(|e| PyErr::new::<DatabaseError, _>(e.to_string()))
+    }
+
     // pub fn delete_content(&mut self, id: &str) -> PyResult<()> {
     //     self.db
     //         .delete_content(id)
@@ -1168,6 +1217,26 @@ impl PyMasterDb {
         Ok(())
     }
 
+    pub fn export_playlist(&mut self, id: &str, format: &str, path: &str) -> PyResult<usize> {
+        self.db
+            .export_playlist(id, format, path)
+            .map_err(|e| PyErr::new::<DatabaseError, _>(e.to_string()))
+    }
+
+    #[pyo3(signature = (path, parent_id=None))]
+    pub fn import_playlist(
+        &mut self,
+        py: Python,
+        path: &str,
+        parent_id: Option<String>,
+    ) -> PyResult<PyDjmdPlaylist> {
+        let model = self
+            .db
+            .import_playlist(path, parent_id)
+            .map_err(|e| PyErr::new::<DatabaseError, _>(e.to_string()))?;
+        model.into_py(py)
+    }
+
     // -- Property ---------------------------------------------------------------------------------
 
     pub fn get_property(&mut self, py: Python) -> PyResult<Py<PyList>> {
@@ -1308,6 +1377,26 @@ impl PyMasterDb {
         Ok(PyList::new(py, items)?.into())
     }
 
+    #[pyo3(signature = (embeddings, k, threshold, bpm_tolerance=None))]
+    pub fn build_related_tracks(
+        &mut self,
+        py: Python,
+        embeddings: HashMap<String, Vec<f32>>,
+        k: usize,
+        threshold: f32,
+        bpm_tolerance: Option<i32>,
+    ) -> PyResult<Py<PyList>> {
+        let models = self
+            .db
+            .build_related_tracks(&embeddings, k, threshold, bpm_tolerance)
+            .map_err(|e| PyErr::new::<DatabaseError, _>(e.to_string()))?;
+        let items = models
+            .into_iter()
+            .map(|m| m.into_py(py).unwrap())
+            .collect::<Vec<_>>();
+        Ok(PyList::new(py, items)?.into())
+    }
+
     // -- Sampler ----------------------------------------------------------------------------------
 
     pub fn get_sampler(&mut self, py: Python) -> PyResult<Py<PyList>> {
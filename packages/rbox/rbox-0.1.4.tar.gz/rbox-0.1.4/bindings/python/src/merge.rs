@@ -0,0 +1,214 @@
+// Author: Dylan Jones
+// Date:   2025-07-06
+
+//! Merge two Rekordbox collections with field-level reconciliation.
+
+use super::errors::XmlError;
+use super::xml::{PyPlaylistNode, PyRekordboxXml, PyTrack};
+use pyo3::prelude::*;
+use std::collections::{HashMap, HashSet};
+
+/// Keep whatever value is already on the track in `self`.
+pub const KEEP_EXISTING: u8 = 0;
+/// Always take the incoming value when one is present.
+pub const PREFER_INCOMING: u8 = 1;
+/// Only take the incoming value when the existing field is empty.
+pub const FILL_EMPTY: u8 = 2;
+
+fn merge_field<T: Clone>(existing: &mut Option<T>, incoming: &Option<T>, strategy: u8) {
+    match strategy {
+        PREFER_INCOMING => {
+            if incoming.is_some() {
+                *existing = incoming.clone();
+            }
+        }
+        FILL_EMPTY => {
+            if existing.is_none() {
+                *existing = incoming.clone();
+            }
+        }
+        _ => {} // KEEP_EXISTING
+    }
+}
+
+fn merge_track_fields(existing: &mut PyTrack, incoming: &PyTrack, strategy: u8) {
+    merge_field(&mut existing.name, &incoming.name, strategy);
+    merge_field(&mut existing.artist, &incoming.artist, strategy);
+    merge_field(&mut existing.composer, &incoming.composer, strategy);
+    merge_field(&mut existing.album, &incoming.album, strategy);
+    merge_field(&mut existing.grouping, &incoming.grouping, strategy);
+    merge_field(&mut existing.genre, &incoming.genre, strategy);
+    merge_field(&mut existing.kind, &incoming.kind, strategy);
+    merge_field(&mut existing.size, &incoming.size, strategy);
+    merge_field(&mut existing.totaltime, &incoming.totaltime, strategy);
+    merge_field(&mut existing.discnumber, &incoming.discnumber, strategy);
+    merge_field(&mut existing.tracknumber, &incoming.tracknumber, strategy);
+    merge_field(&mut existing.year, &incoming.year, strategy);
+    merge_field(&mut existing.averagebpm, &incoming.averagebpm, strategy);
+    merge_field(&mut existing.bitrate, &incoming.bitrate, strategy);
+    merge_field(&mut existing.samplerate, &incoming.samplerate, strategy);
+    merge_field(&mut existing.comments, &incoming.comments, strategy);
+    merge_field(&mut existing.rating, &incoming.rating, strategy);
+    merge_field(&mut existing.remixer, &incoming.remixer, strategy);
+    merge_field(&mut existing.tonality, &incoming.tonality, strategy);
+    merge_field(&mut existing.label, &incoming.label, strategy);
+    merge_field(&mut existing.mix, &incoming.mix, strategy);
+    merge_field(&mut existing.color, &incoming.color, strategy);
+    merge_field(&mut existing.musicbrainz_id, &incoming.musicbrainz_id, strategy);
+    merge_field(&mut existing.name_sort, &incoming.name_sort, strategy);
+    merge_field(&mut existing.artist_sort, &incoming.artist_sort, strategy);
+    merge_field(&mut existing.album_sort, &incoming.album_sort, strategy);
+
+    if existing.tempos.is_empty() {
+        existing.tempos = incoming.tempos.clone();
+    }
+    if existing.position_marks.is_empty() {
+        existing.position_marks = incoming.position_marks.clone();
+    }
+}
+
+fn unique_trackid(taken: &HashSet<String>, base: &str) -> String {
+    if !taken.contains(base) {
+        return base.to_string();
+    }
+    let mut suffix = 1;
+    loop {
+        let candidate = format!("{base}-merged{suffix}");
+        if !taken.contains(&candidate) {
+            return candidate;
+        }
+        suffix += 1;
+    }
+}
+
+/// Rebuild `node` (and its descendants) with trackid-keyed playlist entries
+/// remapped through `trackid_remap`, leaving location-keyed entries as-is.
+fn remap_node_keys(py: Python, node: &PyPlaylistNode, trackid_remap: &HashMap<String, String>) -> PyResult<PyPlaylistNode> {
+    let tracks = match &node.tracks {
+        Some(tracks) => {
+            let mut remapped = Vec::with_capacity(tracks.len());
+            for track in tracks {
+                let track = track.borrow(py);
+                let key = if node.key_type == Some(0) {
+                    trackid_remap.get(&track.key).cloned().unwrap_or_else(|| track.key.clone())
+                } else {
+                    track.key.clone()
+                };
+                remapped.push(Py::new(py, super::xml::PyPlaylistTrack::new(&key)?)?);
+            }
+            Some(remapped)
+        }
+        None => None,
+    };
+
+    let nodes = match &node.nodes {
+        Some(nodes) => {
+            let mut remapped = Vec::with_capacity(nodes.len());
+            for child in nodes {
+                let child = remap_node_keys(py, &child.borrow(py), trackid_remap)?;
+                remapped.push(Py::new(py, child)?);
+            }
+            Some(remapped)
+        }
+        None => None,
+    };
+
+    Ok(PyPlaylistNode {
+        name: node.name.clone(),
+        node_type: node.node_type,
+        key_type: node.key_type,
+        tracks,
+        nodes,
+        node_path: node.node_path.clone(),
+    })
+}
+
+#[pymethods]
+impl PyRekordboxXml {
+    /// Merge `other`'s tracks and playlist tree into this collection.
+    ///
+    /// Tracks are matched by `location` (falling back to `trackid`);
+    /// matched tracks are reconciled field-by-field per `strategy`
+    /// (`KEEP_EXISTING=0`, `PREFER_INCOMING=1`, `FILL_EMPTY=2`), taking the
+    /// non-empty `tempos`/`position_marks` set when one side has none.
+    /// Unmatched incoming tracks are appended with remapped `trackid`s to
+    /// avoid collisions, and `other`'s `root_playlist` children are grafted
+    /// under a new folder named `target_folder` at the root of `self`.
+    #[pyo3(signature = (other, strategy, target_folder="Merged"))]
+    pub fn merge(
+        &mut self,
+        py: Python,
+        mut other: PyRefMut<'_, PyRekordboxXml>,
+        strategy: u8,
+        target_folder: &str,
+    ) -> PyResult<()> {
+        let mut existing_locations = HashMap::new();
+        let mut existing_ids = HashSet::new();
+        for i in 0.. {
+            match self.get_track(i)? {
+                Some(track) => {
+                    let track = track.borrow(py);
+                    existing_locations.insert(track.location.clone(), track.trackid.clone());
+                    existing_ids.insert(track.trackid.clone());
+                }
+                None => break,
+            }
+        }
+
+        let mut trackid_remap = HashMap::new();
+        for i in 0.. {
+            let incoming = match other.get_track(i)? {
+                Some(track) => track.clone_ref(py),
+                None => break,
+            };
+
+            let (incoming_location, incoming_trackid) = {
+                let incoming = incoming.borrow(py);
+                (incoming.location.clone(), incoming.trackid.clone())
+            };
+
+            let matched_id = existing_locations
+                .get(&incoming_location)
+                .cloned()
+                .or_else(|| existing_ids.contains(&incoming_trackid).then(|| incoming_trackid.clone()));
+
+            if let Some(existing_id) = matched_id {
+                trackid_remap.insert(incoming_trackid, existing_id.clone());
+                let existing = self
+                    .get_track_by_id(py, &existing_id)?
+                    .ok_or_else(|| XmlError::new_err("Inconsistent track index"))?
+                    .clone_ref(py);
+                let mut existing_mut = existing.borrow_mut(py);
+                let incoming_ref = incoming.borrow(py);
+                merge_track_fields(&mut existing_mut, &incoming_ref, strategy);
+            } else {
+                let new_id = unique_trackid(&existing_ids, &incoming_trackid);
+                existing_ids.insert(new_id.clone());
+                existing_locations.insert(incoming_location, new_id.clone());
+                trackid_remap.insert(incoming_trackid.clone(), new_id.clone());
+
+                let mut incoming_mut = incoming.borrow_mut(py);
+                incoming_mut.trackid = new_id;
+                self.add_track(py, &incoming_mut)?;
+                incoming_mut.trackid = incoming_trackid;
+            }
+        }
+
+        let target = self
+            .root_playlist
+            .borrow_mut(py)
+            .new_folder(py, target_folder)?
+            .clone_ref(py);
+
+        let other_children = other.root_playlist.borrow(py).nodes.clone();
+        if let Some(children) = other_children {
+            for child in &children {
+                let remapped = remap_node_keys(py, &child.borrow(py), &trackid_remap)?;
+                let remapped = Py::new(py, remapped)?;
+                target.borrow_mut(py).add_node(py, remapped.borrow(py))?;
+            }
+        }
+
+        Ok(())
+    }
+}
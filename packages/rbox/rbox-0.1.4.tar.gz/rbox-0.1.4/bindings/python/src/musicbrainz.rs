@@ -0,0 +1,280 @@
+// Author: Dylan Jones
+// Date:   2025-06-22
+
+//! MusicBrainz metadata enrichment for tracks with incomplete tags.
+//!
+//! Fills empty optional fields on a `PyTrack` by querying the MusicBrainz
+//! web service, scoped by its existing `artist`/`name` (and `album` when
+//! present) or an explicit MBID. Never overwrites a field the user already
+//! set unless `overwrite=true` is passed. The HTTP/JSON layer sits behind
+//! the `MusicBrainzClient` trait so tests can inject canned responses.
+
+use super::errors::XmlError;
+use super::xml::{PyRekordboxXml, PyTrack};
+use pyo3::prelude::*;
+use serde::Deserialize;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+const USER_AGENT_DEFAULT: &str = "rbox/0.1 ( https://github.com/ )";
+const MIN_REQUEST_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Tracks the time of the last request so callers never exceed MusicBrainz's
+/// one-request-per-second rate limit, regardless of how many tracks are
+/// enriched in a batch.
+static LAST_REQUEST: Mutex<Option<Instant>> = Mutex::new(None);
+
+fn throttle() {
+    let mut last = LAST_REQUEST.lock().unwrap();
+    if let Some(previous) = *last {
+        let elapsed = previous.elapsed();
+        if elapsed < MIN_REQUEST_INTERVAL {
+            std::thread::sleep(MIN_REQUEST_INTERVAL - elapsed);
+        }
+    }
+    *last = Some(Instant::now());
+}
+
+#[derive(Debug, Deserialize)]
+struct RecordingSearchResponse {
+    #[serde(default)]
+    recordings: Vec<Recording>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Recording {
+    #[serde(default)]
+    id: String,
+    #[serde(default)]
+    score: i32,
+    #[serde(rename = "artist-credit", default)]
+    artist_credit: Vec<ArtistCredit>,
+    #[serde(rename = "releases", default)]
+    releases: Vec<Release>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ArtistCredit {
+    #[serde(default)]
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Release {
+    #[serde(default)]
+    date: Option<String>,
+    #[serde(default)]
+    title: Option<String>,
+    #[serde(rename = "release-group", default)]
+    release_group: Option<ReleaseGroup>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ReleaseGroup {
+    #[serde(default)]
+    label: Option<String>,
+    #[serde(default)]
+    genres: Vec<Genre>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Genre {
+    #[serde(default)]
+    name: String,
+}
+
+/// Abstracts the MusicBrainz HTTP/JSON transport so tests can substitute a
+/// fake client returning canned responses instead of hitting the network.
+pub trait MusicBrainzClient {
+    fn search_recording(&self, query: &str, user_agent: &str) -> PyResult<String>;
+    fn lookup_recording(&self, mbid: &str, user_agent: &str) -> PyResult<String>;
+}
+
+/// Default client backed by a blocking HTTP call to the real MusicBrainz API.
+pub struct UreqClient;
+
+impl MusicBrainzClient for UreqClient {
+    fn search_recording(&self, query: &str, user_agent: &str) -> PyResult<String> {
+        throttle();
+        ureq::get("https://musicbrainz.org/ws/2/recording")
+            .query("query", query)
+            .query("fmt", "json")
+            .query("limit", "1")
+            .set("User-Agent", user_agent)
+            .call()
+            .map_err(|e| XmlError::new_err(format!("MusicBrainz request failed: {e}")))?
+            .into_string()
+            .map_err(|e| XmlError::new_err(format!("Invalid MusicBrainz response: {e}")))
+    }
+
+    fn lookup_recording(&self, mbid: &str, user_agent: &str) -> PyResult<String> {
+        throttle();
+        ureq::get(&format!("https://musicbrainz.org/ws/2/recording/{mbid}"))
+            .query("fmt", "json")
+            .query("inc", "artist-credits+releases+release-groups+tags")
+            .set("User-Agent", user_agent)
+            .call()
+            .map_err(|e| XmlError::new_err(format!("MusicBrainz request failed: {e}")))?
+            .into_string()
+            .map_err(|e| XmlError::new_err(format!("Invalid MusicBrainz response: {e}")))
+    }
+}
+
+fn resolve_recording(
+    client: &dyn MusicBrainzClient,
+    artist: &str,
+    name: &str,
+    album: Option<&str>,
+    mbid_or_query: Option<&str>,
+    user_agent: &str,
+) -> PyResult<Option<Recording>> {
+    // An explicit MBID (36-char UUID) is a direct lookup; anything else is
+    // treated as a free-text query that augments the artist/name scoping.
+    if let Some(value) = mbid_or_query {
+        if value.len() == 36 && value.chars().filter(|&c| c == '-').count() == 4 {
+            let body = client.lookup_recording(value, user_agent)?;
+            let recording: Recording = serde_json::from_str(&body)
+                .map_err(|e| XmlError::new_err(format!("Invalid MusicBrainz response: {e}")))?;
+            return Ok(Some(recording));
+        }
+    }
+
+    let mut query = format!("recording:\"{name}\" AND artist:\"{artist}\"");
+    if let Some(album) = album {
+        query.push_str(&format!(" AND release:\"{album}\""));
+    }
+    if let Some(extra) = mbid_or_query {
+        query.push_str(&format!(" AND {extra}"));
+    }
+
+    let body = client.search_recording(&query, user_agent)?;
+    let response: RecordingSearchResponse = serde_json::from_str(&body)
+        .map_err(|e| XmlError::new_err(format!("Invalid MusicBrainz response: {e}")))?;
+    Ok(response.recordings.into_iter().max_by_key(|r| r.score))
+}
+
+fn apply_recording(track: &mut PyTrack, recording: &Recording, overwrite: bool) {
+    if overwrite || track.musicbrainz_id.is_none() {
+        if !recording.id.is_empty() {
+            track.musicbrainz_id = Some(recording.id.clone());
+        }
+    }
+    if (overwrite || track.composer.is_none()) && !recording.artist_credit.is_empty() {
+        track.composer = recording.artist_credit.first().map(|c| c.name.clone());
+    }
+    if let Some(release) = recording.releases.first() {
+        if overwrite || track.album.is_none() {
+            if let Some(title) = &release.title {
+                track.album = Some(title.clone());
+            }
+        }
+        if overwrite || track.year.is_none() {
+            track.year = release
+                .date
+                .as_ref()
+                .and_then(|d| d.get(0..4))
+                .and_then(|y| y.parse().ok());
+        }
+        if let Some(group) = &release.release_group {
+            if (overwrite || track.label.is_none()) && group.label.is_some() {
+                track.label = group.label.clone();
+            }
+            if (overwrite || track.genre.is_none()) && !group.genres.is_empty() {
+                track.genre = group.genres.first().map(|g| g.name.clone());
+            }
+        }
+    }
+}
+
+#[pymethods]
+impl PyTrack {
+    /// Fill empty `composer`/`album`/`label`/`year`/`genre`/`musicbrainz_id`
+    /// fields by searching MusicBrainz for a recording matching this track's
+    /// `artist`/`name` (and `album`, when set), or by a direct MBID lookup
+    /// when `mbid_or_query` is a MusicBrainz ID. Fields the user already
+    /// populated are left untouched unless `overwrite=true`. Returns whether
+    /// a match was found and applied.
+    #[pyo3(signature = (mbid_or_query=None, user_agent=None, overwrite=false))]
+    pub fn enrich_from_musicbrainz(
+        &mut self,
+        mbid_or_query: Option<&str>,
+        user_agent: Option<&str>,
+        overwrite: bool,
+    ) -> PyResult<bool> {
+        let artist = self
+            .artist
+            .as_deref()
+            .ok_or_else(|| XmlError::new_err("Track has no artist to search MusicBrainz with"))?;
+        let name = self
+            .name
+            .as_deref()
+            .ok_or_else(|| XmlError::new_err("Track has no name to search MusicBrainz with"))?;
+
+        let recording = resolve_recording(
+            &UreqClient,
+            artist,
+            name,
+            self.album.as_deref(),
+            mbid_or_query,
+            user_agent.unwrap_or(USER_AGENT_DEFAULT),
+        )?;
+
+        match recording {
+            Some(recording) => {
+                apply_recording(self, &recording, overwrite);
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+}
+
+#[pymethods]
+impl PyRekordboxXml {
+    /// Enrich a single track (looked up by `trackid`) from MusicBrainz; see
+    /// `PyTrack.enrich_from_musicbrainz` for the matching/overwrite rules.
+    #[pyo3(signature = (track_id, mbid_or_query=None, user_agent=None, overwrite=false))]
+    pub fn enrich_from_musicbrainz(
+        &mut self,
+        py: Python,
+        track_id: &str,
+        mbid_or_query: Option<&str>,
+        user_agent: Option<&str>,
+        overwrite: bool,
+    ) -> PyResult<bool> {
+        let track = self
+            .get_track_by_id(py, track_id)?
+            .ok_or_else(|| XmlError::new_err(format!("Track not found: '{track_id}'")))?
+            .clone_ref(py);
+        track
+            .borrow_mut(py)
+            .enrich_from_musicbrainz(mbid_or_query, user_agent, overwrite)
+    }
+
+    /// Run `PyTrack.enrich_from_musicbrainz` over every track in the
+    /// collection, skipping tracks with no `artist`/`name` to search by.
+    /// Returns the number of tracks that were updated.
+    #[pyo3(signature = (user_agent=None, overwrite=false))]
+    pub fn enrich_all_from_musicbrainz(
+        &mut self,
+        py: Python,
+        user_agent: Option<&str>,
+        overwrite: bool,
+    ) -> PyResult<usize> {
+        let mut updated = 0;
+        for i in 0.. {
+            let track = match self.get_track(i)? {
+                Some(track) => track.clone_ref(py),
+                None => break,
+            };
+            let mut track = track.borrow_mut(py);
+            if track.artist.is_none() || track.name.is_none() {
+                continue;
+            }
+            if track.enrich_from_musicbrainz(None, user_agent, overwrite)? {
+                updated += 1;
+            }
+        }
+        Ok(updated)
+    }
+}
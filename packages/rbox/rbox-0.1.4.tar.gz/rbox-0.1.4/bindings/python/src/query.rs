@@ -0,0 +1,269 @@
+// Author: Dylan Jones
+// Date:   2025-06-25
+
+//! Query/sort layer over `RekordboxXml` tracks: filtering by field equality
+//! or range, and multi-key sorting with a same-year month/day tiebreak.
+
+use super::errors::XmlError;
+use super::xml::{PyPlaylistNode, PyRekordboxXml, PyTrack};
+use chrono::Datelike;
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use std::cmp::Ordering;
+
+fn month_day(track: &PyTrack, py: Python, field: &str) -> Option<(u32, u32)> {
+    let dt = match field {
+        "dateadded" => &track.dateadded,
+        "datemodified" => &track.datemodified,
+        _ => return None,
+    };
+    let dt = dt.as_ref()?;
+    let dt = dt.extract::<chrono::DateTime<chrono::Utc>>(py).ok()?;
+    Some((dt.month(), dt.day()))
+}
+
+/// Resolve a sortable key for a field that may have a `*_sort` override
+/// (e.g. `name_sort` for `name`, so "The Beatles" files under "Beatles").
+fn sort_key<'a>(sort_field: &'a Option<String>, display_field: &'a Option<String>) -> &'a Option<String> {
+    if sort_field.is_some() {
+        sort_field
+    } else {
+        display_field
+    }
+}
+
+fn compare_by_key(a: &PyTrack, b: &PyTrack, py: Python, key: &str) -> PyResult<Ordering> {
+    let ordering = match key {
+        "trackid" => a.trackid.cmp(&b.trackid),
+        "name" => sort_key(&a.name_sort, &a.name).cmp(sort_key(&b.name_sort, &b.name)),
+        "artist" => sort_key(&a.artist_sort, &a.artist).cmp(sort_key(&b.artist_sort, &b.artist)),
+        "album" => sort_key(&a.album_sort, &a.album).cmp(sort_key(&b.album_sort, &b.album)),
+        "genre" => a.genre.cmp(&b.genre),
+        "year" => {
+            let ordering = a.year.cmp(&b.year);
+            if ordering != Ordering::Equal || a.year.is_none() {
+                ordering
+            } else {
+                // Same year: fall through to album, then tracknumber, so same-year
+                // releases order deterministically instead of by raw insertion order.
+                sort_key(&a.album_sort, &a.album)
+                    .cmp(sort_key(&b.album_sort, &b.album))
+                    .then_with(|| a.tracknumber.cmp(&b.tracknumber))
+                    .then_with(|| {
+                        month_day(a, py, "dateadded").cmp(&month_day(b, py, "dateadded"))
+                    })
+                    .then_with(|| {
+                        month_day(a, py, "datemodified").cmp(&month_day(b, py, "datemodified"))
+                    })
+            }
+        }
+        "averagebpm" => a
+            .averagebpm
+            .partial_cmp(&b.averagebpm)
+            .unwrap_or(Ordering::Equal),
+        "rating" => a.rating.cmp(&b.rating),
+        "totaltime" => a.totaltime.cmp(&b.totaltime),
+        "bitrate" => a.bitrate.cmp(&b.bitrate),
+        "tonality" => a.tonality.cmp(&b.tonality),
+        "location" => a.location.cmp(&b.location),
+        "dateadded" => {
+            let added = |t: &PyTrack| {
+                t.dateadded
+                    .as_ref()
+                    .and_then(|dt| dt.extract::<chrono::DateTime<chrono::Utc>>(py).ok())
+            };
+            added(a).cmp(&added(b))
+        }
+        _ => {
+            return Err(XmlError::new_err(format!(
+                "Unsupported sort key: '{key}'"
+            )))
+        }
+    };
+    Ok(ordering)
+}
+
+fn field_value<'py>(track: &PyTrack, py: Python<'py>, field: &str) -> PyResult<Bound<'py, PyAny>> {
+    use pyo3::IntoPyObject;
+    let value: PyObject = match field {
+        "trackid" => track.trackid.clone().into_pyobject(py)?.into_any().unbind(),
+        "name" => track.name.clone().into_pyobject(py)?.into_any().unbind(),
+        "artist" => track.artist.clone().into_pyobject(py)?.into_any().unbind(),
+        "composer" => track.composer.clone().into_pyobject(py)?.into_any().unbind(),
+        "album" => track.album.clone().into_pyobject(py)?.into_any().unbind(),
+        "grouping" => track.grouping.clone().into_pyobject(py)?.into_any().unbind(),
+        "genre" => track.genre.clone().into_pyobject(py)?.into_any().unbind(),
+        "kind" => track.kind.clone().into_pyobject(py)?.into_any().unbind(),
+        "size" => track.size.into_pyobject(py)?.into_any().unbind(),
+        "totaltime" => track.totaltime.into_pyobject(py)?.into_any().unbind(),
+        "discnumber" => track.discnumber.into_pyobject(py)?.into_any().unbind(),
+        "tracknumber" => track.tracknumber.into_pyobject(py)?.into_any().unbind(),
+        "year" => track.year.into_pyobject(py)?.into_any().unbind(),
+        "averagebpm" => track.averagebpm.into_pyobject(py)?.into_any().unbind(),
+        "bitrate" => track.bitrate.into_pyobject(py)?.into_any().unbind(),
+        "samplerate" => track.samplerate.into_pyobject(py)?.into_any().unbind(),
+        "comments" => track.comments.clone().into_pyobject(py)?.into_any().unbind(),
+        "playcount" => track.playcount.into_pyobject(py)?.into_any().unbind(),
+        "rating" => track.rating.into_pyobject(py)?.into_any().unbind(),
+        "location" => track.location.clone().into_pyobject(py)?.into_any().unbind(),
+        "remixer" => track.remixer.clone().into_pyobject(py)?.into_any().unbind(),
+        "tonality" => track.tonality.clone().into_pyobject(py)?.into_any().unbind(),
+        "label" => track.label.clone().into_pyobject(py)?.into_any().unbind(),
+        "mix" => track.mix.clone().into_pyobject(py)?.into_any().unbind(),
+        "color" => track.color.clone().into_pyobject(py)?.into_any().unbind(),
+        "musicbrainz_id" => track.musicbrainz_id.clone().into_pyobject(py)?.into_any().unbind(),
+        "name_sort" => track.name_sort.clone().into_pyobject(py)?.into_any().unbind(),
+        "artist_sort" => track.artist_sort.clone().into_pyobject(py)?.into_any().unbind(),
+        "album_sort" => track.album_sort.clone().into_pyobject(py)?.into_any().unbind(),
+        _ => return Err(XmlError::new_err(format!("Key '{field}' not found"))),
+    };
+    Ok(value.into_bound(py))
+}
+
+fn matches_predicate(track: &PyTrack, py: Python, field: &str, value: &Bound<PyAny>) -> PyResult<bool> {
+    let actual = field_value(track, py, field)?;
+    let actual = &actual;
+
+    if let Ok(dict) = value.downcast::<PyDict>() {
+        for (op, bound) in dict.iter() {
+            let op: String = op.extract()?;
+            let matched = match op.as_str() {
+                "eq" => actual.eq(&bound)?,
+                "ne" => !actual.eq(&bound)?,
+                "gt" => actual.compare(&bound)?.is_gt(),
+                "gte" => actual.compare(&bound)?.is_ge(),
+                "lt" => actual.compare(&bound)?.is_lt(),
+                "lte" => actual.compare(&bound)?.is_le(),
+                _ => return Err(XmlError::new_err(format!("Unsupported predicate operator: '{op}'"))),
+            };
+            if !matched {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    } else {
+        Ok(actual.eq(value)?)
+    }
+}
+
+#[pymethods]
+impl PyRekordboxXml {
+    /// Return tracks ordered by `key`, one of `trackid`, `name`, `artist`,
+    /// `album`, `genre`, `year`, `averagebpm`, `rating`, `totaltime`,
+    /// `bitrate`, `tonality`, `location` or `dateadded`; any other field name
+    /// is an error.
+    ///
+    /// `name`/`artist`/`album` use the corresponding `*_sort` field when one
+    /// is set (so "The Beatles" files under "Beatles"). Sorting by `year`
+    /// falls through to `album`, then `tracknumber`, then
+    /// `dateadded`/`datemodified` month-and-day, so releases from the same
+    /// year still order deterministically.
+    #[pyo3(signature = (key, reverse=false))]
+    pub fn sort_tracks(&mut self, py: Python, key: &str, reverse: bool) -> PyResult<Vec<Py<PyTrack>>> {
+        let mut tracks = Vec::new();
+        for i in 0.. {
+            match self.get_track(i)? {
+                Some(track) => tracks.push(track.clone_ref(py)),
+                None => break,
+            }
+        }
+
+        let mut error = None;
+        tracks.sort_by(|a, b| {
+            if error.is_some() {
+                return Ordering::Equal;
+            }
+            match compare_by_key(&a.borrow(py), &b.borrow(py), py, key) {
+                Ok(ordering) => ordering,
+                Err(e) => {
+                    error = Some(e);
+                    Ordering::Equal
+                }
+            }
+        });
+        if let Some(e) = error {
+            return Err(e);
+        }
+
+        if reverse {
+            tracks.reverse();
+        }
+        Ok(tracks)
+    }
+
+    /// Return tracks matching every `field: value` condition in `predicate`.
+    ///
+    /// A plain value requires equality; a dict value may instead give one or
+    /// more of `eq`/`ne`/`gt`/`gte`/`lt`/`lte` range conditions.
+    pub fn filter_tracks(&mut self, py: Python, predicate: &Bound<'_, PyDict>) -> PyResult<Vec<Py<PyTrack>>> {
+        let mut result = Vec::new();
+        for i in 0.. {
+            let track = match self.get_track(i)? {
+                Some(track) => track.clone_ref(py),
+                None => break,
+            };
+
+            let mut matches = true;
+            for (field, value) in predicate.iter() {
+                let field: String = field.extract()?;
+                if !matches_predicate(&track.borrow(py), py, &field, &value)? {
+                    matches = false;
+                    break;
+                }
+            }
+            if matches {
+                result.push(track);
+            }
+        }
+        Ok(result)
+    }
+}
+
+#[pymethods]
+impl PyPlaylistNode {
+    /// Reorder this node's own `tracks` entries (not its sub-nodes) by `key`,
+    /// resolving each `PyPlaylistTrack` key against `xml` to look up the
+    /// underlying `PyTrack`. See `PyRekordboxXml.sort_tracks` for the
+    /// supported keys and sort-field fallbacks.
+    #[pyo3(signature = (xml, key, reverse=false))]
+    pub fn sort_tracks(&mut self, py: Python, xml: &mut PyRekordboxXml, key: &str, reverse: bool) -> PyResult<()> {
+        let entries = self
+            .tracks
+            .as_ref()
+            .ok_or_else(|| XmlError::new_err("Node has no tracks"))?;
+
+        let key_type = self.key_type.unwrap_or(0) as i32;
+        let mut resolved = Vec::with_capacity(entries.len());
+        for entry in entries {
+            let entry_key = entry.borrow(py).key.clone();
+            let track = xml
+                .get_track_by_key(py, &entry_key, key_type)?
+                .ok_or_else(|| XmlError::new_err(format!("Track not found for key '{entry_key}'")))?
+                .clone_ref(py);
+            resolved.push((entry.clone_ref(py), track));
+        }
+
+        let mut error = None;
+        resolved.sort_by(|a, b| {
+            if error.is_some() {
+                return Ordering::Equal;
+            }
+            match compare_by_key(&a.1.borrow(py), &b.1.borrow(py), py, key) {
+                Ok(ordering) => ordering,
+                Err(e) => {
+                    error = Some(e);
+                    Ordering::Equal
+                }
+            }
+        });
+        if let Some(e) = error {
+            return Err(e);
+        }
+
+        if reverse {
+            resolved.reverse();
+        }
+        self.tracks = Some(resolved.into_iter().map(|(entry, _)| entry).collect());
+        Ok(())
+    }
+}
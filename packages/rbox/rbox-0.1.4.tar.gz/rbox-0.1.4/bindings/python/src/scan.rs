@@ -0,0 +1,147 @@
+// Author: Dylan Jones
+// Date:   2025-07-03
+
+//! Build a `RekordboxXml` by recursively scanning a directory of audio files
+//! and reading their embedded tags.
+
+use super::errors::XmlError;
+use super::xml::{PyRekordboxXml, PyTrack};
+use lofty::file::{AudioFile, TaggedFileExt};
+use lofty::probe::Probe;
+use lofty::tag::Accessor;
+use pyo3::prelude::*;
+use pyo3::types::PyType;
+use std::path::Path;
+
+const SUPPORTED_EXTENSIONS: &[&str] = &["mp3", "flac", "m4a", "aiff", "wav"];
+
+fn location_from_path(path: &Path) -> String {
+    let mut out = String::from("file://localhost/");
+    for byte in path.to_string_lossy().replace('\\', "/").bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'_' | b'-' | b'.' | b':' | b'/' => {
+                out.push(byte as char);
+            }
+            _ => out.push_str(&format!("%{byte:02X}")),
+        }
+    }
+    out
+}
+
+fn track_from_file(trackid: &str, path: &Path) -> PyResult<PyTrack> {
+    let location = location_from_path(path);
+    let metadata = std::fs::metadata(path).ok();
+
+    let tagged = Probe::open(path)
+        .and_then(|p| p.read())
+        .map_err(|e| XmlError::new_err(format!("Failed to read tags from {}: {e}", path.display())))?;
+
+    let properties = tagged.properties();
+    let tag = tagged.primary_tag().or_else(|| tagged.first_tag());
+
+    let track = PyTrack {
+        trackid: trackid.to_string(),
+        location,
+        name: tag.and_then(|t| t.title().map(|s| s.to_string())),
+        artist: tag.and_then(|t| t.artist().map(|s| s.to_string())),
+        composer: None,
+        album: tag.and_then(|t| t.album().map(|s| s.to_string())),
+        grouping: None,
+        genre: tag.and_then(|t| t.genre().map(|s| s.to_string())),
+        kind: path
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(|e| e.to_uppercase()),
+        size: metadata.map(|m| m.len() as i32),
+        totaltime: Some(properties.duration().as_secs() as i32),
+        discnumber: tag.and_then(|t| t.disk()).map(|d| d as i32),
+        tracknumber: tag.and_then(|t| t.track()).map(|t| t as i32),
+        year: tag.and_then(|t| t.year()).map(|y| y as i32),
+        averagebpm: None,
+        datemodified: None,
+        dateadded: None,
+        bitrate: properties.audio_bitrate().map(|b| b as i32),
+        samplerate: properties.sample_rate().map(f64::from),
+        comments: tag.and_then(|t| t.comment().map(|s| s.to_string())),
+        playcount: None,
+        lastplayed: None,
+        rating: None,
+        remixer: None,
+        tonality: None,
+        label: None,
+        mix: None,
+        color: None,
+        tempos: Vec::new(),
+        position_marks: Vec::new(),
+        musicbrainz_id: None,
+        name_sort: None,
+        artist_sort: None,
+        album_sort: None,
+    };
+    Ok(track)
+}
+
+fn walk_directory(dir: &Path, out: &mut Vec<std::path::PathBuf>) -> PyResult<()> {
+    let entries = std::fs::read_dir(dir).map_err(|e| XmlError::new_err(e.to_string()))?;
+    for entry in entries {
+        let entry = entry.map_err(|e| XmlError::new_err(e.to_string()))?;
+        let path = entry.path();
+        if path.is_dir() {
+            walk_directory(&path, out)?;
+        } else if path
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(|e| SUPPORTED_EXTENSIONS.contains(&e.to_lowercase().as_str()))
+            .unwrap_or(false)
+        {
+            out.push(path);
+        }
+    }
+    Ok(())
+}
+
+#[pymethods]
+impl PyRekordboxXml {
+    /// Build (or extend) a `RekordboxXml` by recursively scanning `dir` for
+    /// supported audio files (mp3/flac/m4a/aiff/wav) and populating a
+    /// `PyTrack` per file from its embedded tags.
+    ///
+    /// When `base_xml` is given, tracks are added to that existing file's
+    /// collection instead of a fresh, empty one. Files whose `file://`
+    /// location already matches an existing track are skipped.
+    #[classmethod]
+    #[pyo3(signature = (dir, base_xml=None))]
+    pub fn from_directory(
+        _cls: &Bound<'_, PyType>,
+        py: Python,
+        dir: &str,
+        base_xml: Option<&str>,
+    ) -> PyResult<Py<PyRekordboxXml>> {
+        let mut xml = match base_xml {
+            Some(path) => PyRekordboxXml::new(py, path)?,
+            None => {
+                let cls = py.get_type::<PyRekordboxXml>();
+                PyRekordboxXml::empty(&cls, py, "untitled.xml")?
+            }
+        };
+
+        let mut files = Vec::new();
+        walk_directory(Path::new(dir), &mut files)?;
+
+        let mut next_id = 0usize;
+        for path in files {
+            let location = location_from_path(&path);
+            if xml.get_track_by_location(py, &location)?.is_some() {
+                continue;
+            }
+            while xml.get_track_by_id(py, &next_id.to_string())?.is_some() {
+                next_id += 1;
+            }
+            let track = track_from_file(&next_id.to_string(), &path)?;
+            next_id += 1;
+            xml.add_track(py, &track)?;
+        }
+
+        Py::new(py, xml)
+    }
+}
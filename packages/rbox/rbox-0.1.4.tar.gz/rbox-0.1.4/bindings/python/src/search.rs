@@ -0,0 +1,98 @@
+// Author: Dylan Jones
+// Date:   2025-06-27
+
+//! Fuzzy track search across the library, ranked Skim-style by subsequence
+//! match quality rather than the exact `get_track_by_id`/`get_track_by_location`
+//! lookups.
+
+use super::xml::{PyRekordboxXml, PyTrack};
+use pyo3::prelude::*;
+
+const SCORE_MATCH: i64 = 16;
+const BONUS_CONSECUTIVE: i64 = 8;
+const BONUS_WORD_BOUNDARY: i64 = 12;
+
+fn searchable_text(track: &PyTrack) -> String {
+    let mut text = String::new();
+    if let Some(name) = &track.name {
+        text.push_str(name);
+    }
+    text.push(' ');
+    if let Some(artist) = &track.artist {
+        text.push_str(artist);
+    }
+    text.push(' ');
+    if let Some(album) = &track.album {
+        text.push_str(album);
+    }
+    text.to_lowercase()
+}
+
+/// Score `candidate` against `query` as an ordered subsequence match, or
+/// `None` if the query's characters don't all appear in order.
+fn fuzzy_score(query: &str, candidate: &str) -> Option<i64> {
+    if query.is_empty() {
+        return Some(0);
+    }
+
+    let candidate_chars: Vec<char> = candidate.chars().collect();
+    let mut query_chars = query.chars().peekable();
+    let mut score = 0_i64;
+    let mut prev_matched_index: Option<usize> = None;
+
+    for (i, &ch) in candidate_chars.iter().enumerate() {
+        let Some(&target) = query_chars.peek() else {
+            break;
+        };
+        if ch == target {
+            score += SCORE_MATCH;
+            if prev_matched_index == Some(i.wrapping_sub(1)) {
+                score += BONUS_CONSECUTIVE;
+            }
+            let at_word_boundary = i == 0
+                || candidate_chars
+                    .get(i - 1)
+                    .is_some_and(|c| c.is_whitespace() || "-_/.".contains(*c));
+            if at_word_boundary {
+                score += BONUS_WORD_BOUNDARY;
+            }
+            prev_matched_index = Some(i);
+            query_chars.next();
+        }
+    }
+
+    if query_chars.peek().is_some() {
+        None
+    } else {
+        Some(score)
+    }
+}
+
+#[pymethods]
+impl PyRekordboxXml {
+    /// Search tracks by a fuzzy, case-insensitive subsequence match against
+    /// each track's `name`/`artist`/`album`, ranked by match quality
+    /// (consecutive-match and word-boundary bonuses), best first.
+    #[pyo3(signature = (query, limit=None))]
+    pub fn search_tracks(&mut self, py: Python, query: &str, limit: Option<usize>) -> PyResult<Vec<Py<PyTrack>>> {
+        let query = query.to_lowercase();
+
+        let mut scored = Vec::new();
+        for i in 0.. {
+            let track = match self.get_track(i)? {
+                Some(track) => track.clone_ref(py),
+                None => break,
+            };
+            let text = searchable_text(&track.borrow(py));
+            if let Some(score) = fuzzy_score(&query, &text) {
+                scored.push((score, track));
+            }
+        }
+
+        scored.sort_by(|a, b| b.0.cmp(&a.0));
+        if let Some(limit) = limit {
+            scored.truncate(limit);
+        }
+        Ok(scored.into_iter().map(|(_, track)| track).collect())
+    }
+}
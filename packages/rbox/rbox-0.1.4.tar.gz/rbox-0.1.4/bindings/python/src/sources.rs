@@ -0,0 +1,49 @@
+// Author: Dylan Jones
+// Date:   2025-08-17
+
+//! External catalogue cross-references (MusicBrainz/Discogs/Beatport/...) for
+//! `DjmdContent` entries, backed by the `rbox_sources.json` sidecar file.
+
+use pyo3::prelude::*;
+use rbox::masterdb::{Provider, Source};
+
+#[pyclass]
+#[derive(Clone)]
+pub struct PySource {
+    #[pyo3(get)]
+    pub content_id: String,
+    #[pyo3(get)]
+    pub provider: String,
+    #[pyo3(get)]
+    pub url: String,
+}
+
+impl From<Source> for PySource {
+    fn from(source: Source) -> Self {
+        PySource {
+            content_id: source.content_id,
+            provider: provider_to_str(&source.provider),
+            url: source.url,
+        }
+    }
+}
+
+fn provider_to_str(provider: &Provider) -> String {
+    match provider {
+        Provider::MusicBrainz => "musicbrainz".to_string(),
+        Provider::Discogs => "discogs".to_string(),
+        Provider::Beatport => "beatport".to_string(),
+        Provider::Other(name) => name.clone(),
+    }
+}
+
+/// Maps a free-form provider name (`"musicbrainz"`, `"discogs"`, `"beatport"`,
+/// or anything else) to a [`Provider`], case-insensitively.
+pub(crate) fn provider_from_str(provider: &str) -> Provider {
+    match provider.to_lowercase().as_str() {
+        "musicbrainz" => Provider::MusicBrainz,
+        "discogs" => Provider::Discogs,
+        "beatport" => Provider::Beatport,
+        other => Provider::Other(other.to_string()),
+    }
+}
@@ -359,6 +359,10 @@ pub struct PyTrack {
     pub color: Option<String>,
     pub tempos: Vec<Py<PyTempo>>,
     pub position_marks: Vec<Py<PyPositionMark>>,
+    pub musicbrainz_id: Option<String>,
+    pub name_sort: Option<String>,
+    pub artist_sort: Option<String>,
+    pub album_sort: Option<String>,
 }
 
 impl PyTrack {
@@ -412,6 +416,10 @@ impl PyTrack {
         Self {
             tempos,
             position_marks,
+            musicbrainz_id: self.musicbrainz_id.clone(),
+            name_sort: self.name_sort.clone(),
+            artist_sort: self.artist_sort.clone(),
+            album_sort: self.album_sort.clone(),
             trackid: self.trackid.clone(),
             location: self.location.clone(),
             name: self.name.clone(),
@@ -475,6 +483,10 @@ impl PyTrack {
             "label".to_string(),
             "mix".to_string(),
             "color".to_string(),
+            "musicbrainz_id".to_string(),
+            "name_sort".to_string(),
+            "artist_sort".to_string(),
+            "album_sort".to_string(),
         ]
     }
 }
@@ -514,12 +526,16 @@ impl PyTrack {
             color: None,
             tempos: Vec::new(),
             position_marks: Vec::new(),
+            musicbrainz_id: None,
+            name_sort: None,
+            artist_sort: None,
+            album_sort: None,
         };
         Ok(track)
     }
 
     fn __len__(&self) -> usize {
-        28
+        32
     }
 
     fn __iter__(&self, py: Python) -> PyResult<Py<PyStrIter>> {
@@ -575,6 +591,10 @@ impl PyTrack {
             "label" => Ok(self.label.clone().into_pyobject(py)?.into()),
             "mix" => Ok(self.mix.clone().into_pyobject(py)?.into()),
             "color" => Ok(self.color.clone().into_pyobject(py)?.into()),
+            "musicbrainz_id" => Ok(self.musicbrainz_id.clone().into_pyobject(py)?.into()),
+            "name_sort" => Ok(self.name_sort.clone().into_pyobject(py)?.into()),
+            "artist_sort" => Ok(self.artist_sort.clone().into_pyobject(py)?.into()),
+            "album_sort" => Ok(self.album_sort.clone().into_pyobject(py)?.into()),
             _ => Err(PyErr::new::<pyo3::exceptions::PyKeyError, _>(format!(
                 "Key '{}' not found",
                 key
@@ -614,6 +634,10 @@ impl PyTrack {
             "label" => self.label = value.extract::<Option<String>>(py)?,
             "mix" => self.mix = value.extract::<Option<String>>(py)?,
             "color" => self.color = value.extract::<Option<String>>(py)?,
+            "musicbrainz_id" => self.musicbrainz_id = value.extract::<Option<String>>(py)?,
+            "name_sort" => self.name_sort = value.extract::<Option<String>>(py)?,
+            "artist_sort" => self.artist_sort = value.extract::<Option<String>>(py)?,
+            "album_sort" => self.album_sort = value.extract::<Option<String>>(py)?,
             _ => {
                 return Err(PyErr::new::<pyo3::exceptions::PyKeyError, _>(format!(
                     "Key '{}' not found",
@@ -755,6 +779,10 @@ impl IntoPy<PyTrack> for Track {
             color: self.color,
             tempos,
             position_marks,
+            musicbrainz_id: self.musicbrainz_id,
+            name_sort: self.name_sort,
+            artist_sort: self.artist_sort,
+            album_sort: self.album_sort,
         };
         Ok(model)
     }
@@ -821,6 +849,10 @@ impl FromPy<PyTrack> for Track {
             color: model.color.clone(),
             tempos,
             position_marks,
+            musicbrainz_id: model.musicbrainz_id.clone(),
+            name_sort: model.name_sort.clone(),
+            artist_sort: model.artist_sort.clone(),
+            album_sort: model.album_sort.clone(),
         }
     }
 }
@@ -1221,6 +1253,20 @@ impl PyRekordboxXml {
         Ok(PyRekordboxXml::new(py, path)?)
     }
 
+    /// Create a fresh, empty collection not backed by an existing XML file
+    /// on disk, to be populated and later `dump_copy`'d to a chosen path.
+    #[classmethod]
+    pub fn empty(_cls: &Bound<'_, PyType>, py: Python, path: &str) -> PyResult<Self> {
+        let xml = RekordboxXml::new(path);
+        let root_node = PlaylistNode::folder("ROOT");
+        let node_ref = root_node.into_py(py)?;
+        Ok(Self {
+            xml,
+            root_playlist: Py::new(py, node_ref)?,
+            tracks: Vec::new(),
+        })
+    }
+
     pub fn to_string(&mut self, py: Python) -> PyResult<String> {
         self.update_xml(py);
         let s = self.xml.to_string()?;
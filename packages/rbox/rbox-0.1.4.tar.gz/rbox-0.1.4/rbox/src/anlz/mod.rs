@@ -2,6 +2,7 @@
 // Date:   2025-05-15
 
 pub mod anlz;
+pub mod render;
 mod xor;
 
 pub use anlz::{find_anlz_files, Anlz, AnlzFiles, AnlzPaths, AnlzTag};
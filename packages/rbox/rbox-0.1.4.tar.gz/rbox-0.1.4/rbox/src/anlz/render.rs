@@ -0,0 +1,239 @@
+// Author: Dylan Jones
+// Date:   2025-08-15
+
+//! Rasterizes waveform columns parsed from `ANLZ` files into RGBA pixel buffers
+//! and, optionally, standalone PNGs.
+//!
+//! PNG encoding only needs a valid (but not necessarily *compressed*) DEFLATE
+//! stream, so this writes "stored" blocks rather than pulling in a
+//! compression crate for what's already a small, already-lossy thumbnail image.
+
+use super::anlz::{Waveform3BandColumn, WaveformColorDetailColumn};
+
+/// An RGB color, as passed in for the three bands of `render_waveform_3band_detail`.
+pub type Rgb = (u8, u8, u8);
+
+/// Rasterizes `WaveformColorDetail` columns (the `PWV5` format) into an RGBA buffer.
+///
+/// Each column packs a 3-bit red/green/blue base color and a 5-bit height. The
+/// base color is scaled up to a full byte per channel, the height is scaled to
+/// `0.0..=1.0` and used both as the bar's brightness and, drawn symmetrically
+/// about the vertical midpoint, its length (this format carries no separate
+/// whiteness channel, so the one height value does double duty for both, which
+/// matches how short/quiet columns in Rekordbox's own waveform render both
+/// short and dim).
+///
+/// `width`/`height` are the output image dimensions; columns are nearest-neighbor
+/// resampled to `width` if it doesn't match `columns.len()`.
+pub fn render_waveform_color_detail(
+    columns: &[WaveformColorDetailColumn],
+    width: u32,
+    height: u32,
+) -> Vec<u8> {
+    let mut buffer = vec![0u8; width as usize * height as usize * 4];
+    if columns.is_empty() || width == 0 || height == 0 {
+        return buffer;
+    }
+
+    let mid_y = height as f32 / 2.0;
+
+    for x in 0..width {
+        let source_index = ((x as usize * columns.len()) / width as usize).min(columns.len() - 1);
+        let column = &columns[source_index];
+
+        let red = scale_3bit(column.red());
+        let green = scale_3bit(column.green());
+        let blue = scale_3bit(column.blue());
+        let brightness = column.height() as f32 / 31.0;
+
+        let bar_half = brightness * mid_y;
+        let top = (mid_y - bar_half).max(0.0).round() as u32;
+        let bottom = (mid_y + bar_half).min(height as f32).round() as u32;
+
+        let pixel = [
+            (red as f32 * brightness) as u8,
+            (green as f32 * brightness) as u8,
+            (blue as f32 * brightness) as u8,
+            255,
+        ];
+        for y in top..bottom {
+            set_pixel(&mut buffer, width, x, y, pixel);
+        }
+    }
+
+    buffer
+}
+
+/// Rasterizes `Waveform3BandDetail` columns (the `PWV7` format) into an RGBA buffer.
+///
+/// Each column carries a full-range low/mid/high band magnitude; those are
+/// additively blended through the three caller-supplied colors (Rekordbox's
+/// own defaults are a dark blue low band, an amber mid band and a white high
+/// band) to get the bar's color, and the average magnitude across all three
+/// bands drives the bar's length, again drawn symmetrically about the
+/// vertical midpoint.
+pub fn render_waveform_3band_detail(
+    columns: &[Waveform3BandColumn],
+    width: u32,
+    height: u32,
+    low_color: Rgb,
+    mid_color: Rgb,
+    high_color: Rgb,
+) -> Vec<u8> {
+    let mut buffer = vec![0u8; width as usize * height as usize * 4];
+    if columns.is_empty() || width == 0 || height == 0 {
+        return buffer;
+    }
+
+    let mid_y = height as f32 / 2.0;
+
+    for x in 0..width {
+        let source_index = ((x as usize * columns.len()) / width as usize).min(columns.len() - 1);
+        let column = &columns[source_index];
+
+        let low = column.low() as f32 / 255.0;
+        let mid = column.mid() as f32 / 255.0;
+        let high = column.high() as f32 / 255.0;
+
+        let pixel = [
+            blend_channel(low, mid, high, low_color.0, mid_color.0, high_color.0),
+            blend_channel(low, mid, high, low_color.1, mid_color.1, high_color.1),
+            blend_channel(low, mid, high, low_color.2, mid_color.2, high_color.2),
+            255,
+        ];
+
+        let bar_half = ((low + mid + high) / 3.0).min(1.0) * mid_y;
+        let top = (mid_y - bar_half).max(0.0).round() as u32;
+        let bottom = (mid_y + bar_half).min(height as f32).round() as u32;
+        for y in top..bottom {
+            set_pixel(&mut buffer, width, x, y, pixel);
+        }
+    }
+
+    buffer
+}
+
+/// Writes an RGBA buffer produced by `render_waveform_color_detail` or
+/// `render_waveform_3band_detail` out as a standalone PNG file.
+pub fn write_png<P: AsRef<std::path::Path>>(
+    path: P,
+    width: u32,
+    height: u32,
+    rgba: &[u8],
+) -> anyhow::Result<()> {
+    std::fs::write(path, encode_png(width, height, rgba)?)?;
+    Ok(())
+}
+
+fn set_pixel(buffer: &mut [u8], width: u32, x: u32, y: u32, pixel: [u8; 4]) {
+    let offset = (y as usize * width as usize + x as usize) * 4;
+    buffer[offset..offset + 4].copy_from_slice(&pixel);
+}
+
+/// Scales a 3-bit color component (`0..=7`) up to a full byte.
+fn scale_3bit(value: u8) -> u8 {
+    ((value as u16 * 255) / 7) as u8
+}
+
+fn blend_channel(low: f32, mid: f32, high: f32, low_c: u8, mid_c: u8, high_c: u8) -> u8 {
+    let value = low * low_c as f32 + mid * mid_c as f32 + high * high_c as f32;
+    value.min(255.0) as u8
+}
+
+fn encode_png(width: u32, height: u32, rgba: &[u8]) -> anyhow::Result<Vec<u8>> {
+    anyhow::ensure!(
+        rgba.len() == width as usize * height as usize * 4,
+        "RGBA buffer length {} does not match a {}x{} image",
+        rgba.len(),
+        width,
+        height
+    );
+
+    let mut out = Vec::new();
+    out.extend_from_slice(&[0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A]);
+
+    let mut ihdr = Vec::with_capacity(13);
+    ihdr.extend_from_slice(&width.to_be_bytes());
+    ihdr.extend_from_slice(&height.to_be_bytes());
+    ihdr.push(8); // bit depth
+    ihdr.push(6); // color type: truecolor + alpha
+    ihdr.push(0); // compression method
+    ihdr.push(0); // filter method
+    ihdr.push(0); // interlace method
+    write_chunk(&mut out, b"IHDR", &ihdr);
+
+    let stride = width as usize * 4;
+    let mut raw = Vec::with_capacity((stride + 1) * height as usize);
+    for row in rgba.chunks_exact(stride) {
+        raw.push(0); // filter type: None
+        raw.extend_from_slice(row);
+    }
+    write_chunk(&mut out, b"IDAT", &zlib_store(&raw));
+
+    write_chunk(&mut out, b"IEND", &[]);
+
+    Ok(out)
+}
+
+fn write_chunk(out: &mut Vec<u8>, tag: &[u8; 4], data: &[u8]) {
+    out.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    let mut body = Vec::with_capacity(4 + data.len());
+    body.extend_from_slice(tag);
+    body.extend_from_slice(data);
+    out.extend_from_slice(&body);
+    out.extend_from_slice(&crc32(&body).to_be_bytes());
+}
+
+/// Wraps `data` in a minimal zlib stream made of uncompressed ("stored") DEFLATE blocks.
+fn zlib_store(data: &[u8]) -> Vec<u8> {
+    const MAX_BLOCK_LEN: usize = 0xFFFF;
+
+    let mut out = Vec::with_capacity(data.len() + data.len() / MAX_BLOCK_LEN * 5 + 11);
+    out.extend_from_slice(&[0x78, 0x01]); // CMF/FLG: 32K window, no dictionary, fastest level
+
+    let mut offset = 0;
+    loop {
+        let remaining = data.len() - offset;
+        let block_len = remaining.min(MAX_BLOCK_LEN);
+        let is_final = offset + block_len == data.len();
+
+        out.push(if is_final { 1 } else { 0 });
+        out.extend_from_slice(&(block_len as u16).to_le_bytes());
+        out.extend_from_slice(&(!(block_len as u16)).to_le_bytes());
+        out.extend_from_slice(&data[offset..offset + block_len]);
+
+        offset += block_len;
+        if is_final {
+            break;
+        }
+    }
+
+    out.extend_from_slice(&adler32(data).to_be_bytes());
+    out
+}
+
+fn adler32(data: &[u8]) -> u32 {
+    const MOD_ADLER: u32 = 65521;
+    let mut a: u32 = 1;
+    let mut b: u32 = 0;
+    for &byte in data {
+        a = (a + byte as u32) % MOD_ADLER;
+        b = (b + a) % MOD_ADLER;
+    }
+    (b << 16) | a
+}
+
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc: u32 = 0xFFFFFFFF;
+    for &byte in data {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            if crc & 1 == 1 {
+                crc = (crc >> 1) ^ 0xEDB88320;
+            } else {
+                crc >>= 1;
+            }
+        }
+    }
+    !crc
+}
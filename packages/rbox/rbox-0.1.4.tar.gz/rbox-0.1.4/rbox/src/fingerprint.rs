@@ -0,0 +1,241 @@
+// Author: Dylan Jones
+// Date:   2025-08-16
+
+//! Chromaprint-style acoustic fingerprinting, used to identify a track by its
+//! audio content rather than its file path or tags (dedup, or relinking a
+//! moved/renamed file back to its `DjmdContent` row).
+//!
+//! Decodes audio via `symphonia`, resamples to mono 11025 Hz, and reduces
+//! each ~4096-sample analysis window (50% overlap) to a 12-bin chroma
+//! (pitch-class energy) vector via direct Goertzel evaluation rather than a
+//! full FFT, since only 12 fixed target frequencies are needed per frame.
+//! Adjacent frames/bins are then compared with a small fixed filter set (the
+//! same "compare a cell's energy to its neighbors" trick Chromaprint itself
+//! uses) to quantize each frame down to a single 32-bit subfingerprint.
+
+use anyhow::{Context, Result};
+use std::path::Path;
+use symphonia::core::audio::{AudioBufferRef, Signal};
+use symphonia::core::codecs::{DecoderOptions, CODEC_TYPE_NULL};
+use symphonia::core::conv::IntoSample;
+use symphonia::core::formats::FormatOptions;
+use symphonia::core::io::MediaSourceStream;
+use symphonia::core::meta::MetadataOptions;
+use symphonia::core::probe::Hint;
+
+const TARGET_SAMPLE_RATE: u32 = 11025;
+const WINDOW_SIZE: usize = 4096;
+const HOP_SIZE: usize = WINDOW_SIZE / 2;
+const CHROMA_BINS: usize = 12;
+/// A4 = 440Hz; the 12 chroma bins span the octave around it.
+const REFERENCE_FREQ: f32 = 440.0;
+
+/// A computed acoustic fingerprint: one 32-bit subfingerprint per analysis frame.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fingerprint {
+    pub subfingerprints: Vec<u32>,
+}
+
+impl Fingerprint {
+    /// Decodes the audio file at `path`, resamples it to mono 11025 Hz, and
+    /// computes its fingerprint.
+    pub fn compute<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let samples = decode_mono_11025(path.as_ref())?;
+        let frames = chroma_frames(&samples);
+        Ok(Fingerprint {
+            subfingerprints: quantize(&frames),
+        })
+    }
+
+    /// Compares two fingerprints via sliding-window Hamming distance and
+    /// returns a match score in `0.0..=1.0` (1.0 = identical at the
+    /// best-aligned offset).
+    pub fn compare(&self, other: &Fingerprint) -> f32 {
+        // `quantize` only ever sets bits 0..CHROMA_BINS*2 (24 of the 32 bits); the top 8 bits are
+        // unused headroom and always agree, so they must be masked out of both the XOR and the
+        // normalizing denominator to avoid an artificial floor on the score.
+        const USED_BITS_MASK: u32 = (1 << (CHROMA_BINS * 2)) - 1;
+
+        let (a, b) = (&self.subfingerprints, &other.subfingerprints);
+        if a.is_empty() || b.is_empty() {
+            return 0.0;
+        }
+
+        let (shorter, longer) = if a.len() <= b.len() { (a, b) } else { (b, a) };
+        let max_offset = longer.len() - shorter.len();
+
+        let mut best_score = 0.0f32;
+        for offset in 0..=max_offset {
+            let matching_bits: u32 = shorter
+                .iter()
+                .zip(&longer[offset..offset + shorter.len()])
+                .map(|(x, y)| (CHROMA_BINS * 2) as u32 - ((x ^ y) & USED_BITS_MASK).count_ones())
+                .sum();
+            let score = matching_bits as f32 / (shorter.len() * CHROMA_BINS * 2) as f32;
+            best_score = best_score.max(score);
+        }
+        best_score
+    }
+}
+
+fn decode_mono_11025(path: &Path) -> Result<Vec<f32>> {
+    let file =
+        std::fs::File::open(path).with_context(|| format!("failed to open {}", path.display()))?;
+    let mss = MediaSourceStream::new(Box::new(file), Default::default());
+
+    let mut hint = Hint::new();
+    if let Some(ext) = path.extension().and_then(|e| e.to_str()) {
+        hint.with_extension(ext);
+    }
+
+    let probed = symphonia::default::get_probe().format(
+        &hint,
+        mss,
+        &FormatOptions::default(),
+        &MetadataOptions::default(),
+    )?;
+    let mut format = probed.format;
+
+    let track = format
+        .tracks()
+        .iter()
+        .find(|t| t.codec_params.codec != CODEC_TYPE_NULL)
+        .context("no decodable audio track found")?;
+    let track_id = track.id;
+    let source_rate = track.codec_params.sample_rate.unwrap_or(TARGET_SAMPLE_RATE);
+    let mut decoder =
+        symphonia::default::get_codecs().make(&track.codec_params, &DecoderOptions::default())?;
+
+    let mut mono = Vec::new();
+    while let Ok(packet) = format.next_packet() {
+        if packet.track_id() != track_id {
+            continue;
+        }
+        let decoded = decoder.decode(&packet)?;
+        append_mono_samples(&decoded, &mut mono);
+    }
+
+    Ok(resample_linear(&mono, source_rate, TARGET_SAMPLE_RATE))
+}
+
+/// Downmixes a decoded packet's channels to mono and appends it to `mono`.
+fn append_mono_samples(decoded: &AudioBufferRef, mono: &mut Vec<f32>) {
+    macro_rules! downmix {
+        ($buf:expr) => {{
+            let channels = $buf.spec().channels.count().max(1);
+            for frame in 0..$buf.frames() {
+                let sum: f32 = (0..channels)
+                    .map(|ch| IntoSample::<f32>::into_sample($buf.chan(ch)[frame]))
+                    .sum();
+                mono.push(sum / channels as f32);
+            }
+        }};
+    }
+
+    match decoded {
+        AudioBufferRef::U8(buf) => downmix!(buf),
+        AudioBufferRef::U16(buf) => downmix!(buf),
+        AudioBufferRef::U24(buf) => downmix!(buf),
+        AudioBufferRef::U32(buf) => downmix!(buf),
+        AudioBufferRef::S8(buf) => downmix!(buf),
+        AudioBufferRef::S16(buf) => downmix!(buf),
+        AudioBufferRef::S24(buf) => downmix!(buf),
+        AudioBufferRef::S32(buf) => downmix!(buf),
+        AudioBufferRef::F32(buf) => downmix!(buf),
+        AudioBufferRef::F64(buf) => downmix!(buf),
+    }
+}
+
+/// Naive linear-interpolation resampler; fingerprinting tolerates the quality
+/// loss and it keeps this module free of a dedicated resampling dependency.
+fn resample_linear(samples: &[f32], source_rate: u32, target_rate: u32) -> Vec<f32> {
+    if samples.is_empty() || source_rate == target_rate {
+        return samples.to_vec();
+    }
+    let ratio = source_rate as f64 / target_rate as f64;
+    let out_len = (samples.len() as f64 / ratio) as usize;
+
+    let mut out = Vec::with_capacity(out_len);
+    for i in 0..out_len {
+        let src_pos = i as f64 * ratio;
+        let index = src_pos as usize;
+        let frac = (src_pos - index as f64) as f32;
+        let a = samples[index];
+        let b = *samples.get(index + 1).unwrap_or(&a);
+        out.push(a + (b - a) * frac);
+    }
+    out
+}
+
+/// Slides a `WINDOW_SIZE` analysis window over `samples` with 50% overlap and
+/// reduces each window to a 12-bin chroma (pitch-class energy) vector.
+fn chroma_frames(samples: &[f32]) -> Vec<[f32; CHROMA_BINS]> {
+    if samples.len() < WINDOW_SIZE {
+        return Vec::new();
+    }
+
+    let bin_freqs = chroma_bin_frequencies();
+    let mut frames = Vec::new();
+    let mut start = 0;
+    while start + WINDOW_SIZE <= samples.len() {
+        let window = &samples[start..start + WINDOW_SIZE];
+        let mut chroma = [0.0f32; CHROMA_BINS];
+        for (bin, &freq) in bin_freqs.iter().enumerate() {
+            chroma[bin] = goertzel_power(window, freq, TARGET_SAMPLE_RATE as f32);
+        }
+        frames.push(chroma);
+        start += HOP_SIZE;
+    }
+    frames
+}
+
+/// The 12 pitch-class frequencies C4..B4, the octave containing
+/// `REFERENCE_FREQ` (A4 is bin 9).
+fn chroma_bin_frequencies() -> [f32; CHROMA_BINS] {
+    let mut freqs = [0.0f32; CHROMA_BINS];
+    for (i, freq) in freqs.iter_mut().enumerate() {
+        *freq = REFERENCE_FREQ * 2f32.powf((i as f32 - 9.0) / 12.0);
+    }
+    freqs
+}
+
+/// Single-bin energy via the Goertzel algorithm — cheaper than a full FFT
+/// when only a fixed, small set of target frequencies is needed per window.
+fn goertzel_power(samples: &[f32], target_freq: f32, sample_rate: f32) -> f32 {
+    let n = samples.len() as f32;
+    let k = (n * target_freq / sample_rate).round();
+    let omega = 2.0 * std::f32::consts::PI * k / n;
+    let coeff = 2.0 * omega.cos();
+
+    let (mut s_prev, mut s_prev2) = (0.0f32, 0.0f32);
+    for &sample in samples {
+        let s = sample + coeff * s_prev - s_prev2;
+        s_prev2 = s_prev;
+        s_prev = s;
+    }
+    s_prev2 * s_prev2 + s_prev * s_prev - coeff * s_prev * s_prev2
+}
+
+/// Quantizes each chroma frame to a 32-bit subfingerprint with a small fixed
+/// filter set: bit `bin` is set when that bin's energy rose since the
+/// previous frame, and bit `12 + bin` is set when it's louder than its
+/// neighboring bin this frame. The top 8 bits are unused headroom for
+/// additional filters, mirroring Chromaprint's own filter bank being smaller
+/// than the word it's packed into.
+fn quantize(frames: &[[f32; CHROMA_BINS]]) -> Vec<u32> {
+    let mut subfingerprints = Vec::with_capacity(frames.len().saturating_sub(1));
+    for t in 1..frames.len() {
+        let mut bits: u32 = 0;
+        for bin in 0..CHROMA_BINS {
+            if frames[t][bin] > frames[t - 1][bin] {
+                bits |= 1 << bin;
+            }
+            let next_bin = (bin + 1) % CHROMA_BINS;
+            if frames[t][bin] > frames[t][next_bin] {
+                bits |= 1 << (CHROMA_BINS + bin);
+            }
+        }
+        subfingerprints.push(bits);
+    }
+    subfingerprints
+}
@@ -94,15 +94,18 @@
 //!
 
 pub mod anlz;
+pub mod fingerprint;
 pub mod masterdb;
 mod options;
 mod pathlib;
 pub mod prelude;
 pub mod settings;
+pub mod sysex;
 pub mod util;
 pub mod xml;
 
 pub use anlz::{Anlz, AnlzTag};
+pub use fingerprint::Fingerprint;
 pub use masterdb::MasterDb;
 pub use options::RekordboxOptions;
 pub use pathlib::NormalizePath;
@@ -50,7 +50,8 @@ use super::enums::*;
 use super::models::*;
 use super::playlist_xml::MasterPlaylistXml;
 use super::random_id::RandomIdGenerator;
-use super::util::{format_datetime, sort_tree_list};
+use super::sources::{Provider, Source, SourceStore};
+use super::util::{format_datetime, resequence_tree_list};
 use super::{
     agentRegistry, cloudAgentRegistry, contentActiveCensor, contentCue, contentFile,
     djmdActiveCensor, djmdAlbum, djmdArtist, djmdCategory, djmdCloudProperty, djmdColor,
@@ -60,6 +61,7 @@ use super::{
     imageFile, schema, settingFile, uuidIDMap,
 };
 use crate::anlz::{find_anlz_files, Anlz, AnlzFiles, AnlzPaths};
+use crate::fingerprint::Fingerprint;
 use crate::options::RekordboxOptions;
 use crate::pathlib::NormalizePath;
 use crate::util::is_rekordbox_running;
@@ -107,6 +109,49 @@ fn open_connection(path: &str) -> Result<SqliteConnection> {
     Ok(conn)
 }
 
+/// Recursively collects audio file paths (by extension) under `roots`, for use
+/// as relink candidates in [`MasterDb::relink_missing_content`].
+fn find_audio_files(roots: &[PathBuf]) -> Vec<PathBuf> {
+    const AUDIO_EXTENSIONS: &[&str] = &["mp3", "wav", "flac", "aiff", "aif", "m4a", "aac", "ogg"];
+
+    let mut files = Vec::new();
+    let mut stack: Vec<PathBuf> = roots.to_vec();
+    while let Some(dir) = stack.pop() {
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.is_dir() {
+                stack.push(path);
+            } else if path
+                .extension()
+                .and_then(OsStr::to_str)
+                .map(|ext| AUDIO_EXTENSIONS.iter().any(|&e| e.eq_ignore_ascii_case(ext)))
+                .unwrap_or(false)
+            {
+                files.push(path);
+            }
+        }
+    }
+    files
+}
+
+/// Scales `vector` to unit length, for use as a prerequisite to cosine-similarity comparison in
+/// [`MasterDb::build_related_tracks`]. A zero vector is returned unchanged.
+fn l2_normalize(vector: &[f32]) -> Vec<f32> {
+    let norm = vector.iter().map(|v| v * v).sum::<f32>().sqrt();
+    if norm == 0.0 {
+        return vector.to_vec();
+    }
+    vector.iter().map(|v| v / norm).collect()
+}
+
+/// Dot product of two already L2-normalized vectors, i.e. their cosine similarity.
+fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
+    a.iter().zip(b.iter()).map(|(x, y)| x * y).sum()
+}
+
 pub struct MasterDb {
     /// Represents the SQLite database connection used for interacting with the database.
     pub conn: SqliteConnection,
@@ -116,6 +161,9 @@ pub struct MasterDb {
     /// Stores the path to the `masterPlaylist6.xml` file located in the same directory as the database.
     /// This is optional and may not be set if the file is not found.
     pub plxml_path: Option<PathBuf>,
+    /// Path to the `rbox_sources.json` sidecar file, located next to the database, that stores
+    /// external catalogue cross-references for `DjmdContent` entries (see [`super::sources`]).
+    pub sources_path: PathBuf,
     /// Indicates whether unsafe writes to the database are allowed while Rekordbox is running.
     /// - `true`: Unsafe writes are enabled, allowing modifications to the database.
     /// - `false`: Unsafe writes are disabled, preventing modifications to the database.
@@ -152,6 +200,7 @@ impl MasterDb {
             conn,
             share_dir: share_dir_str,
             plxml_path: pl_xml_path_str,
+            sources_path: parent_dir.join("rbox_sources.json"),
             unsafe_writes: false,
         })
     }
@@ -164,11 +213,17 @@ impl MasterDb {
         let share_dir = options.analysis_root.normalize();
         let plxml_path = options.get_db_dir()?.normalize();
         let conn = open_connection(options.db_path.to_str().unwrap())?;
+        let sources_path = options
+            .db_path
+            .parent()
+            .expect("Failed to get parent directory")
+            .join("rbox_sources.json");
 
         Ok(Self {
             conn,
             share_dir: Some(share_dir),
             plxml_path: Some(plxml_path),
+            sources_path,
             unsafe_writes: false,
         })
     }
@@ -1933,6 +1988,174 @@ impl MasterDb {
         Ok(result)
     }
 
+    /// Update the content folder path.
+    ///
+    /// Sets the [DjmdContent.FolderPath] field, e.g. after a file was moved
+    /// or relinked to a different location on disk.
+    pub fn update_content_folder_path(&mut self, content_id: &str, folder_path: &str) -> Result<usize> {
+        // Check if Rekordbox is running
+        if !self.unsafe_writes && is_rekordbox_running() {
+            return Err(anyhow::anyhow!(
+                "Rekordbox is running, unsafe writes are not allowed!"
+            ));
+        }
+        let result = diesel::update(djmdContent.filter(schema::djmdContent::ID.eq(content_id)))
+            .set(schema::djmdContent::FolderPath.eq(folder_path))
+            .execute(&mut self.conn)?;
+        Ok(result)
+    }
+
+    /// Groups [`DjmdContent`] entries whose audio fingerprints match within `threshold`.
+    ///
+    /// Computes a [`Fingerprint`] for every content entry whose `FolderPath` still
+    /// points at a file on disk, then clusters entries pairwise by
+    /// [`Fingerprint::compare`]. Entries whose file is missing, or that fails to
+    /// decode, are silently skipped rather than failing the whole scan.
+    ///
+    /// # Arguments
+    /// * `threshold` - Minimum match score (`0.0..=1.0`) for two tracks to be
+    ///   considered duplicates.
+    ///
+    /// # Returns
+    /// * `Result<Vec<Vec<String>>>` - Groups of two or more `DjmdContent` IDs:
+    ///   an entry plus every later, not yet grouped entry whose score against
+    ///   that first entry reaches `threshold`. Later members of a group are not
+    ///   compared with each other.
+    ///
+    /// # Errors
+    /// * Returns an error if the database query fails.
+    pub fn find_duplicate_content(&mut self, threshold: f32) -> Result<Vec<Vec<String>>> {
+        let contents = self.get_content()?;
+
+        let mut fingerprints: Vec<(String, Fingerprint)> = Vec::new();
+        for content in &contents {
+            let Some(folder_path) = &content.FolderPath else {
+                continue;
+            };
+            if let Ok(fp) = Fingerprint::compute(folder_path) {
+                fingerprints.push((content.ID.clone(), fp));
+            }
+        }
+
+        let mut visited = vec![false; fingerprints.len()];
+        let mut groups = Vec::new();
+        for i in 0..fingerprints.len() {
+            if visited[i] {
+                continue;
+            }
+            let mut group = vec![fingerprints[i].0.clone()];
+            for j in (i + 1)..fingerprints.len() {
+                if !visited[j] && fingerprints[i].1.compare(&fingerprints[j].1) >= threshold {
+                    visited[j] = true;
+                    group.push(fingerprints[j].0.clone());
+                }
+            }
+            if group.len() > 1 {
+                groups.push(group);
+            }
+        }
+
+        Ok(groups)
+    }
+
+    /// Re-points dead `FolderPath` entries to a candidate file by fingerprint.
+    ///
+    /// A file that has already gone missing can no longer be fingerprinted, so
+    /// callers must supply the fingerprints they captured while the files
+    /// still existed (e.g. from a prior [`MasterDb::find_duplicate_content`]
+    /// run), keyed by `DjmdContent` ID. Every audio file found under
+    /// `search_roots` is fingerprinted and matched against those entries whose
+    /// current `FolderPath` no longer exists on disk; the best match above
+    /// `0.95` similarity is written back via [`MasterDb::update_content_folder_path`].
+    ///
+    /// # Arguments
+    /// * `known_fingerprints` - Fingerprints computed before the content's file went missing.
+    /// * `search_roots` - Directories to scan for candidate replacement files.
+    ///
+    /// # Returns
+    /// * `Result<usize>` - The number of `DjmdContent` entries that were relinked.
+    ///
+    /// # Errors
+    /// * Returns an error if Rekordbox is running and unsafe writes are not allowed.
+    /// * Returns an error if the database query or update fails.
+    pub fn relink_missing_content(
+        &mut self,
+        known_fingerprints: &HashMap<String, Fingerprint>,
+        search_roots: &[PathBuf],
+    ) -> Result<usize> {
+        const RELINK_THRESHOLD: f32 = 0.95;
+
+        // Check if Rekordbox is running
+        if !self.unsafe_writes && is_rekordbox_running() {
+            return Err(anyhow::anyhow!(
+                "Rekordbox is running, unsafe writes are not allowed!"
+            ));
+        }
+
+        let contents = self.get_content()?;
+        let missing: Vec<(String, &Fingerprint)> = contents
+            .iter()
+            .filter_map(|content| {
+                let folder_path = content.FolderPath.as_ref()?;
+                if Path::new(folder_path).exists() {
+                    return None;
+                }
+                let fp = known_fingerprints.get(&content.ID)?;
+                Some((content.ID.clone(), fp))
+            })
+            .collect();
+        if missing.is_empty() {
+            return Ok(0);
+        }
+
+        let candidates = find_audio_files(search_roots);
+        let mut relinked = 0;
+        for (content_id, known_fp) in missing {
+            let mut best: Option<(f32, &PathBuf)> = None;
+            for candidate in &candidates {
+                let Ok(candidate_fp) = Fingerprint::compute(candidate) else {
+                    continue;
+                };
+                let score = known_fp.compare(&candidate_fp);
+                if score >= RELINK_THRESHOLD && best.map_or(true, |(best_score, _)| score > best_score) {
+                    best = Some((score, candidate));
+                }
+            }
+            if let Some((_, candidate)) = best {
+                let folder_path = candidate.to_string_lossy();
+                self.update_content_folder_path(&content_id, &folder_path)?;
+                relinked += 1;
+            }
+        }
+
+        Ok(relinked)
+    }
+
+    /// Returns all external catalogue [`Source`]s attached to a `DjmdContent` entry.
+    ///
+    /// Sources are read from the `rbox_sources.json` sidecar (see [`super::sources`]),
+    /// not from the Rekordbox database itself.
+    ///
+    /// # Errors
+    /// * Returns an error if the sidecar file exists but cannot be parsed.
+    pub fn content_sources(&self, content_id: &str) -> Result<Vec<Source>> {
+        let store = SourceStore::load(&self.sources_path)?;
+        Ok(store.get(content_id))
+    }
+
+    /// Attaches a new external catalogue reference to a `DjmdContent` entry.
+    ///
+    /// Stored in the `rbox_sources.json` sidecar (see [`super::sources`]) rather
+    /// than the Rekordbox database, so it survives a Rekordbox database rewrite.
+    ///
+    /// # Errors
+    /// * Returns an error if the sidecar file cannot be read or written.
+    pub fn add_content_source(&mut self, content_id: &str, provider: Provider, url: String) -> Result<()> {
+        let mut store = SourceStore::load(&self.sources_path)?;
+        store.add(content_id.to_string(), provider, url);
+        store.dump()
+    }
+
     // pub fn delete_content(&mut self, id: &str) -> Result<usize> {
     //     // Check if Rekordbox is running
     //     if !self.unsafe_writes && is_rekordbox_running() {
@@ -3380,13 +3603,20 @@ impl MasterDb {
         Ok(results)
     }
 
-    /// Returns a sorted tree of playlists as [`DjmdPlaylistTreeItem`] nodes.
+    /// Returns a sorted, densely-resequenced tree of playlists as
+    /// [`DjmdPlaylistTreeItem`] nodes.
+    ///
+    /// Each sibling group's `Seq` is rewritten to a contiguous `0..n`
+    /// ordering (see [`resequence_tree_list`]) so consumers that serialize
+    /// this tree don't have to reason about gaps left behind by prior
+    /// inserts, moves, or deletes.
     ///
     /// # Returns
     /// * `Result<Vec<Rc<RefCell<DjmdPlaylistTreeItem>>>>` - A vector of root nodes representing the playlist tree.
     ///
     /// # Errors
-    /// * Returns an error if the database query cannot be executed.
+    /// * Returns an error if the database query cannot be executed, or if the
+    ///   playlist tree contains a cycle.
     ///
     /// # Example
     /// ```no_run
@@ -3423,7 +3653,7 @@ impl MasterDb {
                 }
             }
         }
-        sort_tree_list(&mut roots);
+        resequence_tree_list(&mut roots)?;
 
         Ok(roots)
     }
@@ -4502,6 +4732,191 @@ impl MasterDb {
         Ok(result)
     }
 
+    /// Exports a playlist (or, for `format = "tree"`, a whole folder of
+    /// playlists) to a portable format.
+    ///
+    /// # Arguments
+    /// * `id` - The [`DjmdPlaylist`] ID to export.
+    /// * `format` - Either `"m3u8"` (export a single playlist as an extended
+    ///   M3U8 file at `path`) or `"tree"` (export `id` and, if it is a
+    ///   folder, every playlist nested under it, as a directory of `.m3u8`
+    ///   files mirroring the folder structure, rooted at `path`).
+    /// * `path` - Output file (`"m3u8"`) or directory (`"tree"`) path.
+    ///
+    /// # Returns
+    /// * `Result<usize>` - The number of `.m3u8` files written.
+    ///
+    /// # Errors
+    /// * Returns an error if `id` does not exist, `format` is unrecognized, or writing fails.
+    pub fn export_playlist(&mut self, id: &str, format: &str, path: &str) -> Result<usize> {
+        match format {
+            "m3u8" => {
+                self.write_playlist_m3u8(id, Path::new(path))?;
+                Ok(1)
+            }
+            "tree" => self.export_playlist_tree(id, Path::new(path)),
+            _ => Err(anyhow!("Unknown playlist export format: {}", format)),
+        }
+    }
+
+    /// Writes a single playlist out as an extended M3U8 file.
+    fn write_playlist_m3u8(&mut self, playlist_id: &str, out_path: &Path) -> Result<()> {
+        let songs = self.get_playlist_songs(playlist_id)?;
+
+        let mut out = String::from("#EXTM3U\n");
+        for song in &songs {
+            let Some(content_id) = &song.ContentID else {
+                continue;
+            };
+            let Some(content) = self.get_content_by_id(content_id)? else {
+                continue;
+            };
+            let Some(folder_path) = &content.FolderPath else {
+                continue;
+            };
+
+            let artist = match &content.ArtistID {
+                Some(artist_id) => self
+                    .get_artist_by_id(artist_id)?
+                    .and_then(|a| a.Name)
+                    .unwrap_or_default(),
+                None => String::new(),
+            };
+            let title = content.Title.clone().unwrap_or_default();
+            let duration = content.Length.unwrap_or(0);
+
+            out.push_str(&format!("#EXTINF:{duration},{artist} - {title}\n"));
+            out.push_str(folder_path);
+            out.push('\n');
+        }
+
+        if let Some(parent) = out_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(out_path, out)?;
+        Ok(())
+    }
+
+    /// Recursively dumps `playlist_id` (and, if it is a folder, its children)
+    /// into `dir` as a directory tree of `.m3u8` files.
+    fn export_playlist_tree(&mut self, playlist_id: &str, dir: &Path) -> Result<usize> {
+        let playlist = self
+            .get_playlist_by_id(playlist_id)?
+            .ok_or_else(|| anyhow!("Playlist with ID {} does not exist!", playlist_id))?;
+
+        if self.playlist_type(&playlist.ID)? != PlaylistType::Folder {
+            let file_path = dir.join(format!("{}.m3u8", playlist.Name.unwrap_or_default()));
+            self.write_playlist_m3u8(playlist_id, &file_path)?;
+            return Ok(1);
+        }
+
+        let sub_dir = dir.join(playlist.Name.unwrap_or_default());
+        std::fs::create_dir_all(&sub_dir)?;
+        let mut count = 0;
+        for child in self.get_playlist_children(playlist_id)? {
+            count += self.export_playlist_tree(&child.ID, &sub_dir)?;
+        }
+        Ok(count)
+    }
+
+    /// Imports a portable playlist export created by [`MasterDb::export_playlist`].
+    ///
+    /// `path` may point at a single `.m3u8` file (imported as one new
+    /// playlist) or a directory (imported as a new folder, recursively
+    /// mirroring its subdirectories and `.m3u8` files). Tracks are matched
+    /// back to [`DjmdContent`] by `FolderPath` first; if no entry has that
+    /// exact `FolderPath`, and the referenced file is itself still reachable on
+    /// disk, it is matched by audio fingerprint instead, so playlists survive
+    /// a reorganization of the library on disk.
+    ///
+    /// # Arguments
+    /// * `path` - The `.m3u8` file or directory to import.
+    /// * `parent_id` - Optional parent folder ID to import under (defaults to `"root"`).
+    ///
+    /// # Returns
+    /// * `Result<DjmdPlaylist>` - The newly created top-level playlist or folder.
+    ///
+    /// # Errors
+    /// * Returns an error if Rekordbox is running and unsafe writes are not allowed.
+    /// * Returns an error if `path` does not exist or the parent playlist is invalid.
+    pub fn import_playlist(&mut self, path: &str, parent_id: Option<String>) -> Result<DjmdPlaylist> {
+        let parent_id = parent_id.unwrap_or_else(|| "root".to_string());
+        let path = Path::new(path);
+
+        if path.is_dir() {
+            let name = path
+                .file_name()
+                .and_then(OsStr::to_str)
+                .unwrap_or("Imported")
+                .to_string();
+            let folder = self.create_playlist_folder(name, Some(parent_id), None)?;
+            for entry in std::fs::read_dir(path)?.flatten() {
+                let entry_path = entry.path();
+                if entry_path.is_dir() || entry_path.extension().and_then(OsStr::to_str) == Some("m3u8") {
+                    self.import_playlist(&entry_path.to_string_lossy(), Some(folder.ID.clone()))?;
+                }
+            }
+            Ok(folder)
+        } else {
+            self.import_playlist_file(path, &parent_id)
+        }
+    }
+
+    /// Imports a single `.m3u8` file as a new playlist under `parent_id`.
+    fn import_playlist_file(&mut self, path: &Path, parent_id: &str) -> Result<DjmdPlaylist> {
+        let contents = std::fs::read_to_string(path)?;
+        let name = path
+            .file_stem()
+            .and_then(OsStr::to_str)
+            .unwrap_or("Imported")
+            .to_string();
+        let playlist = self.create_playlist(name, Some(parent_id.to_string()), None, None, None)?;
+
+        for line in contents.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if let Some(content_id) = self.match_content_by_location(line)? {
+                self.insert_playlist_song(&playlist.ID, &content_id, None)?;
+            }
+        }
+
+        Ok(playlist)
+    }
+
+    /// Resolves a playlist-entry file path to a [`DjmdContent`] ID, first by
+    /// exact `FolderPath` match and, failing that, by audio fingerprint
+    /// comparison against the library (only attempted if `location` still
+    /// exists on disk).
+    fn match_content_by_location(&mut self, location: &str) -> Result<Option<String>> {
+        if let Some(content) = self.get_content_by_path(location)? {
+            return Ok(Some(content.ID));
+        }
+        if !Path::new(location).exists() {
+            return Ok(None);
+        }
+        let Ok(fp) = Fingerprint::compute(location) else {
+            return Ok(None);
+        };
+
+        const MATCH_THRESHOLD: f32 = 0.95;
+        let mut best: Option<(f32, String)> = None;
+        for content in self.get_content()? {
+            let Some(folder_path) = &content.FolderPath else {
+                continue;
+            };
+            let Ok(candidate_fp) = Fingerprint::compute(folder_path) else {
+                continue;
+            };
+            let score = fp.compare(&candidate_fp);
+            if score >= MATCH_THRESHOLD && best.as_ref().map_or(true, |(best_score, _)| score > *best_score) {
+                best = Some((score, content.ID));
+            }
+        }
+        Ok(best.map(|(_, id)| id))
+    }
+
     // -- Property ---------------------------------------------------------------------------------
 
     /// Retrieves all entries from the [`DjmdProperty`] table in the database.
@@ -4781,6 +5196,168 @@ impl MasterDb {
         Ok(result)
     }
 
+    /// Generates a unique related tracks ID that does not exist in the database.
+    fn generate_related_tracks_id(&mut self) -> Result<String> {
+        let generator = RandomIdGenerator::new(true);
+        let mut id: String = String::new();
+        for id_result in generator {
+            if let Ok(tmp_id) = id_result {
+                let id_exists: bool = select(exists(
+                    djmdRelatedTracks.filter(schema::djmdRelatedTracks::ID.eq(&tmp_id)),
+                ))
+                .get_result(&mut self.conn)?;
+                if !id_exists {
+                    id = tmp_id;
+                    break;
+                }
+            }
+        }
+        Ok(id)
+    }
+
+    /// Generates a unique related tracks song ID that does not exist in the database.
+    fn generate_related_tracks_song_id(&mut self) -> Result<String> {
+        let generator = RandomIdGenerator::new(true);
+        let mut id: String = String::new();
+        for id_result in generator {
+            if let Ok(tmp_id) = id_result {
+                let id_exists: bool = select(exists(
+                    schema::djmdSongRelatedTracks::table
+                        .filter(schema::djmdSongRelatedTracks::ID.eq(&tmp_id)),
+                ))
+                .get_result(&mut self.conn)?;
+                if !id_exists {
+                    id = tmp_id;
+                    break;
+                }
+            }
+        }
+        Ok(id)
+    }
+
+    /// Builds "similar tracks" related-tracks lists from precomputed per-track audio embeddings.
+    ///
+    /// For every track in `embeddings`, the embedding vectors are L2-normalized and compared via
+    /// cosine similarity. For each seed track, the top `k` neighbours scoring above `threshold`
+    /// become the members of a new [`DjmdRelatedTracks`] entry (and its ordered
+    /// [`DjmdSongRelatedTracks`] rows), optionally restricted to neighbours sharing the seed's
+    /// [`DjmdKey`] and falling within `bpm_tolerance` of its `BPM` field. Seeds with no qualifying
+    /// neighbours are skipped.
+    ///
+    /// # Arguments
+    /// * `embeddings` - Map of content ID to its audio embedding vector.
+    /// * `k` - Maximum number of neighbours to keep per seed track.
+    /// * `threshold` - Minimum cosine similarity (in `[-1.0, 1.0]`) for a neighbour to qualify.
+    /// * `bpm_tolerance` - Optional maximum `BPM` difference; when set, also requires a matching `KeyID`.
+    ///
+    /// # Returns
+    /// * `Result<Vec<DjmdRelatedTracks>>` - The newly created [`DjmdRelatedTracks`] entries, one per seed track that had at least one qualifying neighbour.
+    ///
+    /// # Errors
+    /// * Returns an error if Rekordbox is running and unsafe writes are not allowed.
+    /// * Returns an error if the database insertion fails.
+    pub fn build_related_tracks(
+        &mut self,
+        embeddings: &HashMap<String, Vec<f32>>,
+        k: usize,
+        threshold: f32,
+        bpm_tolerance: Option<i32>,
+    ) -> Result<Vec<DjmdRelatedTracks>> {
+        // Check if Rekordbox is running
+        if !self.unsafe_writes && is_rekordbox_running() {
+            return Err(anyhow::anyhow!(
+                "Rekordbox is running, unsafe writes are not allowed!"
+            ));
+        }
+
+        let normalized: HashMap<&str, Vec<f32>> = embeddings
+            .iter()
+            .map(|(id, vector)| (id.as_str(), l2_normalize(vector)))
+            .collect();
+
+        let ids: Vec<&str> = normalized.keys().copied().collect();
+        let contents = self.get_contents_by_ids(ids.clone())?;
+        let key_bpm: HashMap<&str, (Option<&String>, Option<i32>)> = contents
+            .iter()
+            .map(|c| (c.ID.as_str(), (c.KeyID.as_ref(), c.BPM)))
+            .collect();
+
+        let mut seed_ids = ids.clone();
+        seed_ids.sort_unstable();
+
+        let mut created = Vec::new();
+        for seed_id in seed_ids {
+            let seed_vector = &normalized[seed_id];
+            let (seed_key, seed_bpm) = key_bpm.get(seed_id).copied().unwrap_or((None, None));
+
+            let mut scored: Vec<(&str, f32)> = Vec::new();
+            for (candidate_id, candidate_vector) in normalized.iter() {
+                let candidate_id = *candidate_id;
+                if candidate_id == seed_id {
+                    continue;
+                }
+                if let Some(tolerance) = bpm_tolerance {
+                    let (key, bpm) = key_bpm.get(candidate_id).copied().unwrap_or((None, None));
+                    let same_key = key.is_some() && key == seed_key;
+                    let within_bpm = matches!(
+                        (seed_bpm, bpm),
+                        (Some(seed_bpm), Some(bpm)) if (seed_bpm - bpm).abs() <= tolerance
+                    );
+                    if !(same_key && within_bpm) {
+                        continue;
+                    }
+                }
+                let score = cosine_similarity(seed_vector, candidate_vector);
+                if score >= threshold {
+                    scored.push((candidate_id, score));
+                }
+            }
+            scored.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+            scored.truncate(k);
+
+            if scored.is_empty() {
+                continue;
+            }
+
+            let utcnow = Utc::now();
+            let usn = self.increment_local_usn(1)?;
+            let related_id = self.generate_related_tracks_id()?;
+            let parent = DjmdRelatedTracks::new(
+                related_id.clone(),
+                Uuid::new_v4().to_string(),
+                usn,
+                utcnow,
+                0,
+                format!("Related: {}", seed_id),
+                None,
+            )?;
+            let parent: DjmdRelatedTracks = diesel::insert_into(djmdRelatedTracks)
+                .values(parent)
+                .get_result(&mut self.conn)?;
+
+            for (track_no, (neighbour_id, _score)) in scored.into_iter().enumerate() {
+                let song_id = self.generate_related_tracks_song_id()?;
+                let song_usn = self.increment_local_usn(1)?;
+                let song = DjmdSongRelatedTracks::new(
+                    song_id,
+                    Uuid::new_v4().to_string(),
+                    song_usn,
+                    Utc::now(),
+                    related_id.clone(),
+                    neighbour_id.to_string(),
+                    track_no as i32 + 1,
+                )?;
+                diesel::insert_into(schema::djmdSongRelatedTracks::table)
+                    .values(song)
+                    .execute(&mut self.conn)?;
+            }
+
+            created.push(parent);
+        }
+
+        Ok(created)
+    }
+
     // -- Sampler ----------------------------------------------------------------------------------
 
     /// Retrieves all entries from the [`DjmdSampler`] table in the database.
@@ -8,6 +8,7 @@ pub mod playlist_xml;
 mod random_id;
 pub mod schema;
 pub mod smart_list;
+pub mod sources;
 mod util;
 
 // Core
@@ -15,6 +16,7 @@ pub use database::MasterDb;
 pub use enums::{AnalysisUpdated, Analyzed, FileType, PlaylistType};
 pub use playlist_xml::MasterPlaylistXml;
 pub use smart_list::{Condition, LogicalOperator, Operator, Property, SmartList};
+pub use sources::{Provider, Source};
 pub use util::{format_datetime, parse_datetime, RekordboxDateString};
 
 // Models
@@ -1880,6 +1880,36 @@ pub struct DjmdRelatedTracks {
     pub Criteria: Option<String>,
 }
 
+impl DjmdRelatedTracks {
+    pub fn new(
+        id: String,
+        uuid: String,
+        usn: i32,
+        now: Date,
+        seq: i32,
+        name: String,
+        criteria: Option<String>,
+    ) -> anyhow::Result<Self> {
+        Ok(Self {
+            ID: id.clone(),
+            UUID: uuid.clone(),
+            rb_data_status: 0,
+            rb_local_data_status: 0,
+            rb_local_deleted: 0,
+            rb_local_synced: 0,
+            usn: None,
+            rb_local_usn: Some(usn.clone()),
+            created_at: now.clone(),
+            updated_at: now.clone(),
+            Seq: Some(seq.clone()),
+            Name: Some(name.clone()),
+            Attribute: None,
+            ParentID: Some("root".to_string()),
+            Criteria: criteria.clone(),
+        })
+    }
+}
+
 #[derive(
     Queryable,
     Selectable,
@@ -1916,6 +1946,34 @@ pub struct DjmdSongRelatedTracks {
     pub TrackNo: Option<i32>,
 }
 
+impl DjmdSongRelatedTracks {
+    pub fn new(
+        id: String,
+        uuid: String,
+        usn: i32,
+        now: Date,
+        related_tracks_id: String,
+        content_id: String,
+        track_no: i32,
+    ) -> anyhow::Result<Self> {
+        Ok(Self {
+            ID: id.clone(),
+            UUID: uuid.clone(),
+            rb_data_status: 0,
+            rb_local_data_status: 0,
+            rb_local_deleted: 0,
+            rb_local_synced: 0,
+            usn: None,
+            rb_local_usn: Some(usn.clone()),
+            created_at: now.clone(),
+            updated_at: now.clone(),
+            RelatedTracksID: Some(related_tracks_id.clone()),
+            ContentID: Some(content_id.clone()),
+            TrackNo: Some(track_no.clone()),
+        })
+    }
+}
+
 #[derive(
     Queryable,
     Selectable,
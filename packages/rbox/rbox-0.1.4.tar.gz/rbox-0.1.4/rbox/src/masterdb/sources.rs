@@ -0,0 +1,84 @@
+// Author: Dylan Jones
+// Date:   2025-08-17
+
+//! External catalogue cross-references for `DjmdContent` entries.
+//!
+//! Rekordbox's own schema has no column for this, and this crate ships
+//! without migrations, so sources are kept in a small JSON sidecar file next
+//! to `master.db` — the same approach already used for `masterPlaylists6.xml`
+//! via [`super::playlist_xml::MasterPlaylistXml`]. The sidecar is loaded on
+//! every access and re-written on every change, so it survives a Rekordbox database rewrite
+//! untouched by Rekordbox itself.
+
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// The external catalogue a [`Source`] URL/ID belongs to.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Provider {
+    MusicBrainz,
+    Discogs,
+    Beatport,
+    Other(String),
+}
+
+/// A single external identifier attached to a `DjmdContent` entry.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Source {
+    pub content_id: String,
+    pub provider: Provider,
+    pub url: String,
+}
+
+/// The JSON sidecar file holding all [`Source`] entries for a library.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct SourceDocument {
+    #[serde(default)]
+    sources: Vec<Source>,
+}
+
+/// Handle to the `rbox_sources.json` sidecar file living next to `master.db`.
+pub struct SourceStore {
+    path: PathBuf,
+    doc: SourceDocument,
+}
+
+impl SourceStore {
+    /// Loads the sidecar at `path`, or starts an empty store if it doesn't exist yet.
+    pub fn load<P: AsRef<Path>>(path: P) -> anyhow::Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let doc = if path.exists() {
+            let contents = std::fs::read_to_string(&path)?;
+            serde_json::from_str(&contents)?
+        } else {
+            SourceDocument::default()
+        };
+        Ok(SourceStore { path, doc })
+    }
+
+    /// Writes the store back to its sidecar file.
+    pub fn dump(&self) -> anyhow::Result<()> {
+        let contents = serde_json::to_string_pretty(&self.doc)?;
+        std::fs::write(&self.path, contents)?;
+        Ok(())
+    }
+
+    /// Returns all sources attached to `content_id`.
+    pub fn get(&self, content_id: &str) -> Vec<Source> {
+        self.doc
+            .sources
+            .iter()
+            .filter(|s| s.content_id == content_id)
+            .cloned()
+            .collect()
+    }
+
+    /// Attaches a new source to `content_id`.
+    pub fn add(&mut self, content_id: String, provider: Provider, url: String) {
+        self.doc.sources.push(Source {
+            content_id,
+            provider,
+            url,
+        });
+    }
+}
@@ -4,6 +4,7 @@
 use super::models::DjmdPlaylistTreeItem;
 use chrono::{DateTime, TimeZone, Utc};
 use std::cell::RefCell;
+use std::collections::HashSet;
 use std::rc::Rc;
 
 const DATEFMT: &str = "%Y-%m-%d %H:%M:%S%.3f %:z";
@@ -36,7 +37,17 @@ impl RekordboxDateString for String {
     }
 }
 
-fn sort_tree(item: &mut Rc<RefCell<DjmdPlaylistTreeItem>>) {
+fn sort_tree(
+    item: &mut Rc<RefCell<DjmdPlaylistTreeItem>>,
+    visited: &mut HashSet<String>,
+) -> anyhow::Result<()> {
+    if !visited.insert(item.borrow().ID.clone()) {
+        return Err(anyhow::anyhow!(
+            "Cycle detected in playlist tree at node {}",
+            item.borrow().ID
+        ));
+    }
+
     // Sort the current node's children by a chosen criterion (e.g., ID or name)
     item.borrow_mut().Children.sort_by(|a, b| {
         let a_seq = a.borrow().Seq;
@@ -45,19 +56,126 @@ fn sort_tree(item: &mut Rc<RefCell<DjmdPlaylistTreeItem>>) {
     });
     // Recursively sort each child
     for child in &mut item.borrow_mut().Children {
-        sort_tree(child);
+        sort_tree(child, visited)?;
     }
+    Ok(())
 }
 
-pub fn sort_tree_list(tree: &mut Vec<Rc<RefCell<DjmdPlaylistTreeItem>>>) {
+pub fn sort_tree_list(tree: &mut Vec<Rc<RefCell<DjmdPlaylistTreeItem>>>) -> anyhow::Result<()> {
     // Sort the root nodes
     tree.sort_by(|a, b| {
         let a_seq = a.borrow().Seq;
         let b_seq = b.borrow().Seq;
         a_seq.cmp(&b_seq)
     });
-    // Sort each tree item recursively
+    // Sort each tree item recursively, guarding against cycles across the whole forest
+    let mut visited = HashSet::new();
     for item in tree {
-        sort_tree(item);
+        sort_tree(item, &mut visited)?;
+    }
+    Ok(())
+}
+
+fn resequence_tree(item: &Rc<RefCell<DjmdPlaylistTreeItem>>) {
+    for (i, child) in item.borrow().Children.iter().enumerate() {
+        child.borrow_mut().Seq = Some(i as i32);
+    }
+    for child in &item.borrow().Children {
+        resequence_tree(child);
+    }
+}
+
+/// Sorts the tree like [`sort_tree_list`], then rewrites every node's `Seq`
+/// to a dense, contiguous ordering within its sibling group, so that
+/// inserted, moved, or deleted playlists serialize back without gaps.
+pub fn resequence_tree_list(
+    tree: &mut Vec<Rc<RefCell<DjmdPlaylistTreeItem>>>,
+) -> anyhow::Result<()> {
+    sort_tree_list(tree)?;
+
+    for (i, root) in tree.iter().enumerate() {
+        root.borrow_mut().Seq = Some(i as i32);
+    }
+    for root in tree.iter() {
+        resequence_tree(root);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_item(id: &str, parent_id: Option<&str>, seq: i32) -> Rc<RefCell<DjmdPlaylistTreeItem>> {
+        let now = Utc.timestamp_opt(0, 0).unwrap();
+        Rc::new(RefCell::new(DjmdPlaylistTreeItem {
+            ID: id.to_string(),
+            UUID: id.to_string(),
+            rb_data_status: 0,
+            rb_local_data_status: 0,
+            rb_local_deleted: 0,
+            rb_local_synced: 0,
+            usn: None,
+            rb_local_usn: None,
+            created_at: now,
+            updated_at: now,
+            Seq: Some(seq),
+            Name: Some(id.to_string()),
+            ImagePath: None,
+            Attribute: None,
+            ParentID: parent_id.map(str::to_string),
+            SmartList: None,
+            Children: Vec::new(),
+        }))
+    }
+
+    fn link(parent: &Rc<RefCell<DjmdPlaylistTreeItem>>, child: Rc<RefCell<DjmdPlaylistTreeItem>>) {
+        parent.borrow_mut().Children.push(child);
+    }
+
+    #[test]
+    fn resequence_tree_list_fills_gaps_with_a_dense_ordering() {
+        let root_a = make_item("a", None, 10);
+        let root_b = make_item("b", None, 5);
+        let child_1 = make_item("a1", Some("a"), 20);
+        let child_2 = make_item("a2", Some("a"), 0);
+        link(&root_a, child_1.clone());
+        link(&root_a, child_2.clone());
+
+        let mut tree = vec![root_a.clone(), root_b.clone()];
+        resequence_tree_list(&mut tree).expect("resequence should succeed on an acyclic tree");
+
+        // Roots are sorted by their original Seq (b=5 before a=10), then
+        // rewritten to a dense 0..n ordering.
+        assert_eq!(tree[0].borrow().ID, "b");
+        assert_eq!(tree[0].borrow().Seq, Some(0));
+        assert_eq!(tree[1].borrow().ID, "a");
+        assert_eq!(tree[1].borrow().Seq, Some(1));
+
+        // Children of "a" are sorted by their original Seq (a2=0 before
+        // a1=20), then also rewritten densely.
+        let a_children = &tree[1].borrow().Children;
+        assert_eq!(a_children[0].borrow().ID, "a2");
+        assert_eq!(a_children[0].borrow().Seq, Some(0));
+        assert_eq!(a_children[1].borrow().ID, "a1");
+        assert_eq!(a_children[1].borrow().Seq, Some(1));
+    }
+
+    #[test]
+    fn resequence_tree_list_rejects_a_revisited_node() {
+        // `shared` appears under both `branch_1` and `branch_2`, so the
+        // second traversal re-inserts its ID into `visited` and must fail
+        // instead of silently resequencing it twice.
+        let shared = make_item("shared", None, 0);
+        let branch_1 = make_item("branch_1", None, 0);
+        let branch_2 = make_item("branch_2", None, 1);
+        link(&branch_1, shared.clone());
+        link(&branch_2, shared);
+
+        let mut tree = vec![branch_1, branch_2];
+        let err =
+            resequence_tree_list(&mut tree).expect_err("a revisited node must be rejected");
+        assert!(err.to_string().contains("Cycle detected"));
     }
 }
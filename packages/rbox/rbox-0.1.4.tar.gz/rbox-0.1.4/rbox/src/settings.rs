@@ -17,8 +17,10 @@
 use anyhow::anyhow;
 use binrw::{binrw, io::Cursor, BinRead, BinWrite, Endian, NullString};
 use parse_display::Display;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::ffi::OsStr;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 // -- MySetting ------------------------------------------------------------------------------------
 
@@ -1120,7 +1122,7 @@ impl MySetting2 {
 /// Found at "MIXER > DJ SETTING > CH FADER CURVE" of the "My Settings" page in the Rekordbox
 /// preferences.
 #[binrw]
-#[derive(Display, Debug, PartialEq, Eq, Default, Clone, Copy)]
+#[derive(Display, Debug, PartialEq, Eq, Default, Clone, Copy, Serialize, Deserialize)]
 #[brw(repr = u8)]
 pub enum ChannelFaderCurve {
     /// Steep volume raise when the fader is moved near the top.
@@ -1151,7 +1153,7 @@ impl TryFrom<String> for ChannelFaderCurve {
 /// Found at "MIXER > DJ SETTING > CROSSFADER CURVE" of the "My Settings" page in the Rekordbox
 /// preferences.
 #[binrw]
-#[derive(Display, Debug, PartialEq, Eq, Default, Clone, Copy)]
+#[derive(Display, Debug, PartialEq, Eq, Default, Clone, Copy, Serialize, Deserialize)]
 #[brw(repr = u8)]
 pub enum CrossfaderCurve {
     /// Logarithmic volume raise of the other channel near the edges of the fader.
@@ -1184,7 +1186,7 @@ impl TryFrom<String> for CrossfaderCurve {
 /// Found at "MIXER > DJ SETTING > HEADPHONES PRE EQ" of the "My Settings" page in the
 /// Rekordbox preferences.
 #[binrw]
-#[derive(Display, Debug, PartialEq, Eq, Default, Clone, Copy)]
+#[derive(Display, Debug, PartialEq, Eq, Default, Clone, Copy, Serialize, Deserialize)]
 #[brw(repr = u8)]
 pub enum HeadphonesPreEQ {
     /// Named "POST EQ" in the Rekordbox preferences.
@@ -1211,7 +1213,7 @@ impl TryFrom<String> for HeadphonesPreEQ {
 /// Found at "MIXER > DJ SETTING > HEADPHONES MONO SPLIT" of the "My Settings" page in the
 /// Rekordbox preferences.
 #[binrw]
-#[derive(Display, Debug, PartialEq, Eq, Default, Clone, Copy)]
+#[derive(Display, Debug, PartialEq, Eq, Default, Clone, Copy, Serialize, Deserialize)]
 #[brw(repr = u8)]
 pub enum HeadphonesMonoSplit {
     /// Named "MONO SPLIT" in the Rekordbox preferences.
@@ -1237,7 +1239,7 @@ impl TryFrom<String> for HeadphonesMonoSplit {
 /// Found at "MIXER > DJ SETTING > BEAT FX QUANTIZE" of the "My Settings" page in the
 /// Rekordbox preferences.
 #[binrw]
-#[derive(Display, Debug, PartialEq, Eq, Default, Clone, Copy)]
+#[derive(Display, Debug, PartialEq, Eq, Default, Clone, Copy, Serialize, Deserialize)]
 #[brw(repr = u8)]
 pub enum BeatFXQuantize {
     /// Named "OFF" in the Rekordbox preferences.
@@ -1262,7 +1264,7 @@ impl TryFrom<String> for BeatFXQuantize {
 /// Found at "MIXER > DJ SETTING > MIC LOW CUT" of the "My Settings" page in the
 /// Rekordbox preferences.
 #[binrw]
-#[derive(Display, Debug, PartialEq, Eq, Default, Clone, Copy)]
+#[derive(Display, Debug, PartialEq, Eq, Default, Clone, Copy, Serialize, Deserialize)]
 #[brw(repr = u8)]
 pub enum MicLowCut {
     /// Named "OFF" in the Rekordbox preferences.
@@ -1287,7 +1289,7 @@ impl TryFrom<String> for MicLowCut {
 /// Found at "MIXER > DJ SETTING > TALK OVER MODE" of the "My Settings" page in the Rekordbox
 /// preferences.
 #[binrw]
-#[derive(Display, Debug, PartialEq, Eq, Default, Clone, Copy)]
+#[derive(Display, Debug, PartialEq, Eq, Default, Clone, Copy, Serialize, Deserialize)]
 #[brw(repr = u8)]
 pub enum TalkOverMode {
     /// Named "ADVANCED" in the Rekordbox preferences.
@@ -1312,7 +1314,7 @@ impl TryFrom<String> for TalkOverMode {
 /// Found at "MIXER > DJ SETTING > TALK OVER LEVEL" of the "My Settings" page in the Rekordbox
 /// preferences.
 #[binrw]
-#[derive(Display, Debug, PartialEq, Eq, Default, Clone, Copy)]
+#[derive(Display, Debug, PartialEq, Eq, Default, Clone, Copy, Serialize, Deserialize)]
 #[brw(repr = u8)]
 pub enum TalkOverLevel {
     /// Named "-24dB" in the Rekordbox preferences.
@@ -1347,7 +1349,7 @@ impl TryFrom<String> for TalkOverLevel {
 /// Found at "MIXER > DJ SETTING > MIDI CH" of the "My Settings" page in the Rekordbox
 /// preferences.
 #[binrw]
-#[derive(Display, Debug, PartialEq, Eq, Default, Clone, Copy)]
+#[derive(Display, Debug, PartialEq, Eq, Default, Clone, Copy, Serialize, Deserialize)]
 #[brw(repr = u8)]
 pub enum MidiChannel {
     /// Named "1" in the Rekordbox preferences.
@@ -1430,7 +1432,7 @@ impl TryFrom<String> for MidiChannel {
 /// Found at "MIXER > DJ SETTING > MIDI BUTTON TYPE" of the "My Settings" page in the Rekordbox
 /// preferences.
 #[binrw]
-#[derive(Display, Debug, PartialEq, Eq, Default, Clone, Copy)]
+#[derive(Display, Debug, PartialEq, Eq, Default, Clone, Copy, Serialize, Deserialize)]
 #[brw(repr = u8)]
 pub enum MidiButtonType {
     #[default]
@@ -1455,7 +1457,7 @@ impl TryFrom<String> for MidiButtonType {
 /// Found at "MIXER > BRIGHTNESS > DISPLAY" of the "My Settings" page in the Rekordbox
 /// preferences.
 #[binrw]
-#[derive(Display, Debug, PartialEq, Eq, Default, Clone, Copy)]
+#[derive(Display, Debug, PartialEq, Eq, Default, Clone, Copy, Serialize, Deserialize)]
 #[brw(repr = u8)]
 pub enum MixerDisplayBrightness {
     /// Named "WHITE" in the Rekordbox preferences.
@@ -1497,7 +1499,7 @@ impl TryFrom<String> for MixerDisplayBrightness {
 /// Found at "MIXER > BRIGHTNESS > INDICATOR" of the "My Settings" page in the Rekordbox
 /// preferences.
 #[binrw]
-#[derive(Display, Debug, PartialEq, Eq, Default, Clone, Copy)]
+#[derive(Display, Debug, PartialEq, Eq, Default, Clone, Copy, Serialize, Deserialize)]
 #[brw(repr = u8)]
 pub enum MixerIndicatorBrightness {
     /// Named "1" in the Rekordbox preferences.
@@ -1528,7 +1530,7 @@ impl TryFrom<String> for MixerIndicatorBrightness {
 /// Found at "MIXER > DJ SETTING > CH FADER CURVE (LONG FADER)" of the "My Settings" page in the
 /// Rekordbox preferences.
 #[binrw]
-#[derive(Display, Debug, PartialEq, Eq, Default, Clone, Copy)]
+#[derive(Display, Debug, PartialEq, Eq, Default, Clone, Copy, Serialize, Deserialize)]
 #[brw(repr = u8)]
 pub enum ChannelFaderCurveLongFader {
     /// Very steep volume raise when the fader is moved the near the top (e.g. y = x⁵).
@@ -3763,3 +3765,810 @@ impl Setting {
         }
     }
 }
+
+// -- Text document serialization -------------------------------------------------------------
+
+/// Text document representation of a [`Setting`], for diffing, version control, and hand-editing.
+///
+/// Each field is keyed by its accessor name (e.g. `"auto_cue"`) and rendered as its Rust variant
+/// name (e.g. `"On"`) — the same string its `TryFrom<String>` impl accepts — so a document
+/// produced by [`Setting::to_document`] always round-trips through [`Setting::from_document`]
+/// without maintaining a second string form per enum.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SettingDocument {
+    /// Which `*SETTING.DAT` file kind this document was produced from.
+    pub kind: SettingKind,
+    /// Field name -> rendered value, e.g. `"auto_cue" -> "On"`.
+    pub fields: BTreeMap<String, String>,
+}
+
+/// Renders one field of a `Setting` into a [`SettingDocument`]'s field map via its `get_*` method
+/// and [`std::fmt::Debug`] (which matches the field's `TryFrom<String>` spelling).
+macro_rules! doc_get {
+    ($fields:expr, $setting:expr, $name:literal, $getter:ident) => {
+        $fields.insert($name.to_string(), format!("{:?}", $setting.$getter()?));
+    };
+}
+
+/// Parses one field out of a [`SettingDocument`]'s field map, if present, and applies it via its
+/// `set_*` method. Missing fields are left at the `Setting`'s current (default) value.
+macro_rules! doc_set {
+    ($fields:expr, $setting:expr, $name:literal, $ty:ty, $setter:ident) => {
+        if let Some(value) = $fields.get($name) {
+            let parsed = <$ty>::try_from(value.clone())
+                .map_err(|e| anyhow!("field {:?}: {}", $name, e))?;
+            $setting.$setter(parsed)?;
+        }
+    };
+}
+
+impl Setting {
+    /// The [`SettingKind`] of this `Setting`, i.e. which `*SETTING.DAT` file it was loaded from
+    /// or created as.
+    pub fn kind(&self) -> SettingKind {
+        match &self.data.content {
+            SettingContent::MySetting(_) => SettingKind::MySetting,
+            SettingContent::MySetting2(_) => SettingKind::MySetting2,
+            SettingContent::DJMMySetting(_) => SettingKind::DJMMySetting,
+            SettingContent::DevSetting(_) => SettingKind::DevSetting,
+        }
+    }
+
+    /// Renders every field valid for this `Setting`'s kind into a [`SettingDocument`].
+    pub fn to_document(&self) -> anyhow::Result<SettingDocument> {
+        let mut fields = BTreeMap::new();
+        match self.kind() {
+            SettingKind::MySetting => {
+                doc_get!(fields, self, "on_air_display", get_on_air_display);
+                doc_get!(fields, self, "lcd_brightness", get_lcd_brightness);
+                doc_get!(fields, self, "quantize", get_quantize);
+                doc_get!(fields, self, "auto_cue_level", get_auto_cue_level);
+                doc_get!(fields, self, "language", get_language);
+                doc_get!(fields, self, "jog_ring_brightness", get_jog_ring_brightness);
+                doc_get!(fields, self, "jog_ring_indicator", get_jog_ring_indicator);
+                doc_get!(fields, self, "slip_flashing", get_slip_flashing);
+                doc_get!(fields, self, "disc_slot_illumination", get_disc_slot_illumination);
+                doc_get!(fields, self, "eject_lock", get_eject_lock);
+                doc_get!(fields, self, "sync", get_sync);
+                doc_get!(fields, self, "play_mode", get_play_mode);
+                doc_get!(fields, self, "quantize_beat_value", get_quantize_beat_value);
+                doc_get!(fields, self, "hotcue_autoload", get_hotcue_autoload);
+                doc_get!(fields, self, "hotcue_color", get_hotcue_color);
+                doc_get!(fields, self, "needle_lock", get_needle_lock);
+                doc_get!(fields, self, "time_mode", get_time_mode);
+                doc_get!(fields, self, "jog_mode", get_jog_mode);
+                doc_get!(fields, self, "auto_cue", get_auto_cue);
+                doc_get!(fields, self, "master_tempo", get_master_tempo);
+                doc_get!(fields, self, "tempo_range", get_tempo_range);
+                doc_get!(fields, self, "phase_meter", get_phase_meter);
+            }
+            SettingKind::MySetting2 => {
+                doc_get!(fields, self, "vinyl_speed_adjust", get_vinyl_speed_adjust);
+                doc_get!(fields, self, "jog_display_mode", get_jog_display_mode);
+                doc_get!(fields, self, "pad_button_brightness", get_pad_button_brightness);
+                doc_get!(fields, self, "jog_lcd_brightness", get_jog_lcd_brightness);
+                doc_get!(fields, self, "waveform_divisions", get_waveform_divisions);
+                doc_get!(fields, self, "waveform", get_waveform);
+                doc_get!(fields, self, "beat_jump_beat_value", get_beat_jump_beat_value);
+            }
+            SettingKind::DJMMySetting => {
+                doc_get!(fields, self, "channel_fader_curve", get_channel_fader_curve);
+                doc_get!(fields, self, "crossfader_curve", get_crossfader_curve);
+                doc_get!(fields, self, "headphones_pre_eq", get_headphones_pre_eq);
+                doc_get!(fields, self, "headphones_mono_split", get_headphones_mono_split);
+                doc_get!(fields, self, "beat_fx_quantize", get_beat_fx_quantize);
+                doc_get!(fields, self, "mic_low_cut", get_mic_low_cut);
+                doc_get!(fields, self, "talk_over_mode", get_talk_over_mode);
+                doc_get!(fields, self, "talk_over_level", get_talk_over_level);
+                doc_get!(fields, self, "midi_channel", get_midi_channel);
+                doc_get!(fields, self, "midi_button_type", get_midi_button_type);
+                doc_get!(fields, self, "mixer_display_brightness", get_mixer_display_brightness);
+                doc_get!(fields, self, "mixer_indicator_brightness", get_mixer_indicator_brightness);
+                doc_get!(
+                    fields,
+                    self,
+                    "channel_fader_curve_long_fader",
+                    get_channel_fader_curve_long_fader
+                );
+            }
+            SettingKind::DevSetting => {
+                doc_get!(fields, self, "overview_waveform_type", get_overview_waveform_type);
+                doc_get!(fields, self, "waveform_color", get_waveform_color);
+                doc_get!(fields, self, "key_display_format", get_key_display_format);
+                doc_get!(
+                    fields,
+                    self,
+                    "waveform_current_position",
+                    get_waveform_current_position
+                );
+            }
+        }
+        Ok(SettingDocument {
+            kind: self.kind(),
+            fields,
+        })
+    }
+
+    /// Reconstructs a `Setting` from a [`SettingDocument`], starting from `doc.kind`'s default
+    /// byte template and applying every field present in the document. Fields absent from the
+    /// document keep their default value; unknown field values return an error.
+    pub fn from_document(doc: &SettingDocument) -> anyhow::Result<Setting> {
+        let mut setting = Setting::new(doc.kind);
+        let fields = &doc.fields;
+        match doc.kind {
+            SettingKind::MySetting => {
+                doc_set!(fields, setting, "on_air_display", OnAirDisplay, set_on_air_display);
+                doc_set!(fields, setting, "lcd_brightness", LCDBrightness, set_lcd_brightness);
+                doc_set!(fields, setting, "quantize", Quantize, set_quantize);
+                doc_set!(fields, setting, "auto_cue_level", AutoCueLevel, set_auto_cue_level);
+                doc_set!(fields, setting, "language", Language, set_language);
+                doc_set!(fields, setting, "jog_ring_brightness", JogRingBrightness, set_jog_ring_brightness);
+                doc_set!(fields, setting, "jog_ring_indicator", JogRingIndicator, set_jog_ring_indicator);
+                doc_set!(fields, setting, "slip_flashing", SlipFlashing, set_slip_flashing);
+                doc_set!(
+                    fields,
+                    setting,
+                    "disc_slot_illumination",
+                    DiscSlotIllumination,
+                    set_disc_slot_illumination
+                );
+                doc_set!(fields, setting, "eject_lock", EjectLock, set_eject_lock);
+                doc_set!(fields, setting, "sync", Sync, set_sync);
+                doc_set!(fields, setting, "play_mode", PlayMode, set_play_mode);
+                doc_set!(
+                    fields,
+                    setting,
+                    "quantize_beat_value",
+                    QuantizeBeatValue,
+                    set_quantize_beat_value
+                );
+                doc_set!(fields, setting, "hotcue_autoload", HotCueAutoLoad, set_hotcue_autoload);
+                doc_set!(fields, setting, "hotcue_color", HotCueColor, set_hotcue_color);
+                doc_set!(fields, setting, "needle_lock", NeedleLock, set_needle_lock);
+                doc_set!(fields, setting, "time_mode", TimeMode, set_time_mode);
+                doc_set!(fields, setting, "jog_mode", JogMode, set_jog_mode);
+                doc_set!(fields, setting, "auto_cue", AutoCue, set_auto_cue);
+                doc_set!(fields, setting, "master_tempo", MasterTempo, set_master_tempo);
+                doc_set!(fields, setting, "tempo_range", TempoRange, set_tempo_range);
+                doc_set!(fields, setting, "phase_meter", PhaseMeter, set_phase_meter);
+            }
+            SettingKind::MySetting2 => {
+                doc_set!(fields, setting, "vinyl_speed_adjust", VinylSpeedAdjust, set_vinyl_speed_adjust);
+                doc_set!(fields, setting, "jog_display_mode", JogDisplayMode, set_jog_display_mode);
+                doc_set!(
+                    fields,
+                    setting,
+                    "pad_button_brightness",
+                    PadButtonBrightness,
+                    set_pad_button_brightness
+                );
+                doc_set!(fields, setting, "jog_lcd_brightness", JogLCDBrightness, set_jog_lcd_brightness);
+                doc_set!(
+                    fields,
+                    setting,
+                    "waveform_divisions",
+                    WaveformDivisions,
+                    set_waveform_divisions
+                );
+                doc_set!(fields, setting, "waveform", Waveform, set_waveform);
+                doc_set!(
+                    fields,
+                    setting,
+                    "beat_jump_beat_value",
+                    BeatJumpBeatValue,
+                    set_beat_jump_beat_value
+                );
+            }
+            SettingKind::DJMMySetting => {
+                doc_set!(
+                    fields,
+                    setting,
+                    "channel_fader_curve",
+                    ChannelFaderCurve,
+                    set_channel_fader_curve
+                );
+                doc_set!(fields, setting, "crossfader_curve", CrossfaderCurve, set_crossfader_curve);
+                doc_set!(fields, setting, "headphones_pre_eq", HeadphonesPreEQ, set_headphones_pre_eq);
+                doc_set!(
+                    fields,
+                    setting,
+                    "headphones_mono_split",
+                    HeadphonesMonoSplit,
+                    set_headphones_mono_split
+                );
+                doc_set!(fields, setting, "beat_fx_quantize", BeatFXQuantize, set_beat_fx_quantize);
+                doc_set!(fields, setting, "mic_low_cut", MicLowCut, set_mic_low_cut);
+                doc_set!(fields, setting, "talk_over_mode", TalkOverMode, set_talk_over_mode);
+                doc_set!(fields, setting, "talk_over_level", TalkOverLevel, set_talk_over_level);
+                doc_set!(fields, setting, "midi_channel", MidiChannel, set_midi_channel);
+                doc_set!(fields, setting, "midi_button_type", MidiButtonType, set_midi_button_type);
+                doc_set!(
+                    fields,
+                    setting,
+                    "mixer_display_brightness",
+                    MixerDisplayBrightness,
+                    set_mixer_display_brightness
+                );
+                doc_set!(
+                    fields,
+                    setting,
+                    "mixer_indicator_brightness",
+                    MixerIndicatorBrightness,
+                    set_mixer_indicator_brightness
+                );
+                doc_set!(
+                    fields,
+                    setting,
+                    "channel_fader_curve_long_fader",
+                    ChannelFaderCurveLongFader,
+                    set_channel_fader_curve_long_fader
+                );
+            }
+            SettingKind::DevSetting => {
+                doc_set!(
+                    fields,
+                    setting,
+                    "overview_waveform_type",
+                    OverviewWaveformType,
+                    set_overview_waveform_type
+                );
+                doc_set!(fields, setting, "waveform_color", WaveformColor, set_waveform_color);
+                doc_set!(
+                    fields,
+                    setting,
+                    "key_display_format",
+                    KeyDisplayFormat,
+                    set_key_display_format
+                );
+                doc_set!(
+                    fields,
+                    setting,
+                    "waveform_current_position",
+                    WaveformCurrentPosition,
+                    set_waveform_current_position
+                );
+            }
+        }
+        Ok(setting)
+    }
+
+    /// Serializes this `Setting` to a pretty-printed JSON document (see [`Setting::to_document`]).
+    /// Reloading it with [`Setting::from_json`] restores every named field; the remaining bytes
+    /// come from the kind's default template (see [`Setting::from_document`]), so
+    /// [`Setting::dump_copy`] reproduces the original `.DAT` byte for byte only when its other
+    /// bytes match that template.
+    pub fn to_json(&self) -> anyhow::Result<String> {
+        Ok(serde_json::to_string_pretty(&self.to_document()?)?)
+    }
+
+    /// Parses a `Setting` from JSON produced by [`Setting::to_json`].
+    pub fn from_json(json: &str) -> anyhow::Result<Setting> {
+        let doc: SettingDocument = serde_json::from_str(json)?;
+        Setting::from_document(&doc)
+    }
+
+    /// Serializes this `Setting` to a TOML document (see [`Setting::to_document`]). Reloading it
+    /// with [`Setting::from_toml`] restores every named field; the remaining bytes come from the
+    /// kind's default template, so [`Setting::dump_copy`] reproduces the original `.DAT` byte for
+    /// byte only when its other bytes match that template.
+    pub fn to_toml(&self) -> anyhow::Result<String> {
+        Ok(toml::to_string_pretty(&self.to_document()?)?)
+    }
+
+    /// Parses a `Setting` from TOML produced by [`Setting::to_toml`].
+    pub fn from_toml(toml_str: &str) -> anyhow::Result<Setting> {
+        let doc: SettingDocument = toml::from_str(toml_str)?;
+        Setting::from_document(&doc)
+    }
+}
+
+// -- Setting builder -------------------------------------------------------------------------
+
+/// Selects which `*SETTING.DAT` file kind a [`Setting`] is created as by [`Setting::new`] /
+/// [`Setting::builder`].
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+pub enum SettingKind {
+    /// A `MYSETTING.DAT` file.
+    MySetting,
+    /// A `MYSETTING2.DAT` file.
+    MySetting2,
+    /// A `DJMMYSETTING.DAT` file.
+    DJMMySetting,
+    /// A `DEVSETTING.DAT` file.
+    DevSetting,
+}
+
+impl Setting {
+    /// Creates a new `Setting` of the given `kind`, pre-filled with its default byte template.
+    ///
+    /// Unlike [`Setting::load`], this does not require an existing file on disk. The returned
+    /// `Setting` writes to `<kind's file name>` in the current directory until [`Setting::dump_copy`]
+    /// is used to pick a different destination.
+    #[must_use]
+    pub fn new(kind: SettingKind) -> Self {
+        let data = match kind {
+            SettingKind::MySetting => SettingData::default_mysetting(),
+            SettingKind::MySetting2 => SettingData::default_mysetting2(),
+            SettingKind::DJMMySetting => SettingData::default_djmmysetting(),
+            SettingKind::DevSetting => SettingData::default_devsetting(),
+        };
+        let path = PathBuf::from(data.file_name());
+        Self { path, data }
+    }
+
+    /// Starts a fluent [`SettingBuilder`] for a new `Setting` of the given `kind`.
+    #[must_use]
+    pub fn builder(kind: SettingKind) -> SettingBuilder {
+        SettingBuilder::new(kind)
+    }
+}
+
+/// Fluent builder for a [`Setting`], started via [`Setting::builder`].
+///
+/// Each typed method sets one field and delegates to the matching `Setting::set_*` method, so it
+/// is only valid for the [`SettingKind`] that field belongs to; calling one for a mismatched kind
+/// panics, since that is a programming error rather than something user-provided data can trigger.
+///
+/// # Example
+/// ```no_run
+/// use rbox::settings::{AutoCue, JogMode, Setting, SettingKind, Waveform};
+///
+/// let setting = Setting::builder(SettingKind::MySetting)
+///     .auto_cue(AutoCue::On)
+///     .jog_mode(JogMode::Vinyl)
+///     .build();
+/// ```
+pub struct SettingBuilder {
+    setting: Setting,
+}
+
+/// Generates a typed, panic-on-mismatch fluent method on [`SettingBuilder`] that forwards to a
+/// `Setting::set_*` method.
+macro_rules! builder_field {
+    ($name:ident, $ty:ty, $setter:ident) => {
+        /// Forwards to the matching `Setting::set_*` method. Panics if this `SettingBuilder`'s
+        /// kind doesn't have this field.
+        #[must_use]
+        pub fn $name(mut self, value: $ty) -> Self {
+            self.setting.$setter(value).expect(concat!(
+                stringify!($name),
+                " is not valid for this SettingBuilder's SettingKind"
+            ));
+            self
+        }
+    };
+}
+
+impl SettingBuilder {
+    /// Starts building a `Setting` of the given `kind`, pre-filled with its default byte template.
+    #[must_use]
+    pub fn new(kind: SettingKind) -> Self {
+        Self {
+            setting: Setting::new(kind),
+        }
+    }
+
+    /// Finishes the builder, producing the resulting [`Setting`].
+    #[must_use]
+    pub fn build(self) -> Setting {
+        self.setting
+    }
+
+    // -- MySetting fields ---------------------------------------------------------------------
+    builder_field!(on_air_display, OnAirDisplay, set_on_air_display);
+    builder_field!(lcd_brightness, LCDBrightness, set_lcd_brightness);
+    builder_field!(quantize, Quantize, set_quantize);
+    builder_field!(auto_cue_level, AutoCueLevel, set_auto_cue_level);
+    builder_field!(language, Language, set_language);
+    builder_field!(jog_ring_brightness, JogRingBrightness, set_jog_ring_brightness);
+    builder_field!(jog_ring_indicator, JogRingIndicator, set_jog_ring_indicator);
+    builder_field!(slip_flashing, SlipFlashing, set_slip_flashing);
+    builder_field!(disc_slot_illumination, DiscSlotIllumination, set_disc_slot_illumination);
+    builder_field!(eject_lock, EjectLock, set_eject_lock);
+    builder_field!(sync, Sync, set_sync);
+    builder_field!(play_mode, PlayMode, set_play_mode);
+    builder_field!(quantize_beat_value, QuantizeBeatValue, set_quantize_beat_value);
+    builder_field!(hotcue_autoload, HotCueAutoLoad, set_hotcue_autoload);
+    builder_field!(hotcue_color, HotCueColor, set_hotcue_color);
+    builder_field!(needle_lock, NeedleLock, set_needle_lock);
+    builder_field!(time_mode, TimeMode, set_time_mode);
+    builder_field!(jog_mode, JogMode, set_jog_mode);
+    builder_field!(auto_cue, AutoCue, set_auto_cue);
+    builder_field!(master_tempo, MasterTempo, set_master_tempo);
+    builder_field!(tempo_range, TempoRange, set_tempo_range);
+    builder_field!(phase_meter, PhaseMeter, set_phase_meter);
+
+    // -- MySetting2 fields --------------------------------------------------------------------
+    builder_field!(vinyl_speed_adjust, VinylSpeedAdjust, set_vinyl_speed_adjust);
+    builder_field!(jog_display_mode, JogDisplayMode, set_jog_display_mode);
+    builder_field!(pad_button_brightness, PadButtonBrightness, set_pad_button_brightness);
+    builder_field!(jog_lcd_brightness, JogLCDBrightness, set_jog_lcd_brightness);
+    builder_field!(waveform_divisions, WaveformDivisions, set_waveform_divisions);
+    builder_field!(waveform, Waveform, set_waveform);
+    builder_field!(beat_jump_beat_value, BeatJumpBeatValue, set_beat_jump_beat_value);
+
+    // -- DJMMySetting fields -------------------------------------------------------------------
+    builder_field!(channel_fader_curve, ChannelFaderCurve, set_channel_fader_curve);
+    builder_field!(crossfader_curve, CrossfaderCurve, set_crossfader_curve);
+    builder_field!(headphones_pre_eq, HeadphonesPreEQ, set_headphones_pre_eq);
+    builder_field!(headphones_mono_split, HeadphonesMonoSplit, set_headphones_mono_split);
+    builder_field!(beat_fx_quantize, BeatFXQuantize, set_beat_fx_quantize);
+    builder_field!(mic_low_cut, MicLowCut, set_mic_low_cut);
+    builder_field!(talk_over_mode, TalkOverMode, set_talk_over_mode);
+    builder_field!(talk_over_level, TalkOverLevel, set_talk_over_level);
+    builder_field!(midi_channel, MidiChannel, set_midi_channel);
+    builder_field!(midi_button_type, MidiButtonType, set_midi_button_type);
+    builder_field!(mixer_display_brightness, MixerDisplayBrightness, set_mixer_display_brightness);
+    builder_field!(mixer_indicator_brightness, MixerIndicatorBrightness, set_mixer_indicator_brightness);
+    builder_field!(channel_fader_curve_long_fader, ChannelFaderCurveLongFader, set_channel_fader_curve_long_fader);
+
+    // -- DevSetting fields ---------------------------------------------------------------------
+    builder_field!(overview_waveform_type, OverviewWaveformType, set_overview_waveform_type);
+    builder_field!(waveform_color, WaveformColor, set_waveform_color);
+    builder_field!(key_display_format, KeyDisplayFormat, set_key_display_format);
+    builder_field!(waveform_current_position, WaveformCurrentPosition, set_waveform_current_position);
+}
+
+// -- Generic field reflection -----------------------------------------------------------------
+
+/// Declares [`SettingField`], [`SettingValue`], and the [`Setting::get`]/[`Setting::set`]/
+/// [`Setting::fields`] methods from a single table of `(field, name, kind, type, getter, setter)`
+/// tuples, so the full set of 46 typed accessors above doesn't need to be hand-duplicated again.
+macro_rules! setting_fields {
+    ($(($variant:ident, $name:literal, $kind:ident, $ty:ty, $getter:ident, $setter:ident)),* $(,)?) => {
+        /// Identifies one field of a `Setting`, independent of which `*SETTING.DAT` kind it
+        /// belongs to. Used by [`Setting::get`], [`Setting::set`], and [`Setting::fields`] so
+        /// generic tooling (dump-all, copy-selected-fields, bulk compare, ...) doesn't need to
+        /// hard-code each typed accessor.
+        #[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+        pub enum SettingField {
+            $($variant,)*
+        }
+
+        impl SettingField {
+            /// This field's accessor name, e.g. `"auto_cue"` — matches [`SettingDocument`]'s
+            /// field keys.
+            pub fn name(&self) -> &'static str {
+                match self {
+                    $(SettingField::$variant => $name,)*
+                }
+            }
+        }
+
+        /// A typed value for one [`SettingField`], as returned by [`Setting::get`] and accepted
+        /// by [`Setting::set`].
+        #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+        pub enum SettingValue {
+            $($variant($ty),)*
+        }
+
+        impl Setting {
+            /// Reads the current value of `field`. Errs if `field` doesn't belong to this
+            /// `Setting`'s kind.
+            pub fn get(&self, field: SettingField) -> anyhow::Result<SettingValue> {
+                match field {
+                    $(SettingField::$variant => Ok(SettingValue::$variant(self.$getter()?)),)*
+                }
+            }
+
+            /// Writes `value` to `field`. Errs if `field` doesn't belong to this `Setting`'s
+            /// kind, or if `value`'s variant doesn't match `field`.
+            pub fn set(&mut self, field: SettingField, value: SettingValue) -> anyhow::Result<()> {
+                match (field, value) {
+                    $((SettingField::$variant, SettingValue::$variant(v)) => self.$setter(v),)*
+                    (field, _) => Err(anyhow!("value type does not match field {:?}", field)),
+                }
+            }
+
+            /// Iterates over every `(field, value)` pair valid for this `Setting`'s kind.
+            pub fn fields(&self) -> anyhow::Result<Vec<(SettingField, SettingValue)>> {
+                let mut out = Vec::new();
+                $(
+                    if self.kind() == SettingKind::$kind {
+                        out.push((SettingField::$variant, SettingValue::$variant(self.$getter()?)));
+                    }
+                )*
+                Ok(out)
+            }
+        }
+    };
+}
+
+setting_fields! {
+    (OnAirDisplay, "on_air_display", MySetting, OnAirDisplay, get_on_air_display, set_on_air_display),
+    (LcdBrightness, "lcd_brightness", MySetting, LCDBrightness, get_lcd_brightness, set_lcd_brightness),
+    (Quantize, "quantize", MySetting, Quantize, get_quantize, set_quantize),
+    (AutoCueLevel, "auto_cue_level", MySetting, AutoCueLevel, get_auto_cue_level, set_auto_cue_level),
+    (Language, "language", MySetting, Language, get_language, set_language),
+    (JogRingBrightness, "jog_ring_brightness", MySetting, JogRingBrightness, get_jog_ring_brightness, set_jog_ring_brightness),
+    (JogRingIndicator, "jog_ring_indicator", MySetting, JogRingIndicator, get_jog_ring_indicator, set_jog_ring_indicator),
+    (SlipFlashing, "slip_flashing", MySetting, SlipFlashing, get_slip_flashing, set_slip_flashing),
+    (DiscSlotIllumination, "disc_slot_illumination", MySetting, DiscSlotIllumination, get_disc_slot_illumination, set_disc_slot_illumination),
+    (EjectLock, "eject_lock", MySetting, EjectLock, get_eject_lock, set_eject_lock),
+    (Sync, "sync", MySetting, Sync, get_sync, set_sync),
+    (PlayMode, "play_mode", MySetting, PlayMode, get_play_mode, set_play_mode),
+    (QuantizeBeatValue, "quantize_beat_value", MySetting, QuantizeBeatValue, get_quantize_beat_value, set_quantize_beat_value),
+    (HotcueAutoload, "hotcue_autoload", MySetting, HotCueAutoLoad, get_hotcue_autoload, set_hotcue_autoload),
+    (HotcueColor, "hotcue_color", MySetting, HotCueColor, get_hotcue_color, set_hotcue_color),
+    (NeedleLock, "needle_lock", MySetting, NeedleLock, get_needle_lock, set_needle_lock),
+    (TimeMode, "time_mode", MySetting, TimeMode, get_time_mode, set_time_mode),
+    (JogMode, "jog_mode", MySetting, JogMode, get_jog_mode, set_jog_mode),
+    (AutoCue, "auto_cue", MySetting, AutoCue, get_auto_cue, set_auto_cue),
+    (MasterTempo, "master_tempo", MySetting, MasterTempo, get_master_tempo, set_master_tempo),
+    (TempoRange, "tempo_range", MySetting, TempoRange, get_tempo_range, set_tempo_range),
+    (PhaseMeter, "phase_meter", MySetting, PhaseMeter, get_phase_meter, set_phase_meter),
+
+    (VinylSpeedAdjust, "vinyl_speed_adjust", MySetting2, VinylSpeedAdjust, get_vinyl_speed_adjust, set_vinyl_speed_adjust),
+    (JogDisplayMode, "jog_display_mode", MySetting2, JogDisplayMode, get_jog_display_mode, set_jog_display_mode),
+    (PadButtonBrightness, "pad_button_brightness", MySetting2, PadButtonBrightness, get_pad_button_brightness, set_pad_button_brightness),
+    (JogLcdBrightness, "jog_lcd_brightness", MySetting2, JogLCDBrightness, get_jog_lcd_brightness, set_jog_lcd_brightness),
+    (WaveformDivisions, "waveform_divisions", MySetting2, WaveformDivisions, get_waveform_divisions, set_waveform_divisions),
+    (Waveform, "waveform", MySetting2, Waveform, get_waveform, set_waveform),
+    (BeatJumpBeatValue, "beat_jump_beat_value", MySetting2, BeatJumpBeatValue, get_beat_jump_beat_value, set_beat_jump_beat_value),
+
+    (ChannelFaderCurve, "channel_fader_curve", DJMMySetting, ChannelFaderCurve, get_channel_fader_curve, set_channel_fader_curve),
+    (CrossfaderCurve, "crossfader_curve", DJMMySetting, CrossfaderCurve, get_crossfader_curve, set_crossfader_curve),
+    (HeadphonesPreEq, "headphones_pre_eq", DJMMySetting, HeadphonesPreEQ, get_headphones_pre_eq, set_headphones_pre_eq),
+    (HeadphonesMonoSplit, "headphones_mono_split", DJMMySetting, HeadphonesMonoSplit, get_headphones_mono_split, set_headphones_mono_split),
+    (BeatFxQuantize, "beat_fx_quantize", DJMMySetting, BeatFXQuantize, get_beat_fx_quantize, set_beat_fx_quantize),
+    (MicLowCut, "mic_low_cut", DJMMySetting, MicLowCut, get_mic_low_cut, set_mic_low_cut),
+    (TalkOverMode, "talk_over_mode", DJMMySetting, TalkOverMode, get_talk_over_mode, set_talk_over_mode),
+    (TalkOverLevel, "talk_over_level", DJMMySetting, TalkOverLevel, get_talk_over_level, set_talk_over_level),
+    (MidiChannel, "midi_channel", DJMMySetting, MidiChannel, get_midi_channel, set_midi_channel),
+    (MidiButtonType, "midi_button_type", DJMMySetting, MidiButtonType, get_midi_button_type, set_midi_button_type),
+    (MixerDisplayBrightness, "mixer_display_brightness", DJMMySetting, MixerDisplayBrightness, get_mixer_display_brightness, set_mixer_display_brightness),
+    (MixerIndicatorBrightness, "mixer_indicator_brightness", DJMMySetting, MixerIndicatorBrightness, get_mixer_indicator_brightness, set_mixer_indicator_brightness),
+    (ChannelFaderCurveLongFader, "channel_fader_curve_long_fader", DJMMySetting, ChannelFaderCurveLongFader, get_channel_fader_curve_long_fader, set_channel_fader_curve_long_fader),
+
+    (OverviewWaveformType, "overview_waveform_type", DevSetting, OverviewWaveformType, get_overview_waveform_type, set_overview_waveform_type),
+    (WaveformColor, "waveform_color", DevSetting, WaveformColor, get_waveform_color, set_waveform_color),
+    (KeyDisplayFormat, "key_display_format", DevSetting, KeyDisplayFormat, get_key_display_format, set_key_display_format),
+    (WaveformCurrentPosition, "waveform_current_position", DevSetting, WaveformCurrentPosition, get_waveform_current_position, set_waveform_current_position),
+}
+
+// -- Diff and merge -----------------------------------------------------------------------------
+
+/// One field that differs between two `Setting`s, as produced by [`Setting::diff`].
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct SettingChange {
+    /// The field that differs.
+    pub field: SettingField,
+    /// The value on the `self` side passed to [`Setting::diff`].
+    pub old: SettingValue,
+    /// The value on the `other` side passed to [`Setting::diff`].
+    pub new: SettingValue,
+}
+
+impl Setting {
+    /// Compares `self` and `other` field-by-field (via [`Setting::fields`]) and returns every
+    /// field whose value differs, with `self` as the "old" side and `other` as the "new" side.
+    ///
+    /// # Errors
+    /// Returns an error if `other` is not the same [`SettingKind`] as `self` — there's nothing a
+    /// caller could meaningfully [`Setting::apply`] across two different file kinds.
+    pub fn diff(&self, other: &Setting) -> anyhow::Result<Vec<SettingChange>> {
+        if self.kind() != other.kind() {
+            return Err(anyhow!(
+                "cannot diff a {:?} Setting against a {:?} Setting",
+                self.kind(),
+                other.kind()
+            ));
+        }
+
+        let mut changes = Vec::new();
+        for (field, old) in self.fields()? {
+            let new = other.get(field)?;
+            if new != old {
+                changes.push(SettingChange { field, old, new });
+            }
+        }
+        Ok(changes)
+    }
+
+    /// Applies `changes` to `self`, writing each change's `new` value to its `field`. Useful for
+    /// selectively merging a handful of fields from [`Setting::diff`] into another file without
+    /// overwriting the rest.
+    pub fn apply(&mut self, changes: &[SettingChange]) -> anyhow::Result<()> {
+        for change in changes {
+            self.set(change.field, change.new)?;
+        }
+        Ok(())
+    }
+
+    /// Three-way merges `theirs` onto `self`, carrying over only the fields that changed between
+    /// `base` and `theirs`. Lets a user copy a handful of tweaked parameters (say just a
+    /// crossfader curve and talk-over level) from one exported setting file onto another device's
+    /// file, without clobbering that device's other settings.
+    ///
+    /// Returns the [`SettingChange`]s that were applied.
+    ///
+    /// # Errors
+    /// Returns an error if `base` and `theirs` are not the same [`SettingKind`] as each other, or
+    /// as `self` (see [`Setting::diff`]/[`Setting::apply`]).
+    pub fn merge(&mut self, base: &Setting, theirs: &Setting) -> anyhow::Result<Vec<SettingChange>> {
+        if self.kind() != base.kind() {
+            return Err(anyhow!(
+                "cannot merge a {:?} Setting using a {:?} base",
+                self.kind(),
+                base.kind()
+            ));
+        }
+
+        let changes = base.diff(theirs)?;
+        self.apply(&changes)?;
+        Ok(changes)
+    }
+}
+
+// -- DJM mixer profile --------------------------------------------------------------------------
+
+/// Every decoded field of a `DJMMYSETTING.DAT` file, bundled into one serializable struct so a
+/// whole mixer configuration round-trips through [`Setting::export_profile`]/
+/// [`Setting::apply_profile`] in a single call instead of one `get_*`/`set_*` pair per field.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DjmMySettingProfile {
+    pub beat_fx_quantize: BeatFXQuantize,
+    pub channel_fader_curve: ChannelFaderCurve,
+    pub channel_fader_curve_long_fader: ChannelFaderCurveLongFader,
+    pub crossfader_curve: CrossfaderCurve,
+    pub mixer_display_brightness: MixerDisplayBrightness,
+    pub headphones_mono_split: HeadphonesMonoSplit,
+    pub headphones_pre_eq: HeadphonesPreEQ,
+    pub mixer_indicator_brightness: MixerIndicatorBrightness,
+    pub mic_low_cut: MicLowCut,
+    pub midi_button_type: MidiButtonType,
+    pub midi_channel: MidiChannel,
+    pub talk_over_level: TalkOverLevel,
+    pub talk_over_mode: TalkOverMode,
+}
+
+impl Setting {
+    /// Reads every `DJMMYSETTING.DAT` field into one [`DjmMySettingProfile`], for dumping to
+    /// JSON/YAML, hand-editing, and reapplying via [`Setting::apply_profile`].
+    ///
+    /// # Errors
+    /// Returns an error if this `Setting` is not a `DJMMySetting`.
+    pub fn export_profile(&self) -> anyhow::Result<DjmMySettingProfile> {
+        Ok(DjmMySettingProfile {
+            beat_fx_quantize: self.get_beat_fx_quantize()?,
+            channel_fader_curve: self.get_channel_fader_curve()?,
+            channel_fader_curve_long_fader: self.get_channel_fader_curve_long_fader()?,
+            crossfader_curve: self.get_crossfader_curve()?,
+            mixer_display_brightness: self.get_mixer_display_brightness()?,
+            headphones_mono_split: self.get_headphones_mono_split()?,
+            headphones_pre_eq: self.get_headphones_pre_eq()?,
+            mixer_indicator_brightness: self.get_mixer_indicator_brightness()?,
+            mic_low_cut: self.get_mic_low_cut()?,
+            midi_button_type: self.get_midi_button_type()?,
+            midi_channel: self.get_midi_channel()?,
+            talk_over_level: self.get_talk_over_level()?,
+            talk_over_mode: self.get_talk_over_mode()?,
+        })
+    }
+
+    /// Writes every field of `profile` back into this `Setting` in one call.
+    ///
+    /// # Errors
+    /// Returns an error if this `Setting` is not a `DJMMySetting`.
+    pub fn apply_profile(&mut self, profile: &DjmMySettingProfile) -> anyhow::Result<()> {
+        self.set_beat_fx_quantize(profile.beat_fx_quantize)?;
+        self.set_channel_fader_curve(profile.channel_fader_curve)?;
+        self.set_channel_fader_curve_long_fader(profile.channel_fader_curve_long_fader)?;
+        self.set_crossfader_curve(profile.crossfader_curve)?;
+        self.set_mixer_display_brightness(profile.mixer_display_brightness)?;
+        self.set_headphones_mono_split(profile.headphones_mono_split)?;
+        self.set_headphones_pre_eq(profile.headphones_pre_eq)?;
+        self.set_mixer_indicator_brightness(profile.mixer_indicator_brightness)?;
+        self.set_mic_low_cut(profile.mic_low_cut)?;
+        self.set_midi_button_type(profile.midi_button_type)?;
+        self.set_midi_channel(profile.midi_channel)?;
+        self.set_talk_over_level(profile.talk_over_level)?;
+        self.set_talk_over_mode(profile.talk_over_mode)?;
+        Ok(())
+    }
+}
+
+// -- Curve transfer functions --------------------------------------------------------------------
+
+/// Converts a linear gain to decibels, clamping the result at `-60.0` dB so a near-silent gain
+/// (or an exact `0.0`) doesn't produce `-inf`.
+fn gain_to_db(gain: f32) -> f32 {
+    (20.0 * gain.log10()).max(-60.0)
+}
+
+impl CrossfaderCurve {
+    /// Returns the linear `(left, right)` gains a DJM would apply at crossfader position `t`,
+    /// where `t = 0.0` is fully left and `t = 1.0` is fully right.
+    pub fn gains_at(&self, t: f32) -> (f32, f32) {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            CrossfaderCurve::ConstantPower => {
+                let angle = t * std::f32::consts::FRAC_PI_2;
+                (angle.cos(), angle.sin())
+            }
+            CrossfaderCurve::SlowCut => (1.0 - t, t),
+            CrossfaderCurve::FastCut => {
+                const K: f32 = 8.0;
+                ((K * (1.0 - t)).clamp(0.0, 1.0), (K * t).clamp(0.0, 1.0))
+            }
+        }
+    }
+}
+
+impl ChannelFaderCurve {
+    /// Returns the linear gain a DJM would apply when the channel fader is at travel `t`, where
+    /// `t = 0.0` is fully down and `t = 1.0` is fully up.
+    pub fn gain_at(&self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            ChannelFaderCurve::Linear => t,
+            ChannelFaderCurve::SteepBottom => t * t,
+            ChannelFaderCurve::SteepTop => 1.0 - (1.0 - t) * (1.0 - t),
+        }
+    }
+
+    /// Equivalent to [`ChannelFaderCurve::gain_at`], expressed in decibels and clamped at `-60.0`
+    /// dB.
+    pub fn to_db(&self, t: f32) -> f32 {
+        gain_to_db(self.gain_at(t))
+    }
+}
+
+impl ChannelFaderCurveLongFader {
+    /// Returns the linear gain a DJM would apply when the long channel fader is at travel `t`,
+    /// where `t = 0.0` is fully down and `t = 1.0` is fully up.
+    pub fn gain_at(&self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            ChannelFaderCurveLongFader::Linear => t,
+            ChannelFaderCurveLongFader::Exponential => t * t,
+            ChannelFaderCurveLongFader::Smooth => 3.0 * t * t - 2.0 * t * t * t,
+        }
+    }
+
+    /// Equivalent to [`ChannelFaderCurveLongFader::gain_at`], expressed in decibels and clamped
+    /// at `-60.0` dB.
+    pub fn to_db(&self, t: f32) -> f32 {
+        gain_to_db(self.gain_at(t))
+    }
+}
+
+// -- Talk-over attenuation ------------------------------------------------------------------------
+
+impl TalkOverLevel {
+    /// Returns the attenuation this level applies to the ducked channel, in decibels.
+    pub fn as_db(&self) -> f32 {
+        match self {
+            TalkOverLevel::Minus24dB => -24.0,
+            TalkOverLevel::Minus18dB => -18.0,
+            TalkOverLevel::Minus12dB => -12.0,
+            TalkOverLevel::Minus6dB => -6.0,
+        }
+    }
+
+    /// Equivalent to [`TalkOverLevel::as_db`], expressed as a linear gain factor
+    /// (`10^(db / 20)`).
+    pub fn as_linear_gain(&self) -> f32 {
+        10f32.powf(self.as_db() / 20.0)
+    }
+}
+
+impl TalkOverMode {
+    /// Whether this mode ducks every channel but the mic's (`Advanced`), or just the master
+    /// output (`Normal`).
+    pub fn is_per_channel(&self) -> bool {
+        matches!(self, TalkOverMode::Advanced)
+    }
+}
+
+impl Setting {
+    /// Returns how much a channel is ducked while the mic is live: the attenuation in decibels
+    /// applied per [`Setting::get_talk_over_level`], alongside the [`TalkOverMode`] describing
+    /// whether that attenuation is applied per-channel or to the master output.
+    ///
+    /// # Errors
+    /// Returns an error if this `Setting` is not a `DJMMySetting`.
+    pub fn talk_over_attenuation(&self) -> anyhow::Result<(f32, TalkOverMode)> {
+        Ok((self.get_talk_over_level()?.as_db(), self.get_talk_over_mode()?))
+    }
+}
@@ -0,0 +1,282 @@
+// Author: Dylan Jones
+// Date:   2026-07-30
+
+//! Encodes/decodes [`Setting`] fields as a manufacturer MIDI System Exclusive (SysEx) dump, so a
+//! configuration can be pushed live to a connected player/mixer instead of only being copied to
+//! it as a `.DAT` file on a USB stick.
+//!
+//! A message is framed as:
+//!
+//! ```text
+//! F0 <manufacturer id> <device id> <kind id> [<field id> <hi nibble> <lo nibble>]... <checksum> F7
+//! ```
+//!
+//! Every payload byte is restricted to 7 bits, as required by the MIDI SysEx wire format: each
+//! field's one-byte value is split into a high/low nibble pair so neither byte ever sets bit 7.
+//! The checksum is a running (wrapping) sum of every byte between `F0` and itself, masked to 7
+//! bits.
+
+use crate::settings::{Setting, SettingField, SettingKind, SettingValue};
+use anyhow::{anyhow, Result};
+use binrw::io::Cursor;
+use binrw::BinRead;
+
+/// Pioneer Corporation's registered one-byte MIDI SysEx manufacturer ID.
+pub const PIONEER_MANUFACTURER_ID: u8 = 0x71;
+
+const SYSEX_START: u8 = 0xF0;
+const SYSEX_END: u8 = 0xF7;
+
+fn checksum_of(bytes: &[u8]) -> u8 {
+    bytes.iter().fold(0u8, |acc, b| acc.wrapping_add(*b)) & 0x7F
+}
+
+fn kind_to_id(kind: SettingKind) -> u8 {
+    match kind {
+        SettingKind::MySetting => 0,
+        SettingKind::MySetting2 => 1,
+        SettingKind::DJMMySetting => 2,
+        SettingKind::DevSetting => 3,
+    }
+}
+
+fn kind_from_id(id: u8) -> Result<SettingKind> {
+    match id {
+        0 => Ok(SettingKind::MySetting),
+        1 => Ok(SettingKind::MySetting2),
+        2 => Ok(SettingKind::DJMMySetting),
+        3 => Ok(SettingKind::DevSetting),
+        _ => Err(anyhow!("unknown SettingKind id {id:#04x} in SysEx dump")),
+    }
+}
+
+/// Maps every [`SettingField`] to a stable one-byte id for the wire format, and back.
+macro_rules! field_ids {
+    ($(($field:ident, $id:literal)),* $(,)?) => {
+        fn field_to_id(field: SettingField) -> u8 {
+            match field {
+                $(SettingField::$field => $id,)*
+            }
+        }
+
+        fn field_from_id(id: u8) -> Result<SettingField> {
+            match id {
+                $($id => Ok(SettingField::$field),)*
+                _ => Err(anyhow!("unknown SettingField id {id:#04x} in SysEx dump")),
+            }
+        }
+    };
+}
+
+field_ids! {
+    (OnAirDisplay, 0),
+    (LcdBrightness, 1),
+    (Quantize, 2),
+    (AutoCueLevel, 3),
+    (Language, 4),
+    (JogRingBrightness, 5),
+    (JogRingIndicator, 6),
+    (SlipFlashing, 7),
+    (DiscSlotIllumination, 8),
+    (EjectLock, 9),
+    (Sync, 10),
+    (PlayMode, 11),
+    (QuantizeBeatValue, 12),
+    (HotcueAutoload, 13),
+    (HotcueColor, 14),
+    (NeedleLock, 15),
+    (TimeMode, 16),
+    (JogMode, 17),
+    (AutoCue, 18),
+    (MasterTempo, 19),
+    (TempoRange, 20),
+    (PhaseMeter, 21),
+    (VinylSpeedAdjust, 22),
+    (JogDisplayMode, 23),
+    (PadButtonBrightness, 24),
+    (JogLcdBrightness, 25),
+    (WaveformDivisions, 26),
+    (Waveform, 27),
+    (BeatJumpBeatValue, 28),
+    (ChannelFaderCurve, 29),
+    (CrossfaderCurve, 30),
+    (HeadphonesPreEq, 31),
+    (HeadphonesMonoSplit, 32),
+    (BeatFxQuantize, 33),
+    (MicLowCut, 34),
+    (TalkOverMode, 35),
+    (TalkOverLevel, 36),
+    (MidiChannel, 37),
+    (MidiButtonType, 38),
+    (MixerDisplayBrightness, 39),
+    (MixerIndicatorBrightness, 40),
+    (ChannelFaderCurveLongFader, 41),
+    (OverviewWaveformType, 42),
+    (WaveformColor, 43),
+    (KeyDisplayFormat, 44),
+    (WaveformCurrentPosition, 45),
+}
+
+/// Converts every `SettingValue` variant to/from its raw one-byte `binrw` representation.
+macro_rules! value_byte_codec {
+    ($(($variant:ident, $ty:ty)),* $(,)?) => {
+        fn value_to_byte(value: SettingValue) -> u8 {
+            match value {
+                $(SettingValue::$variant(v) => v as u8,)*
+            }
+        }
+
+        fn value_from_byte(field: SettingField, byte: u8) -> Result<SettingValue> {
+            let mut reader = Cursor::new(vec![byte]);
+            match field {
+                $(SettingField::$variant => Ok(SettingValue::$variant(
+                    <$ty>::read(&mut reader).map_err(|e| anyhow!("invalid byte for {:?}: {e}", field))?,
+                )),)*
+            }
+        }
+    };
+}
+
+value_byte_codec! {
+    (OnAirDisplay, crate::settings::OnAirDisplay),
+    (LcdBrightness, crate::settings::LCDBrightness),
+    (Quantize, crate::settings::Quantize),
+    (AutoCueLevel, crate::settings::AutoCueLevel),
+    (Language, crate::settings::Language),
+    (JogRingBrightness, crate::settings::JogRingBrightness),
+    (JogRingIndicator, crate::settings::JogRingIndicator),
+    (SlipFlashing, crate::settings::SlipFlashing),
+    (DiscSlotIllumination, crate::settings::DiscSlotIllumination),
+    (EjectLock, crate::settings::EjectLock),
+    (Sync, crate::settings::Sync),
+    (PlayMode, crate::settings::PlayMode),
+    (QuantizeBeatValue, crate::settings::QuantizeBeatValue),
+    (HotcueAutoload, crate::settings::HotCueAutoLoad),
+    (HotcueColor, crate::settings::HotCueColor),
+    (NeedleLock, crate::settings::NeedleLock),
+    (TimeMode, crate::settings::TimeMode),
+    (JogMode, crate::settings::JogMode),
+    (AutoCue, crate::settings::AutoCue),
+    (MasterTempo, crate::settings::MasterTempo),
+    (TempoRange, crate::settings::TempoRange),
+    (PhaseMeter, crate::settings::PhaseMeter),
+    (VinylSpeedAdjust, crate::settings::VinylSpeedAdjust),
+    (JogDisplayMode, crate::settings::JogDisplayMode),
+    (PadButtonBrightness, crate::settings::PadButtonBrightness),
+    (JogLcdBrightness, crate::settings::JogLCDBrightness),
+    (WaveformDivisions, crate::settings::WaveformDivisions),
+    (Waveform, crate::settings::Waveform),
+    (BeatJumpBeatValue, crate::settings::BeatJumpBeatValue),
+    (ChannelFaderCurve, crate::settings::ChannelFaderCurve),
+    (CrossfaderCurve, crate::settings::CrossfaderCurve),
+    (HeadphonesPreEq, crate::settings::HeadphonesPreEQ),
+    (HeadphonesMonoSplit, crate::settings::HeadphonesMonoSplit),
+    (BeatFxQuantize, crate::settings::BeatFXQuantize),
+    (MicLowCut, crate::settings::MicLowCut),
+    (TalkOverMode, crate::settings::TalkOverMode),
+    (TalkOverLevel, crate::settings::TalkOverLevel),
+    (MidiChannel, crate::settings::MidiChannel),
+    (MidiButtonType, crate::settings::MidiButtonType),
+    (MixerDisplayBrightness, crate::settings::MixerDisplayBrightness),
+    (MixerIndicatorBrightness, crate::settings::MixerIndicatorBrightness),
+    (ChannelFaderCurveLongFader, crate::settings::ChannelFaderCurveLongFader),
+    (OverviewWaveformType, crate::settings::OverviewWaveformType),
+    (WaveformColor, crate::settings::WaveformColor),
+    (KeyDisplayFormat, crate::settings::KeyDisplayFormat),
+    (WaveformCurrentPosition, crate::settings::WaveformCurrentPosition),
+}
+
+/// Returns the zero-based MIDI channel (0-15) to target for a `DJMMySetting`'s configured
+/// [`crate::settings::MidiChannel`], or `None` if `setting` isn't a `DJMMySetting`.
+pub fn midi_channel_of(setting: &Setting) -> Option<u8> {
+    setting
+        .get_midi_channel()
+        .ok()
+        .map(|channel| (channel as u8) & 0x0F)
+}
+
+/// Returns whether `setting`'s configured [`crate::settings::MidiButtonType`] calls for
+/// toggle semantics (`true`, one message per state change) rather than trigger semantics
+/// (`false`, a message for every press), or `None` if `setting` isn't a `DJMMySetting`.
+pub fn uses_toggle_semantics(setting: &Setting) -> Option<bool> {
+    setting
+        .get_midi_button_type()
+        .ok()
+        .map(|button_type| button_type == crate::settings::MidiButtonType::Toggle)
+}
+
+/// Encodes `fields` of `setting` into a single SysEx message addressed to `device_id` (masked to
+/// 7 bits). If `setting` has a configured `MidiChannel` (i.e. it's a `DJMMySetting`), prefer
+/// [`encode_on_configured_channel`] so the message is sent on the channel the device was set up
+/// to listen on.
+pub fn encode(setting: &Setting, device_id: u8, fields: &[SettingField]) -> Result<Vec<u8>> {
+    let mut payload = vec![kind_to_id(setting.kind())];
+    for &field in fields {
+        let value = setting.get(field)?;
+        let byte = value_to_byte(value);
+        payload.push(field_to_id(field));
+        payload.push((byte >> 4) & 0x0F);
+        payload.push(byte & 0x0F);
+    }
+
+    let mut body = vec![PIONEER_MANUFACTURER_ID, device_id & 0x7F];
+    body.extend_from_slice(&payload);
+
+    let mut message = Vec::with_capacity(body.len() + 3);
+    message.push(SYSEX_START);
+    message.extend_from_slice(&body);
+    message.push(checksum_of(&body));
+    message.push(SYSEX_END);
+    Ok(message)
+}
+
+/// Like [`encode`], but addresses the message to the device/MIDI channel byte derived from
+/// `setting`'s own configured [`crate::settings::MidiChannel`] (falls back to `device_id` if
+/// `setting` has no such field).
+pub fn encode_on_configured_channel(
+    setting: &Setting,
+    device_id: u8,
+    fields: &[SettingField],
+) -> Result<Vec<u8>> {
+    let channel = midi_channel_of(setting).unwrap_or(device_id);
+    encode(setting, channel, fields)
+}
+
+/// Decodes a SysEx message produced by [`encode`] back into a [`Setting`], starting from the
+/// encoded [`SettingKind`]'s defaults and applying every field carried by the message.
+pub fn decode(message: &[u8]) -> Result<Setting> {
+    if message.len() < 6 || message[0] != SYSEX_START || message[message.len() - 1] != SYSEX_END {
+        return Err(anyhow!("not a well-formed SysEx message"));
+    }
+
+    let body_end = message.len() - 2; // index of the checksum byte
+    let body = &message[1..body_end];
+    if body.len() < 2 {
+        return Err(anyhow!("SysEx message is missing its manufacturer/device/kind bytes"));
+    }
+    if body[0] != PIONEER_MANUFACTURER_ID {
+        return Err(anyhow!("unexpected SysEx manufacturer id {:#04x}", body[0]));
+    }
+
+    let checksum = message[body_end];
+    if checksum_of(body) != checksum {
+        return Err(anyhow!("SysEx checksum mismatch"));
+    }
+
+    let kind = kind_from_id(body[2])?;
+    let mut setting = Setting::new(kind);
+
+    let mut i = 3;
+    while i < body.len() {
+        if i + 2 >= body.len() {
+            return Err(anyhow!("truncated field triplet in SysEx message"));
+        }
+        let field = field_from_id(body[i])?;
+        let byte = (body[i + 1] << 4) | body[i + 2];
+        let value = value_from_byte(field, byte)?;
+        setting.set(field, value)?;
+        i += 3;
+    }
+
+    Ok(setting)
+}
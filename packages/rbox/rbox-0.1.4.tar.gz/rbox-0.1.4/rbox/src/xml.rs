@@ -311,6 +311,26 @@ pub struct Track {
     #[serde(skip_serializing_if = "Vec::is_empty")]
     #[serde(default)]
     pub position_marks: Vec<PositionMark>,
+    /// MusicBrainz recording ID, for tracks enriched via `enrich_from_musicbrainz`
+    #[serde(rename = "@MusicBrainzID")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub musicbrainz_id: Option<String>,
+    /// Sort-order override for `name`, e.g. "Beatles, The"
+    #[serde(rename = "@NameSort")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub name_sort: Option<String>,
+    /// Sort-order override for `artist`
+    #[serde(rename = "@ArtistSort")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub artist_sort: Option<String>,
+    /// Sort-order override for `album`
+    #[serde(rename = "@AlbumSort")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub album_sort: Option<String>,
 }
 
 impl Track {
@@ -356,6 +376,10 @@ impl Default for Track {
             color: None,
             tempos: Vec::new(),
             position_marks: Vec::new(),
+            musicbrainz_id: None,
+            name_sort: None,
+            artist_sort: None,
+            album_sort: None,
         }
     }
 }
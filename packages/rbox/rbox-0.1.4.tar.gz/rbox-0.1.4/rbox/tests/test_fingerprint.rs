@@ -0,0 +1,47 @@
+// Author: Dylan Jones
+// Date:   2025-08-16
+
+use rbox::fingerprint::Fingerprint;
+
+#[test]
+fn test_compare_identical_fingerprints_scores_one() {
+    let fp = Fingerprint {
+        subfingerprints: vec![0x1234_5678, 0x0000_ffff, 0xdead_beef],
+    };
+    assert_eq!(fp.compare(&fp), 1.0);
+}
+
+#[test]
+fn test_compare_finds_best_sliding_window_alignment() {
+    let a = Fingerprint {
+        subfingerprints: vec![0x1111_1111, 0x2222_2222, 0x3333_3333],
+    };
+    let b = Fingerprint {
+        subfingerprints: vec![0x0000_0000, 0x1111_1111, 0x2222_2222, 0x3333_3333, 0xffff_ffff],
+    };
+    assert_eq!(a.compare(&b), 1.0);
+}
+
+#[test]
+fn test_compare_empty_fingerprint_scores_zero() {
+    let a = Fingerprint {
+        subfingerprints: vec![],
+    };
+    let b = Fingerprint {
+        subfingerprints: vec![0x1234_5678],
+    };
+    assert_eq!(a.compare(&b), 0.0);
+}
+
+#[test]
+fn test_compare_dissimilar_fingerprints_scores_low() {
+    // Fully complementary in the 24 bits `quantize` actually sets; the unused top 8 bits are
+    // left at zero on both sides to confirm they aren't counted as "matching".
+    let a = Fingerprint {
+        subfingerprints: vec![0x0000_0000, 0x0000_0000, 0x0000_0000],
+    };
+    let b = Fingerprint {
+        subfingerprints: vec![0x00ff_ffff, 0x00ff_ffff, 0x00ff_ffff],
+    };
+    assert_eq!(a.compare(&b), 0.0);
+}
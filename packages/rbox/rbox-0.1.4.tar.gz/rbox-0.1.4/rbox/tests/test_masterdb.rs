@@ -1336,3 +1336,121 @@ pub fn test_move_playlist_song() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_export_import_playlist_m3u8() -> anyhow::Result<()> {
+    common::setup_master_playlist_xml()?;
+    let mut db = common::setup_master_db()?;
+
+    let pl = db.create_playlist("Playlist".to_string(), None, None, None, None)?;
+    let contents = db.get_content()?;
+    let cid1 = contents[0].ID.clone();
+    let cid2 = contents[1].ID.clone();
+    db.insert_playlist_song(&pl.ID, &cid1, None)?;
+    db.insert_playlist_song(&pl.ID, &cid2, None)?;
+
+    let dir = std::env::temp_dir().join(format!("rbox-test-export-{}", pl.ID));
+    std::fs::create_dir_all(&dir)?;
+    let out_path = dir.join("Playlist.m3u8");
+    let written = db.export_playlist(&pl.ID, "m3u8", &out_path.to_string_lossy())?;
+    assert_eq!(written, 1);
+
+    let m3u8 = std::fs::read_to_string(&out_path)?;
+    assert!(m3u8.starts_with("#EXTM3U\n"));
+    assert!(m3u8.contains(contents[0].FolderPath.as_deref().unwrap()));
+    assert!(m3u8.contains(contents[1].FolderPath.as_deref().unwrap()));
+
+    let imported = db.import_playlist(&out_path.to_string_lossy(), None)?;
+    assert_eq!(imported.Name, Some("Playlist".to_string()));
+    let songs = db.get_playlist_songs(&imported.ID)?;
+    let content_ids: Vec<String> = songs.iter().map(|s| s.ContentID.clone().unwrap()).collect();
+    assert_eq!(content_ids, vec![cid1, cid2]);
+
+    std::fs::remove_dir_all(&dir)?;
+    Ok(())
+}
+
+// -- Source -----------------------------------------------------------------------------------
+
+#[test]
+fn test_content_sources_round_trip() -> anyhow::Result<()> {
+    use rbox::masterdb::Provider;
+
+    let mut db = common::setup_master_db()?;
+    let sources_path = db.sources_path.clone();
+
+    let contents = db.get_content()?;
+    let cid = contents[0].ID.clone();
+
+    assert!(db.content_sources(&cid)?.is_empty());
+
+    db.add_content_source(&cid, Provider::MusicBrainz, "mbid-1234".to_string())?;
+    db.add_content_source(&cid, Provider::Discogs, "https://discogs.com/release/1".to_string())?;
+
+    let sources = db.content_sources(&cid)?;
+    assert_eq!(sources.len(), 2);
+    assert_eq!(sources[0].provider, Provider::MusicBrainz);
+    assert_eq!(sources[0].url, "mbid-1234");
+    assert_eq!(sources[1].provider, Provider::Discogs);
+
+    // Other content entries are unaffected.
+    let cid2 = contents[1].ID.clone();
+    assert!(db.content_sources(&cid2)?.is_empty());
+
+    let _ = std::fs::remove_file(sources_path);
+    Ok(())
+}
+
+#[test]
+fn test_export_playlist_tree() -> anyhow::Result<()> {
+    common::setup_master_playlist_xml()?;
+    let mut db = common::setup_master_db()?;
+
+    let folder = db.create_playlist_folder("Folder".to_string(), None, None)?;
+    let pid = Some(folder.ID.clone());
+    db.create_playlist("Sub 1".to_string(), pid.clone(), None, None, None)?;
+    db.create_playlist("Sub 2".to_string(), pid.clone(), None, None, None)?;
+
+    let dir = std::env::temp_dir().join(format!("rbox-test-export-tree-{}", folder.ID));
+    let written = db.export_playlist(&folder.ID, "tree", &dir.to_string_lossy())?;
+    assert_eq!(written, 2);
+    assert!(dir.join("Folder").join("Sub 1.m3u8").exists());
+    assert!(dir.join("Folder").join("Sub 2.m3u8").exists());
+
+    std::fs::remove_dir_all(&dir)?;
+    Ok(())
+}
+
+// -- RelatedTracks ------------------------------------------------------------------------------
+
+#[test]
+fn test_build_related_tracks_from_embeddings() -> anyhow::Result<()> {
+    use std::collections::HashMap;
+
+    let mut db = common::setup_master_db()?;
+    let contents = db.get_content()?;
+    assert!(contents.len() >= 3);
+
+    let seed = contents[0].ID.clone();
+    let near = contents[1].ID.clone();
+    let far = contents[2].ID.clone();
+
+    let mut embeddings: HashMap<String, Vec<f32>> = HashMap::new();
+    embeddings.insert(seed.clone(), vec![1.0, 0.0]);
+    embeddings.insert(near.clone(), vec![0.9, 0.1]);
+    embeddings.insert(far.clone(), vec![0.0, 1.0]);
+
+    let created = db.build_related_tracks(&embeddings, 5, 0.5, None)?;
+    assert_eq!(created.len(), 2); // `far` has no neighbour above the threshold.
+
+    let seed_name = format!("Related: {}", seed);
+    let seed_entry = created
+        .iter()
+        .find(|rt| rt.Name.as_deref() == Some(seed_name.as_str()))
+        .expect("related tracks entry for seed");
+    let members = db.get_related_tracks_contents(&seed_entry.ID)?;
+    assert_eq!(members.len(), 1);
+    assert_eq!(members[0].ID, near);
+
+    Ok(())
+}
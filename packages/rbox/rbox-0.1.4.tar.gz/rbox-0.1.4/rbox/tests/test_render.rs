@@ -0,0 +1,46 @@
+// Author: Dylan Jones
+// Date:   2025-08-15
+
+use rbox::anlz::render;
+
+mod common;
+
+#[test]
+fn test_render_waveform_color_detail_buffer_size() -> anyhow::Result<()> {
+    let mut files = common::setup_anlz_files()?;
+    let detail = files.ext.get_waveform_color_detail().expect("missing color detail");
+    let buffer = render::render_waveform_color_detail(&detail.data, 400, 80);
+    assert_eq!(buffer.len(), 400 * 80 * 4);
+    Ok(())
+}
+
+#[test]
+fn test_render_waveform_3band_detail_buffer_size() -> anyhow::Result<()> {
+    let mut files = common::setup_anlz_files()?;
+    let detail = files.ex2.get_waveform_3band_detail().expect("missing 3-band detail");
+    let buffer = render::render_waveform_3band_detail(
+        &detail.data,
+        400,
+        80,
+        (0, 0, 139),
+        (255, 165, 0),
+        (255, 255, 255),
+    );
+    assert_eq!(buffer.len(), 400 * 80 * 4);
+    Ok(())
+}
+
+#[test]
+fn test_write_png_roundtrip() -> anyhow::Result<()> {
+    let mut files = common::setup_anlz_files()?;
+    let detail = files.ext.get_waveform_color_detail().expect("missing color detail");
+    let buffer = render::render_waveform_color_detail(&detail.data, 200, 40);
+
+    let out_file = common::testdata_anlz_dir()?.join("waveform-test.png");
+    render::write_png(&out_file, 200, 40, &buffer)?;
+
+    let contents = std::fs::read(&out_file)?;
+    assert_eq!(&contents[..8], &[0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A]);
+    std::fs::remove_file(out_file)?;
+    Ok(())
+}
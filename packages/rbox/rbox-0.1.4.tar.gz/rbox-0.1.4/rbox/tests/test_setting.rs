@@ -1386,3 +1386,393 @@ fn test_djmdmysetting_defaults() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+// -- Setting::new / SettingBuilder -----------------------------------------------------------
+
+#[test]
+fn test_setting_new_matches_file_constructor() -> anyhow::Result<()> {
+    let from_kind = Setting::new(SettingKind::MySetting);
+    let from_path = Setting::new_mysetting("MYSETTING.DAT")?;
+    assert_eq!(from_kind.data, from_path.data);
+
+    let from_kind = Setting::new(SettingKind::MySetting2);
+    let from_path = Setting::new_mysetting2("MYSETTING2.DAT")?;
+    assert_eq!(from_kind.data, from_path.data);
+
+    let from_kind = Setting::new(SettingKind::DJMMySetting);
+    let from_path = Setting::new_djmmysetting("DJMMYSETTING.DAT")?;
+    assert_eq!(from_kind.data, from_path.data);
+
+    let from_kind = Setting::new(SettingKind::DevSetting);
+    let from_path = Setting::new_devsetting("DEVSETTING.DAT")?;
+    assert_eq!(from_kind.data, from_path.data);
+
+    Ok(())
+}
+
+#[test]
+fn test_setting_builder_fluent_chain() -> anyhow::Result<()> {
+    let sett = Setting::builder(SettingKind::MySetting)
+        .auto_cue(AutoCue::On)
+        .jog_mode(JogMode::CDJ)
+        .language(Language::English)
+        .build();
+
+    assert_eq!(sett.get_auto_cue()?, AutoCue::On);
+    assert_eq!(sett.get_jog_mode()?, JogMode::CDJ);
+    assert_eq!(sett.get_language()?, Language::English);
+    // Fields untouched by the builder keep their default value.
+    assert_eq!(sett.get_quantize()?, Quantize::default());
+
+    Ok(())
+}
+
+#[test]
+#[should_panic(expected = "is not valid for this SettingBuilder's SettingKind")]
+fn test_setting_builder_rejects_mismatched_kind() {
+    let _ = Setting::builder(SettingKind::DevSetting).auto_cue(AutoCue::On);
+}
+
+// -- Text document serialization -------------------------------------------------------------
+
+#[test]
+fn test_setting_document_round_trip() -> anyhow::Result<()> {
+    let sett = Setting::builder(SettingKind::DJMMySetting)
+        .channel_fader_curve(ChannelFaderCurve::SteepBottom)
+        .talk_over_mode(TalkOverMode::Normal)
+        .midi_channel(MidiChannel::Two)
+        .build();
+
+    let doc = sett.to_document()?;
+    assert_eq!(doc.kind, SettingKind::DJMMySetting);
+    assert_eq!(doc.fields.get("channel_fader_curve").map(String::as_str), Some("SteepBottom"));
+    assert_eq!(doc.fields.get("talk_over_mode").map(String::as_str), Some("Normal"));
+
+    let restored = Setting::from_document(&doc)?;
+    assert_eq!(restored.data, sett.data);
+
+    Ok(())
+}
+
+#[test]
+fn test_setting_json_round_trip() -> anyhow::Result<()> {
+    let sett = Setting::builder(SettingKind::MySetting)
+        .auto_cue(AutoCue::On)
+        .jog_mode(JogMode::Vinyl)
+        .build();
+
+    let json = sett.to_json()?;
+    let restored = Setting::from_json(&json)?;
+    assert_eq!(restored.data, sett.data);
+
+    Ok(())
+}
+
+#[test]
+fn test_setting_toml_round_trip() -> anyhow::Result<()> {
+    let sett = Setting::builder(SettingKind::MySetting)
+        .auto_cue(AutoCue::On)
+        .jog_mode(JogMode::Vinyl)
+        .build();
+
+    let toml = sett.to_toml()?;
+    assert!(toml.contains("auto_cue = \"On\""));
+
+    let restored = Setting::from_toml(&toml)?;
+    assert_eq!(restored.data, sett.data);
+
+    Ok(())
+}
+
+#[test]
+fn test_setting_from_document_rejects_unknown_value() {
+    let mut fields = std::collections::BTreeMap::new();
+    fields.insert("auto_cue".to_string(), "Sideways".to_string());
+    let doc = SettingDocument {
+        kind: SettingKind::MySetting,
+        fields,
+    };
+
+    assert!(Setting::from_document(&doc).is_err());
+}
+
+// -- Generic field reflection -----------------------------------------------------------------
+
+#[test]
+fn test_setting_get_set_by_field() -> anyhow::Result<()> {
+    let mut sett = Setting::new(SettingKind::MySetting);
+
+    sett.set(SettingField::AutoCue, SettingValue::AutoCue(AutoCue::On))?;
+    assert_eq!(sett.get(SettingField::AutoCue)?, SettingValue::AutoCue(AutoCue::On));
+    assert_eq!(sett.get_auto_cue()?, AutoCue::On);
+
+    sett.set(SettingField::JogMode, SettingValue::JogMode(JogMode::CDJ))?;
+    assert_eq!(sett.get_jog_mode()?, JogMode::CDJ);
+
+    Ok(())
+}
+
+#[test]
+fn test_setting_set_rejects_mismatched_field_value() {
+    let mut sett = Setting::new(SettingKind::MySetting);
+    let err = sett.set(SettingField::AutoCue, SettingValue::JogMode(JogMode::CDJ));
+    assert!(err.is_err());
+}
+
+#[test]
+fn test_setting_get_rejects_field_of_other_kind() {
+    let sett = Setting::new(SettingKind::MySetting);
+    assert!(sett.get(SettingField::MidiChannel).is_err());
+}
+
+#[test]
+fn test_setting_fields_lists_only_fields_of_this_kind() -> anyhow::Result<()> {
+    let sett = Setting::new(SettingKind::DevSetting);
+    let fields = sett.fields()?;
+
+    assert_eq!(fields.len(), 4);
+    assert!(fields.iter().any(|(f, _)| *f == SettingField::WaveformColor));
+    assert!(!fields.iter().any(|(f, _)| *f == SettingField::AutoCue));
+
+    Ok(())
+}
+
+// -- Diff and merge ---------------------------------------------------------------------------
+
+#[test]
+fn test_setting_diff_reports_changed_fields_only() -> anyhow::Result<()> {
+    let a = Setting::builder(SettingKind::MySetting)
+        .auto_cue(AutoCue::On)
+        .jog_mode(JogMode::Vinyl)
+        .build();
+    let b = Setting::builder(SettingKind::MySetting)
+        .auto_cue(AutoCue::Off)
+        .jog_mode(JogMode::Vinyl)
+        .build();
+
+    let changes = a.diff(&b)?;
+    assert_eq!(changes.len(), 1);
+    assert_eq!(changes[0].field, SettingField::AutoCue);
+    assert_eq!(changes[0].old, SettingValue::AutoCue(AutoCue::On));
+    assert_eq!(changes[0].new, SettingValue::AutoCue(AutoCue::Off));
+
+    Ok(())
+}
+
+#[test]
+fn test_setting_apply_merges_selected_fields() -> anyhow::Result<()> {
+    let a = Setting::builder(SettingKind::MySetting)
+        .auto_cue(AutoCue::On)
+        .jog_mode(JogMode::Vinyl)
+        .build();
+    let b = Setting::builder(SettingKind::MySetting)
+        .auto_cue(AutoCue::Off)
+        .jog_mode(JogMode::CDJ)
+        .build();
+
+    let changes = a.diff(&b)?;
+    let mut merged = a;
+    // Only merge the `auto_cue` change, leaving `jog_mode` untouched.
+    let auto_cue_only: Vec<_> = changes.into_iter().filter(|c| c.field == SettingField::AutoCue).collect();
+    merged.apply(&auto_cue_only)?;
+
+    assert_eq!(merged.get_auto_cue()?, AutoCue::Off);
+    assert_eq!(merged.get_jog_mode()?, JogMode::Vinyl);
+
+    Ok(())
+}
+
+#[test]
+fn test_setting_diff_rejects_mismatched_kinds() {
+    let a = Setting::new(SettingKind::MySetting);
+    let b = Setting::new(SettingKind::DevSetting);
+    assert!(a.diff(&b).is_err());
+}
+
+#[test]
+fn test_setting_merge_carries_only_changed_fields_from_base_to_theirs() -> anyhow::Result<()> {
+    let base = Setting::builder(SettingKind::DJMMySetting)
+        .crossfader_curve(CrossfaderCurve::ConstantPower)
+        .talk_over_level(TalkOverLevel::Minus18dB)
+        .build();
+    let theirs = Setting::builder(SettingKind::DJMMySetting)
+        .crossfader_curve(CrossfaderCurve::FastCut)
+        .talk_over_level(TalkOverLevel::Minus6dB)
+        .build();
+    let mut target = Setting::builder(SettingKind::DJMMySetting)
+        .mic_low_cut(MicLowCut::On)
+        .build();
+
+    let changes = target.merge(&base, &theirs)?;
+    assert_eq!(changes.len(), 2);
+
+    assert_eq!(target.get_crossfader_curve()?, CrossfaderCurve::FastCut);
+    assert_eq!(target.get_talk_over_level()?, TalkOverLevel::Minus6dB);
+    // A setting that differed from base/theirs's defaults, but wasn't changed between them,
+    // is left untouched.
+    assert_eq!(target.get_mic_low_cut()?, MicLowCut::On);
+
+    Ok(())
+}
+
+#[test]
+fn test_setting_merge_rejects_mismatched_kinds() {
+    let base = Setting::new(SettingKind::MySetting);
+    let theirs = Setting::new(SettingKind::MySetting);
+    let mut target = Setting::new(SettingKind::DevSetting);
+    assert!(target.merge(&base, &theirs).is_err());
+}
+
+#[test]
+fn test_setting_diff_against_factory_default_flags_drift() -> anyhow::Result<()> {
+    // The same `Setting::diff`/`Setting::apply` pair added for bulk-cloning a preferred
+    // configuration across USB sticks also works for flagging fields that drifted from the
+    // factory default produced by `new_mysetting`.
+    let default = Setting::new_mysetting("MYSETTING.DAT")?;
+    let configured = Setting::builder(SettingKind::MySetting)
+        .auto_cue(AutoCue::Off)
+        .build();
+
+    let drift = default.diff(&configured)?;
+    assert_eq!(drift.len(), 1);
+    assert_eq!(drift[0].field, SettingField::AutoCue);
+    assert_eq!(drift[0].new, SettingValue::AutoCue(AutoCue::Off));
+
+    Ok(())
+}
+
+#[test]
+fn test_djmmysetting_defaults_match_via_diff() -> anyhow::Result<()> {
+    // `new_djmmysetting` and `test_djmdmysetting_defaults` above already assert every DJM field
+    // individually; `Setting::diff` (added for bulk config comparison) gives the same coverage
+    // in one call and will automatically pick up any field added to the reflection table later.
+    let root = common::testdata_settings_dir()?;
+    let sett = Setting::load(root.join("DJMMYSETTING.DAT"))?;
+    let default = Setting::new_djmmysetting("DJMMYSETTING.DAT")?;
+
+    assert!(default.diff(&sett)?.is_empty());
+
+    Ok(())
+}
+
+// -- DJM mixer profile -------------------------------------------------------------------------
+
+#[test]
+fn test_djm_profile_export_apply_round_trip() -> anyhow::Result<()> {
+    let configured = Setting::builder(SettingKind::DJMMySetting)
+        .talk_over_mode(TalkOverMode::Normal)
+        .midi_channel(MidiChannel::Five)
+        .build();
+
+    let profile = configured.export_profile()?;
+    assert_eq!(profile.talk_over_mode, TalkOverMode::Normal);
+    assert_eq!(profile.midi_channel, MidiChannel::Five);
+
+    let mut fresh = Setting::new(SettingKind::DJMMySetting);
+    fresh.apply_profile(&profile)?;
+    assert_eq!(fresh.data, configured.data);
+
+    Ok(())
+}
+
+#[test]
+fn test_djm_profile_serde_json_round_trip() -> anyhow::Result<()> {
+    let configured = Setting::builder(SettingKind::DJMMySetting)
+        .crossfader_curve(CrossfaderCurve::FastCut)
+        .build();
+    let profile = configured.export_profile()?;
+
+    let json = serde_json::to_string(&profile)?;
+    assert!(json.contains("\"crossfader_curve\":\"FastCut\""));
+
+    let restored: DjmMySettingProfile = serde_json::from_str(&json)?;
+    assert_eq!(restored, profile);
+
+    Ok(())
+}
+
+#[test]
+fn test_djmmysetting_defaults_match_via_profile() -> anyhow::Result<()> {
+    let root = common::testdata_settings_dir()?;
+    let sett = Setting::load(root.join("DJMMYSETTING.DAT"))?;
+    let default = Setting::new_djmmysetting("DJMMYSETTING.DAT")?;
+
+    assert_eq!(sett.export_profile()?, default.export_profile()?);
+
+    Ok(())
+}
+
+// -- Curve transfer functions -------------------------------------------------------------------
+
+#[test]
+fn test_crossfader_curve_gains_at_endpoints_and_center() {
+    let (left, right) = CrossfaderCurve::ConstantPower.gains_at(0.5);
+    assert!((left - right).abs() < 1e-6);
+    assert!((left * left + right * right - 1.0).abs() < 1e-6);
+
+    assert_eq!(CrossfaderCurve::SlowCut.gains_at(0.0), (1.0, 0.0));
+    assert_eq!(CrossfaderCurve::SlowCut.gains_at(1.0), (0.0, 1.0));
+
+    let (left, right) = CrossfaderCurve::FastCut.gains_at(0.5);
+    assert_eq!(left, 1.0);
+    assert_eq!(right, 1.0);
+}
+
+#[test]
+fn test_channel_fader_curve_gain_at_endpoints() {
+    for curve in [ChannelFaderCurve::Linear, ChannelFaderCurve::SteepBottom, ChannelFaderCurve::SteepTop]
+    {
+        assert_eq!(curve.gain_at(0.0), 0.0);
+        assert_eq!(curve.gain_at(1.0), 1.0);
+    }
+    assert_eq!(ChannelFaderCurve::Linear.gain_at(0.5), 0.5);
+    assert_eq!(ChannelFaderCurve::SteepBottom.gain_at(0.5), 0.25);
+}
+
+#[test]
+fn test_channel_fader_curve_long_fader_gain_at_endpoints() {
+    for curve in [
+        ChannelFaderCurveLongFader::Linear,
+        ChannelFaderCurveLongFader::Exponential,
+        ChannelFaderCurveLongFader::Smooth,
+    ] {
+        assert_eq!(curve.gain_at(0.0), 0.0);
+        assert_eq!(curve.gain_at(1.0), 1.0);
+    }
+    assert_eq!(ChannelFaderCurveLongFader::Smooth.gain_at(0.5), 0.5);
+}
+
+#[test]
+fn test_channel_fader_curve_to_db_clamps_at_silence() {
+    assert_eq!(ChannelFaderCurve::Linear.to_db(0.0), -60.0);
+    assert!((ChannelFaderCurve::Linear.to_db(1.0) - 0.0).abs() < 1e-4);
+}
+
+// -- Talk-over attenuation ------------------------------------------------------------------------
+
+#[test]
+fn test_talk_over_level_as_db_and_linear_gain() {
+    assert_eq!(TalkOverLevel::Minus6dB.as_db(), -6.0);
+    assert!((TalkOverLevel::Minus6dB.as_linear_gain() - 10f32.powf(-6.0 / 20.0)).abs() < 1e-6);
+    assert_eq!(TalkOverLevel::Minus24dB.as_db(), -24.0);
+}
+
+#[test]
+fn test_talk_over_mode_is_per_channel() {
+    assert!(TalkOverMode::Advanced.is_per_channel());
+    assert!(!TalkOverMode::Normal.is_per_channel());
+}
+
+#[test]
+fn test_setting_talk_over_attenuation() -> anyhow::Result<()> {
+    let sett = Setting::builder(SettingKind::DJMMySetting)
+        .talk_over_level(TalkOverLevel::Minus12dB)
+        .talk_over_mode(TalkOverMode::Normal)
+        .build();
+
+    let (db, mode) = sett.talk_over_attenuation()?;
+    assert_eq!(db, -12.0);
+    assert_eq!(mode, TalkOverMode::Normal);
+
+    Ok(())
+}
@@ -0,0 +1,90 @@
+// Author: Dylan Jones
+// Date:   2026-07-30
+
+use rbox::settings::{
+    AutoCue, ChannelFaderCurve, JogMode, MidiButtonType, MidiChannel, Setting, SettingField,
+    SettingKind,
+};
+use rbox::sysex;
+
+#[test]
+fn test_encode_decode_round_trip() -> anyhow::Result<()> {
+    let sett = Setting::builder(SettingKind::MySetting)
+        .auto_cue(AutoCue::Off)
+        .jog_mode(JogMode::CDJ)
+        .build();
+
+    let message = sysex::encode(&sett, 0x00, &[SettingField::AutoCue, SettingField::JogMode])?;
+    assert_eq!(message[0], 0xF0);
+    assert_eq!(*message.last().unwrap(), 0xF7);
+    // Every payload byte (manufacturer id, device id, kind id, field ids, nibbles, checksum)
+    // must be 7-bit safe.
+    for &byte in &message[1..message.len() - 1] {
+        assert!(byte < 0x80, "byte {byte:#04x} is not 7-bit safe");
+    }
+
+    let decoded = sysex::decode(&message)?;
+    assert_eq!(decoded.get_auto_cue()?, AutoCue::Off);
+    assert_eq!(decoded.get_jog_mode()?, JogMode::CDJ);
+
+    Ok(())
+}
+
+#[test]
+fn test_decode_rejects_bad_checksum() -> anyhow::Result<()> {
+    let sett = Setting::new(SettingKind::MySetting);
+    let mut message = sysex::encode(&sett, 0x00, &[SettingField::AutoCue])?;
+    let checksum_index = message.len() - 2;
+    message[checksum_index] ^= 0x7F;
+
+    assert!(sysex::decode(&message).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_decode_rejects_wrong_manufacturer_id() -> anyhow::Result<()> {
+    let sett = Setting::new(SettingKind::MySetting);
+    let mut message = sysex::encode(&sett, 0x00, &[SettingField::AutoCue])?;
+    message[1] = 0x42;
+    let body_end = message.len() - 2;
+    message[body_end] = {
+        let body = &message[1..body_end];
+        body.iter().fold(0u8, |acc, b| acc.wrapping_add(*b)) & 0x7F
+    };
+
+    assert!(sysex::decode(&message).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_encode_on_configured_channel_uses_midi_channel_field() -> anyhow::Result<()> {
+    let sett = Setting::builder(SettingKind::DJMMySetting)
+        .midi_channel(MidiChannel::Three)
+        .channel_fader_curve(ChannelFaderCurve::SteepBottom)
+        .build();
+
+    assert_eq!(sysex::midi_channel_of(&sett), Some(2));
+
+    let message =
+        sysex::encode_on_configured_channel(&sett, 0x00, &[SettingField::ChannelFaderCurve])?;
+    assert_eq!(message[2], 2);
+
+    let decoded = sysex::decode(&message)?;
+    assert_eq!(decoded.get_channel_fader_curve()?, ChannelFaderCurve::SteepBottom);
+
+    Ok(())
+}
+
+#[test]
+fn test_uses_toggle_semantics_reflects_midi_button_type() {
+    let toggle = Setting::builder(SettingKind::DJMMySetting)
+        .midi_button_type(MidiButtonType::Toggle)
+        .build();
+    let trigger = Setting::builder(SettingKind::DJMMySetting)
+        .midi_button_type(MidiButtonType::Trigger)
+        .build();
+
+    assert_eq!(sysex::uses_toggle_semantics(&toggle), Some(true));
+    assert_eq!(sysex::uses_toggle_semantics(&trigger), Some(false));
+    assert_eq!(sysex::uses_toggle_semantics(&Setting::new(SettingKind::MySetting)), None);
+}
@@ -1,4 +1,13 @@
+use std::alloc::System;
+use std::collections::VecDeque;
+use std::time::Instant;
+
+use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
+use stats_alloc::{INSTRUMENTED_SYSTEM, Region, StatsAlloc};
+
+#[global_allocator]
+static GLOBAL: &StatsAlloc<System> = &INSTRUMENTED_SYSTEM;
 
 mod features;
 mod parallel;
@@ -28,22 +37,166 @@ use features::sp_summaries::{sp_summaries_welch_rect_area_5_1, sp_summaries_welc
 use parallel::{compute_catch22_parallel, extract_catch22_features_cumulative_optimized};
 
 #[pyclass]
+#[derive(Debug, Clone)]
 pub struct Catch22Result {
     #[pyo3(get)]
     pub names: Vec<String>,
     #[pyo3(get)]
     pub values: Vec<f64>,
+    /// Per-feature `(name, seconds, bytes_allocated)`, populated only when the
+    /// caller opted into diagnostics.
+    #[pyo3(get)]
+    pub timings: Option<Vec<(String, f64, u64)>>,
 }
 
 #[pyfunction]
 #[pyo3(signature = (y, normalize=None, catch24=None))]
 fn py_catch22_all(y: Vec<f64>, normalize: Option<bool>, catch24: Option<bool>) -> Catch22Result {
-    
+
     let result = compute_catch22_parallel(y, normalize.unwrap_or(true), catch24.unwrap_or(false));
 
     Catch22Result {
         names: result.names,
         values: result.values,
+        timings: None,
+    }
+}
+
+/// Measures a single feature call's wall-clock time and the total bytes it
+/// allocated under the instrumented global allocator (frees are not subtracted).
+macro_rules! timed_feature {
+    ($timings:expr, $name:expr, $call:expr) => {{
+        let region = Region::new(GLOBAL);
+        let started = Instant::now();
+        let value = $call;
+        let elapsed = started.elapsed().as_secs_f64();
+        let bytes_allocated = region.change().bytes_allocated as u64;
+        $timings.push(($name.to_string(), elapsed, bytes_allocated));
+        value
+    }};
+}
+
+/// Same feature set as [`py_catch22_all`], but runs each feature sequentially
+/// (rather than through the parallel path) under an instrumented allocator so
+/// per-feature wall-clock time and allocation counts can be reported. This is
+/// meant for profiling, not for production extraction: measuring allocations
+/// reliably across the rayon-backed parallel workers of
+/// `compute_catch22_parallel` isn't practical, since the global allocator's
+/// counters are shared across threads, so the diagnostics path trades
+/// parallelism for an accurate, feature-by-feature breakdown.
+#[pyfunction]
+#[pyo3(signature = (y, normalize=None, catch24=None))]
+fn py_catch22_all_with_diagnostics(
+    y: Vec<f64>,
+    normalize: Option<bool>,
+    catch24: Option<bool>,
+) -> Catch22Result {
+    let use_normalization = normalize.unwrap_or(true);
+    let catch24 = catch24.unwrap_or(false);
+
+    let mut names = Vec::new();
+    let mut values = Vec::new();
+    let mut timings = Vec::new();
+
+    macro_rules! feature {
+        ($name:expr, $call:expr) => {{
+            let value = timed_feature!(timings, $name, $call);
+            names.push($name.to_string());
+            values.push(value as f64);
+        }};
+    }
+
+    feature!("DN_Mean", dn_mean(&y));
+    feature!("DN_Spread_Std", dn_spread_std(&y));
+    feature!("CO_Trev_1_Num", co_trev_1_num(&y, use_normalization));
+    feature!("CO_f1ecac", co_f1ecac(&y, use_normalization));
+    feature!("CO_FirstMin_ac", co_first_min_ac(&y, use_normalization));
+    feature!(
+        "CO_HistogramAMI_even_2_5",
+        co_histogramami_even_2_5(&y, use_normalization)
+    );
+    feature!(
+        "DN_HistogramMode_5",
+        dn_histogrammode_5(&y, use_normalization)
+    );
+    feature!(
+        "DN_HistogramMode_10",
+        dn_histogrammode_10(&y, use_normalization)
+    );
+    feature!(
+        "MD_hrv_classic_pnn40",
+        md_hrv_classic_pnn40(&y, use_normalization)
+    );
+    feature!(
+        "SB_BinaryStats_diff_longstretch0",
+        bin_binarystats_diff_longsstretch0(&y, use_normalization)
+    );
+    feature!(
+        "SB_BinaryStats_mean_longstretch1",
+        bin_binarystats_mean_longstretch1(&y, use_normalization)
+    );
+    feature!(
+        "SB_TransitionMatrix_3ac_sumdiagcov",
+        sb_transitionmatrix_3ac_sumdiagcov(&y, use_normalization)
+    );
+    feature!(
+        "PD_PeriodicityWang_th0_01",
+        pd_periodicitywang(&y, use_normalization)
+    );
+    feature!(
+        "CO_Embed2_Dist_tau_d_expfit_meandiff",
+        co_embed2_dist_tau_d_expfit_meandiff(&y, use_normalization)
+    );
+    feature!(
+        "IN_AutoMutualInfoStats_40_gaussian_fmmi",
+        in_automutualinfostats_40_gaussian_fmmi(&y, use_normalization)
+    );
+    feature!(
+        "FC_LocalSimple_mean1_tauresrat",
+        fc_localsimple_mean1_tauresrat(&y, use_normalization)
+    );
+    feature!(
+        "FC_LocalSimple_mean3_stderr",
+        fc_localsimple_mean3_stderr(&y, use_normalization)
+    );
+    feature!(
+        "DN_OutlierInclude_p_001_mdrmd",
+        dn_outlierinclude_p_001_mdrmd(&y, use_normalization)
+    );
+    feature!(
+        "DN_OutlierInclude_n_001_mdrmd",
+        dn_outlierinclude_n_001_mdrmd(&y, use_normalization)
+    );
+    feature!(
+        "SP_Summaries_welch_rect_area_5_1",
+        sp_summaries_welch_rect_area_5_1(&y, use_normalization)
+    );
+    feature!(
+        "SP_Summaries_welch_rect_centroid",
+        sp_summaries_welch_rect_centroid(&y, use_normalization)
+    );
+    feature!(
+        "SB_MotifThree_quantile_hh",
+        sb_motifthree_quantile_hh(&y, use_normalization)
+    );
+    feature!(
+        "SC_FluctAnal_2_dfa_50_1_2_logi_prop_r1",
+        sc_fluctanal_2_dfa_50_1_2_logi_prop_r1(&y, 2, "dfa", use_normalization)
+    );
+    feature!(
+        "SC_FluctAnal_2_rsrangefit_50_1_2_logi_prop_r1",
+        sc_fluctanal_2_rsrangefit_50_1_2_logi_prop_r1(&y, 1, "rsrangefit", use_normalization)
+    );
+
+    if catch24 {
+        feature!("DN_Mean_catch24", dn_mean(&y));
+        feature!("DN_Spread_Std_catch24", dn_spread_std(&y));
+    }
+
+    Catch22Result {
+        names,
+        values,
+        timings: Some(timings),
     }
 }
 
@@ -262,11 +415,125 @@ fn py_extract_catch22_features_cumulative(
     }
 }
 
+// Windowed (fixed-width, sliding) counterpart to the cumulative extractor,
+// for pipelines that consume a continuous feed rather than a single batch.
+#[pyfunction]
+#[pyo3(signature = (series, window, step, normalize=None, catch24=None))]
+fn py_extract_catch22_features_windowed(
+    series: Vec<f64>,
+    window: usize,
+    step: usize,
+    normalize: Option<bool>,
+    catch24: Option<bool>,
+) -> PyResult<CumulativeFeatures> {
+    if window == 0 || step == 0 {
+        return Err(PyValueError::new_err("window and step must both be positive"));
+    }
+
+    let normalize = normalize.unwrap_or(true);
+    let catch24 = catch24.unwrap_or(false);
+
+    let mut feature_names = Vec::new();
+    let mut values = Vec::new();
+
+    let mut start = 0;
+    while start + window <= series.len() {
+        let result =
+            compute_catch22_parallel(series[start..start + window].to_vec(), normalize, catch24);
+
+        if feature_names.is_empty() {
+            feature_names = result.names;
+        }
+        values.push(result.values);
+
+        start += step;
+    }
+
+    Ok(CumulativeFeatures {
+        feature_names,
+        values,
+    })
+}
+
+/// Stateful catch22/24 extractor for streaming data.
+///
+/// Maintains a ring buffer of the last `window` samples. Every `push` drops
+/// the oldest sample once the buffer is full, and once `step` new samples
+/// have accumulated over a full window the features are recomputed; `snapshot`
+/// then returns the features for the most recently closed window without
+/// re-feeding the whole history.
+#[pyclass]
+pub struct Catch22Stream {
+    window: usize,
+    step: usize,
+    normalize: bool,
+    catch24: bool,
+    buffer: VecDeque<f64>,
+    since_last_compute: usize,
+    cached: Option<Catch22Result>,
+}
+
+#[pymethods]
+impl Catch22Stream {
+    #[new]
+    #[pyo3(signature = (window, step, normalize=None, catch24=None))]
+    fn new(
+        window: usize,
+        step: usize,
+        normalize: Option<bool>,
+        catch24: Option<bool>,
+    ) -> PyResult<Self> {
+        if window == 0 || step == 0 {
+            return Err(PyValueError::new_err("window and step must both be positive"));
+        }
+
+        Ok(Self {
+            window,
+            step,
+            normalize: normalize.unwrap_or(true),
+            catch24: catch24.unwrap_or(false),
+            buffer: VecDeque::with_capacity(window),
+            since_last_compute: 0,
+            cached: None,
+        })
+    }
+
+    /// Appends a new sample to the window, recomputing features once the
+    /// window is full and `step` samples have accumulated since the last
+    /// computation.
+    fn push(&mut self, value: f64) {
+        if self.buffer.len() == self.window {
+            self.buffer.pop_front();
+        }
+        self.buffer.push_back(value);
+        self.since_last_compute += 1;
+
+        if self.buffer.len() == self.window && self.since_last_compute >= self.step {
+            let series: Vec<f64> = self.buffer.iter().copied().collect();
+            let result = compute_catch22_parallel(series, self.normalize, self.catch24);
+            self.cached = Some(Catch22Result {
+                names: result.names,
+                values: result.values,
+                timings: None,
+            });
+            self.since_last_compute = 0;
+        }
+    }
+
+    /// Returns the features computed for the most recently closed window, or
+    /// `None` if no window has closed yet.
+    fn snapshot(&self) -> Option<Catch22Result> {
+        self.cached.clone()
+    }
+}
+
 #[pymodule]
 fn rs_catch_22(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<Catch22Result>()?;
     m.add_class::<CumulativeFeatures>()?;
+    m.add_class::<Catch22Stream>()?;
     m.add_function(wrap_pyfunction!(py_catch22_all, m)?)?;
+    m.add_function(wrap_pyfunction!(py_catch22_all_with_diagnostics, m)?)?;
     m.add_function(wrap_pyfunction!(py_co_trev_1_num, m)?)?;
     m.add_function(wrap_pyfunction!(py_dn_histogrammode_10, m)?)?;
     m.add_function(wrap_pyfunction!(py_dn_histogrammode_5, m)?)?;
@@ -304,5 +571,6 @@ fn rs_catch_22(m: &Bound<'_, PyModule>) -> PyResult<()> {
         m
     )?)?;
     m.add_function(wrap_pyfunction!(py_extract_catch22_features_cumulative, m)?)?;
+    m.add_function(wrap_pyfunction!(py_extract_catch22_features_windowed, m)?)?;
     Ok(())
 }
\ No newline at end of file
@@ -11,11 +11,12 @@ use pyo3::{pyclass, pymethods, PyResult, Python};
 use regex::Regex;
 use tokenizers::decoders::fuse::Fuse;
 use tokenizers::models::wordlevel::WordLevel;
+use tokenizers::processors::template::TemplateProcessing;
 use tokenizers::{self, normalizers, DecoderWrapper, Model, NormalizerWrapper};
 use tokenizers::{
     AddedToken, EncodeInput, OffsetReferential, OffsetType, PaddingDirection, PaddingParams,
     PaddingStrategy, PostProcessorWrapper, PreTokenizedString, PreTokenizer, TokenizerBuilder,
-    TokenizerImpl,
+    TokenizerImpl, TruncationDirection, TruncationParams, TruncationStrategy,
 };
 
 type Tokenizer = TokenizerImpl<
@@ -245,6 +246,88 @@ impl SmirkTokenizer {
         Ok(())
     }
 
+    fn no_truncation(&mut self) {
+        self.tokenizer.with_truncation(None).unwrap();
+    }
+
+    #[pyo3(signature = (**kwargs))]
+    fn with_truncation(&mut self, kwargs: Option<&PyDict>) -> PyResult<()> {
+        let mut params = TruncationParams::default();
+        if let Some(kwargs) = kwargs {
+            for (key, value) in kwargs {
+                let key: &str = key.extract().unwrap();
+                match key {
+                    "max_length" => params.max_length = value.extract().unwrap(),
+                    "stride" => params.stride = value.extract().unwrap(),
+                    "strategy" => {
+                        let value: &str = value.extract().unwrap();
+                        params.strategy = match value {
+                            "longest_first" => Ok(TruncationStrategy::LongestFirst),
+                            "only_first" => Ok(TruncationStrategy::OnlyFirst),
+                            "only_second" => Ok(TruncationStrategy::OnlySecond),
+                            other => Err(PyValueError::new_err(format!(
+                                "Unknown strategy {}",
+                                other
+                            ))),
+                        }?
+                    }
+                    "direction" => {
+                        let value: &str = value.extract().unwrap();
+                        params.direction = match value {
+                            "left" => Ok(TruncationDirection::Left),
+                            "right" => Ok(TruncationDirection::Right),
+                            other => Err(PyValueError::new_err(format!(
+                                "Unknown direction {}",
+                                other
+                            ))),
+                        }?
+                    }
+                    _ => println!("Unknown kwargs {}, ignoring", key),
+                }
+            }
+        }
+        self.tokenizer
+            .with_truncation(Some(params))
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+        Ok(())
+    }
+
+    fn resolve_special_token_id(&self, token: &str) -> PyResult<u32> {
+        self.tokenizer
+            .get_vocab(true)
+            .get(token)
+            .copied()
+            .ok_or_else(|| PyValueError::new_err(format!("Unknown special token {:?}", token)))
+    }
+
+    #[pyo3(signature = (single, pair, special_tokens))]
+    fn with_post_processor(
+        &mut self,
+        single: &str,
+        pair: &str,
+        special_tokens: Vec<String>,
+    ) -> PyResult<()> {
+        let resolved: Vec<(String, u32)> = special_tokens
+            .into_iter()
+            .map(|token| {
+                let id = self.resolve_special_token_id(&token)?;
+                Ok((token, id))
+            })
+            .collect::<PyResult<_>>()?;
+
+        let processor = TemplateProcessing::builder()
+            .try_single(single)
+            .map_err(|e| PyValueError::new_err(e.to_string()))?
+            .try_pair(pair)
+            .map_err(|e| PyValueError::new_err(e.to_string()))?
+            .special_tokens(resolved)
+            .build()
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+
+        self.tokenizer.with_post_processor(Some(processor.into()));
+        Ok(())
+    }
+
     fn add_tokens(&mut self, tokens: &PyList) -> PyResult<usize> {
         let tokens = tokens
             .into_iter()
@@ -311,6 +394,12 @@ impl SmirkTokenizer {
                     "split_structure" => {
                         opt_split_structure = value.extract().unwrap();
                     }
+                    // Forwarded to `GpeTrainerBuilder::show_progress`, which toggles the
+                    // progress bar GpeTrainer draws around its word-counting and merge
+                    // loops; defaults to `true` there when not overridden here.
+                    "show_progress" => {
+                        builder.show_progress(value.extract().unwrap());
+                    }
                     _ => println!("Unknown parameter {:?} ignoring", key),
                 }
             }
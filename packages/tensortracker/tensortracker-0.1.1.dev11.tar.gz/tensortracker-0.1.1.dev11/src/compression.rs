@@ -1,6 +1,176 @@
 use safetensors::Dtype;
 use std::io;
-use zstd::bulk::{Compressor, Decompressor};
+use zstd::bulk::{Compressor as ZstdBulkCompressor, Decompressor as ZstdBulkDecompressor};
+
+/// A pluggable byte-level compression codec, identified by a stable one-byte
+/// [`Compressor::ID`] that [`evaluate_payload_candidates`] prepends to every
+/// payload it stores with that codec.
+///
+/// [`evaluate_payload_candidates`] tries [`RawCodec`], [`ZstdCodec`] and
+/// [`Lz4Codec`] for each candidate ([`ZstdDictCodec`] only for the
+/// "residual" candidate, when a dictionary is given) and keeps the smallest
+/// result; [`decompress_by_id`] reads
+/// the leading ID byte back off a stored payload and dispatches to the
+/// matching codec, so reconstruction never has to parse a string label to
+/// know how a payload was stored.
+pub trait Compressor {
+    /// Stable on-disk identifier for this codec.
+    const ID: u8;
+
+    fn compress(&self, data: &[u8]) -> io::Result<Vec<u8>>;
+    fn decompress(&self, data: &[u8], original_size: usize) -> io::Result<Vec<u8>>;
+}
+
+/// Stores the payload verbatim. Wins when no other codec shrinks it.
+pub struct RawCodec;
+
+impl Compressor for RawCodec {
+    const ID: u8 = 0;
+
+    fn compress(&self, data: &[u8]) -> io::Result<Vec<u8>> {
+        Ok(data.to_vec())
+    }
+
+    fn decompress(&self, data: &[u8], _original_size: usize) -> io::Result<Vec<u8>> {
+        Ok(data.to_vec())
+    }
+}
+
+/// Zstd compression at a configurable level.
+pub struct ZstdCodec {
+    pub level: i32,
+}
+
+impl Compressor for ZstdCodec {
+    const ID: u8 = 1;
+
+    fn compress(&self, data: &[u8]) -> io::Result<Vec<u8>> {
+        let mut compressor = ZstdBulkCompressor::new(self.level)?;
+        compressor.compress(data).map_err(io::Error::other)
+    }
+
+    fn decompress(&self, data: &[u8], original_size: usize) -> io::Result<Vec<u8>> {
+        let mut decompressor = ZstdBulkDecompressor::new()?;
+        decompressor
+            .decompress(data, original_size)
+            .map_err(io::Error::other)
+    }
+}
+
+/// LZ4 block compression. The uncompressed size is prepended by the codec
+/// itself, so decompression never needs to guess a buffer size.
+pub struct Lz4Codec;
+
+impl Compressor for Lz4Codec {
+    const ID: u8 = 2;
+
+    fn compress(&self, data: &[u8]) -> io::Result<Vec<u8>> {
+        Ok(lz4_flex::block::compress_prepend_size(data))
+    }
+
+    fn decompress(&self, data: &[u8], _original_size: usize) -> io::Result<Vec<u8>> {
+        lz4_flex::block::decompress_size_prepended(data)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+}
+
+/// Zstd compression against a shared dictionary, for payloads that are
+/// expected to be similar across many tensors (e.g. residuals between
+/// checkpoints of the same model). The dictionary itself is stored
+/// verbatim in the payload (length-prefixed, ahead of the compressed
+/// body) rather than behind a process-local id, so a patch written with
+/// this codec stays decodable after the writing process exits, from a
+/// different process, or by a different dictionary-training run
+/// entirely.
+pub struct ZstdDictCodec {
+    pub level: i32,
+    pub dictionary: Vec<u8>,
+}
+
+impl Compressor for ZstdDictCodec {
+    const ID: u8 = 3;
+
+    fn compress(&self, data: &[u8]) -> io::Result<Vec<u8>> {
+        let compressed = compress_data_with_dictionary(data, self.level, &self.dictionary)?;
+        let mut out = Vec::with_capacity(4 + self.dictionary.len() + compressed.len());
+        out.extend_from_slice(&(self.dictionary.len() as u32).to_le_bytes());
+        out.extend_from_slice(&self.dictionary);
+        out.extend_from_slice(&compressed);
+        Ok(out)
+    }
+
+    fn decompress(&self, data: &[u8], original_size: usize) -> io::Result<Vec<u8>> {
+        if data.len() < 4 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "missing dictionary length",
+            ));
+        }
+        let (len_bytes, rest) = data.split_at(4);
+        let dict_len = u32::from_le_bytes(len_bytes.try_into().unwrap()) as usize;
+        if rest.len() < dict_len {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "truncated residual dictionary",
+            ));
+        }
+        let (dictionary, body) = rest.split_at(dict_len);
+        decompress_data_with_dictionary(body, original_size, dictionary)
+    }
+}
+
+/// Trains a zstd dictionary from sample residual payloads (e.g. residuals
+/// collected across many near-identical checkpoints of the same model),
+/// so later residuals from the same family can compress against shared
+/// structure instead of starting from scratch each time.
+pub fn train_residual_dictionary(samples: &[Vec<u8>], dict_size: usize) -> io::Result<Vec<u8>> {
+    zstd::dict::from_samples(samples, dict_size)
+}
+
+/// Compresses data against a shared dictionary instead of zstd's default,
+/// empty dictionary. See [`ZstdDictCodec`].
+pub fn compress_data_with_dictionary(
+    data: &[u8],
+    level: i32,
+    dictionary: &[u8],
+) -> io::Result<Vec<u8>> {
+    let mut compressor = ZstdBulkCompressor::with_dictionary(level, dictionary)?;
+    compressor.compress(data).map_err(io::Error::other)
+}
+
+/// Decompresses data that was compressed with [`compress_data_with_dictionary`].
+pub fn decompress_data_with_dictionary(
+    compressed: &[u8],
+    original_size: usize,
+    dictionary: &[u8],
+) -> io::Result<Vec<u8>> {
+    let mut decompressor = ZstdBulkDecompressor::with_dictionary(dictionary)?;
+    decompressor
+        .decompress(compressed, original_size)
+        .map_err(io::Error::other)
+}
+
+/// Reads the codec-ID byte that [`evaluate_payload_candidates`] prepends to
+/// a stored payload and hands the remaining bytes to the matching codec.
+pub fn decompress_by_id(payload: &[u8], original_size: usize) -> io::Result<Vec<u8>> {
+    let (&id, body) = payload
+        .split_first()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "empty compressed payload"))?;
+    match id {
+        RawCodec::ID => RawCodec.decompress(body, original_size),
+        ZstdCodec::ID => ZstdCodec { level: 0 }.decompress(body, original_size),
+        Lz4Codec::ID => Lz4Codec.decompress(body, original_size),
+        ZstdDictCodec::ID => ZstdDictCodec {
+            level: 0,
+            dictionary: Vec::new(),
+        }
+        .decompress(body, original_size),
+        other => Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("unknown compression codec id {other}"),
+        )),
+    }
+}
 
 /// Compute element-wise residual (dest - origin) for f32/f64 tensors and
 /// return the residual bytes in the same element type (f32 or f64) in
@@ -70,15 +240,24 @@ pub fn quantize_residual_int8(residual_f32_bytes: &[u8]) -> Option<Vec<u8>> {
     Some(out)
 }
 
-/// Given candidate payloads (label, bytes), try raw and zstd-compressed
-/// forms for each candidate and pick the smallest final stored payload.
-/// Returns (method_label_opt, payload_bytes) where method_label_opt is None
-/// for an uncompressed raw 'raw' candidate, or Some(label) for any stored
-/// method (e.g. "zstd", "residual_zstd", "quant_res8").
+/// Given candidate payloads (label, bytes), try every registered codec
+/// ([`RawCodec`], [`ZstdCodec`], [`Lz4Codec`]) against each candidate and
+/// keep the smallest final stored payload. Returns (method_label_opt,
+/// payload_bytes) where method_label_opt is None for the literal
+/// uncompressed 'raw' candidate (stored with no framing at all), or
+/// Some(label) otherwise (e.g. "residual", "quant_res8"), with the winning
+/// codec's [`Compressor::ID`] byte prepended to the payload so
+/// [`decompress_by_id`] can reconstruct it.
+///
+/// When `residual_dictionary` is given, the "residual" candidate also
+/// competes against itself compressed with [`ZstdDictCodec`] under the
+/// label "residual_zstd_dict", which wins on directories of many
+/// near-identical tensors that a dictionary-free codec can't exploit.
 pub fn evaluate_payload_candidates(
     candidates: Vec<(String, Vec<u8>)>,
     is_fp16: bool,
     allow_lossy: bool,
+    residual_dictionary: Option<&[u8]>,
 ) -> io::Result<(Option<String>, Vec<u8>)> {
     let mut best_label: Option<String> = None;
     let mut best_payload: Vec<u8> = Vec::new();
@@ -89,16 +268,12 @@ pub fn evaluate_payload_candidates(
         if !allow_lossy && label.starts_with("quant") {
             continue;
         }
-        // raw
+
+        // Literal raw bytes, with no codec framing at all. Represented as
+        // None when the label is the direct dest 'raw' candidate.
         if bytes.len() < best_size {
             best_size = bytes.len();
             best_payload = bytes.clone();
-            // raw payload represented as None when it corresponds to the
-            // destination full tensor; but label may be something like
-            // "raw" or "residual". We'll encode raw storage as Some(label)
-            // only if it's not the direct dest 'raw' candidate; callers can
-            // interpret labels accordingly. For simplicity, store label when
-            // it's not 'raw'.
             best_label = if label == "raw" {
                 None
             } else {
@@ -106,16 +281,41 @@ pub fn evaluate_payload_candidates(
             };
         }
 
-        // try zstd
-        match compress_data(&bytes, optimal_compression_level(bytes.len(), is_fp16)) {
-            Ok(comp) => {
-                if comp.len() < best_size {
-                    best_size = comp.len();
-                    best_payload = comp;
-                    best_label = Some(format!("{}_zstd", label));
-                }
+        let level = optimal_compression_level(bytes.len(), is_fp16);
+        let mut codec_results: Vec<(u8, Option<&str>, io::Result<Vec<u8>>)> = vec![
+            (RawCodec::ID, None, RawCodec.compress(&bytes)),
+            (ZstdCodec::ID, None, ZstdCodec { level }.compress(&bytes)),
+            (Lz4Codec::ID, None, Lz4Codec.compress(&bytes)),
+        ];
+        if label == "residual" {
+            if let Some(dictionary) = residual_dictionary {
+                let codec = ZstdDictCodec {
+                    level,
+                    dictionary: dictionary.to_vec(),
+                };
+                codec_results.push((
+                    ZstdDictCodec::ID,
+                    Some("residual_zstd_dict"),
+                    codec.compress(&bytes),
+                ));
+            }
+        }
+
+        for (codec_id, label_override, result) in codec_results {
+            let Ok(compressed) = result else { continue };
+            let stored_size = compressed.len() + 1;
+            if stored_size < best_size {
+                best_size = stored_size;
+                let mut framed = Vec::with_capacity(stored_size);
+                framed.push(codec_id);
+                framed.extend_from_slice(&compressed);
+                best_payload = framed;
+                best_label = Some(
+                    label_override
+                        .map(str::to_string)
+                        .unwrap_or_else(|| label.clone()),
+                );
             }
-            Err(_) => {}
         }
     }
 
@@ -124,16 +324,12 @@ pub fn evaluate_payload_candidates(
 
 /// Compresses data using zstd compression
 pub fn compress_data(data: &[u8], level: i32) -> io::Result<Vec<u8>> {
-    let mut compressor = Compressor::new(level)?;
-    compressor.compress(data).map_err(|e| io::Error::other(e))
+    ZstdCodec { level }.compress(data)
 }
 
 /// Decompresses zstd compressed data
 pub fn decompress_data(compressed: &[u8], original_size: usize) -> io::Result<Vec<u8>> {
-    let mut decompressor = Decompressor::new()?;
-    decompressor
-        .decompress(compressed, original_size)
-        .map_err(|e| io::Error::other(e))
+    ZstdCodec { level: 0 }.decompress(compressed, original_size)
 }
 
 /// Calculates the optimal compression level based on tensor size and type
@@ -245,9 +441,320 @@ pub fn apply_quant_res8(origin: &[u8], payload: &[u8], dtype: Dtype) -> Option<V
     }
 }
 
+/// Number of elements that share a single scale in [`quantize_residual_bits`].
+const QUANT_GROUP_SIZE: u16 = 64;
+
+/// Accumulates sub-byte codes into a tightly packed, LSB-first bitstream.
+struct BitWriter {
+    buf: Vec<u8>,
+    acc: u32,
+    acc_bits: u32,
+}
+
+impl BitWriter {
+    fn new() -> Self {
+        Self {
+            buf: Vec::new(),
+            acc: 0,
+            acc_bits: 0,
+        }
+    }
+
+    fn write(&mut self, value: u32, bits: u32) {
+        self.acc |= value << self.acc_bits;
+        self.acc_bits += bits;
+        while self.acc_bits >= 8 {
+            self.buf.push((self.acc & 0xff) as u8);
+            self.acc >>= 8;
+            self.acc_bits -= 8;
+        }
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        if self.acc_bits > 0 {
+            self.buf.push((self.acc & 0xff) as u8);
+        }
+        self.buf
+    }
+}
+
+/// Reads back a bitstream written by [`BitWriter`].
+struct BitReader<'a> {
+    data: &'a [u8],
+    byte_pos: usize,
+    acc: u32,
+    acc_bits: u32,
+}
+
+impl<'a> BitReader<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Self {
+            data,
+            byte_pos: 0,
+            acc: 0,
+            acc_bits: 0,
+        }
+    }
+
+    fn read(&mut self, bits: u32) -> Option<u32> {
+        while self.acc_bits < bits {
+            let byte = *self.data.get(self.byte_pos)?;
+            self.byte_pos += 1;
+            self.acc |= (byte as u32) << self.acc_bits;
+            self.acc_bits += 8;
+        }
+        let mask = (1u32 << bits) - 1;
+        let value = self.acc & mask;
+        self.acc >>= bits;
+        self.acc_bits -= bits;
+        Some(value)
+    }
+}
+
+/// Quantizes residual f32 bytes into `bits`-wide signed codes (2, 4, or 8
+/// bits per element), with one scale per contiguous group of
+/// [`QUANT_GROUP_SIZE`] elements instead of [`quantize_residual_int8`]'s
+/// single global scale. Wins on residuals that are small and locally
+/// uniform, where the extra per-group scales cost less than the bits
+/// they save. Output format: `[bits:u8][group_size:u16 LE][per-group
+/// scale:f32 LE...][packed codes...]`, codes packed LSB-first across byte
+/// boundaries.
+pub fn quantize_residual_bits(residual_f32_bytes: &[u8], bits: u8) -> Option<Vec<u8>> {
+    if !matches!(bits, 2 | 4 | 8) {
+        return None;
+    }
+    if residual_f32_bytes.len() % 4 != 0 {
+        return None;
+    }
+    let group_size = QUANT_GROUP_SIZE as usize;
+    let max_code = ((1u32 << (bits - 1)) - 1) as f32;
+    let bias = 1i32 << (bits - 1);
+
+    let mut scales: Vec<f32> = Vec::new();
+    let mut writer = BitWriter::new();
+
+    for group in residual_f32_bytes.chunks(group_size * 4) {
+        let values: Vec<f32> = group
+            .chunks(4)
+            .map(|b| f32::from_le_bytes(b.try_into().unwrap()))
+            .collect();
+        let max_abs = values.iter().fold(0f32, |acc, v| acc.max(v.abs()));
+        let scale = if max_abs == 0.0 {
+            1.0
+        } else {
+            max_abs / max_code
+        };
+        scales.push(scale);
+        for v in values {
+            let q = (v / scale).round().clamp(-(bias as f32), max_code) as i32;
+            writer.write((q + bias) as u32, bits as u32);
+        }
+    }
+
+    let codes = writer.finish();
+    let mut out = Vec::with_capacity(3 + scales.len() * 4 + codes.len());
+    out.push(bits);
+    out.extend_from_slice(&QUANT_GROUP_SIZE.to_le_bytes());
+    for scale in &scales {
+        out.extend_from_slice(&scale.to_le_bytes());
+    }
+    out.extend_from_slice(&codes);
+    Some(out)
+}
+
+/// Reconstructs dest from a [`quantize_residual_bits`] payload applied to origin.
+pub fn apply_quant_resn(origin: &[u8], payload: &[u8], dtype: Dtype) -> Option<Vec<u8>> {
+    if dtype != Dtype::F32 || origin.len() % 4 != 0 {
+        return None;
+    }
+    if payload.len() < 3 {
+        return None;
+    }
+    let bits = payload[0];
+    if !matches!(bits, 2 | 4 | 8) {
+        return None;
+    }
+    let group_size = u16::from_le_bytes(payload[1..3].try_into().ok()?) as usize;
+    if group_size == 0 {
+        return None;
+    }
+
+    let n = origin.len() / 4;
+    let n_groups = n.div_ceil(group_size);
+    let scales_start = 3;
+    let scales_end = scales_start + n_groups * 4;
+    if payload.len() < scales_end {
+        return None;
+    }
+    let scales: Vec<f32> = payload[scales_start..scales_end]
+        .chunks(4)
+        .map(|b| f32::from_le_bytes(b.try_into().unwrap()))
+        .collect();
+
+    let bias = 1i32 << (bits - 1);
+    let mut reader = BitReader::new(&payload[scales_end..]);
+    let mut out = Vec::with_capacity(origin.len());
+    for (i, origin_chunk) in origin.chunks(4).enumerate() {
+        let scale = scales[i / group_size];
+        let code = reader.read(bits as u32)? as i32 - bias;
+        let r = code as f32 * scale;
+        let o = f32::from_le_bytes(origin_chunk.try_into().ok()?);
+        let d = o + r;
+        out.extend_from_slice(&d.to_le_bytes());
+    }
+    Some(out)
+}
+
+/// Format tag prepended to the delta+varint sparse layout. Never collides
+/// with a legacy payload's leading `elem_size` byte, since
+/// [`elem_size_for_dtype`] never returns 0.
+const SPARSE_FORMAT_DELTA_VARINT: u8 = 0;
+
+/// Appends `value` to `out` as an unsigned LEB128 varint (7 data bits per
+/// byte, high bit set while more bytes follow).
+fn write_uvarint(out: &mut Vec<u8>, mut value: u64) {
+    loop {
+        let mut byte = (value & 0x7f) as u8;
+        value >>= 7;
+        if value != 0 {
+            byte |= 0x80;
+        }
+        out.push(byte);
+        if value == 0 {
+            break;
+        }
+    }
+}
+
+/// Reads an unsigned LEB128 varint starting at `*cursor`, advancing it past
+/// the bytes consumed.
+fn read_uvarint(data: &[u8], cursor: &mut usize) -> Option<u64> {
+    let mut result: u64 = 0;
+    let mut shift = 0u32;
+    loop {
+        let byte = *data.get(*cursor)?;
+        *cursor += 1;
+        result |= ((byte & 0x7f) as u64) << shift;
+        if byte & 0x80 == 0 {
+            return Some(result);
+        }
+        shift += 7;
+        if shift >= 64 {
+            return None;
+        }
+    }
+}
+
+fn zigzag_encode_i32(v: i32) -> u32 {
+    ((v << 1) ^ (v >> 31)) as u32
+}
+
+fn zigzag_decode_i32(v: u32) -> i32 {
+    ((v >> 1) as i32) ^ -((v & 1) as i32)
+}
+
+fn zigzag_encode_i64(v: i64) -> u64 {
+    ((v << 1) ^ (v >> 63)) as u64
+}
+
+fn zigzag_decode_i64(v: u64) -> i64 {
+    ((v >> 1) as i64) ^ -((v & 1) as i64)
+}
+
+/// Encodes a changed element's value. F32/F64 values are zigzag-varint
+/// packed against the origin element's bit pattern, so a small residual
+/// takes one or two bytes; other dtypes fall back to raw `elem_size` bytes.
+fn write_sparse_value(out: &mut Vec<u8>, origin_elem: &[u8], dest_elem: &[u8], dtype: Dtype) {
+    match dtype {
+        Dtype::F32 => {
+            let o = u32::from_le_bytes(origin_elem.try_into().unwrap());
+            let d = u32::from_le_bytes(dest_elem.try_into().unwrap());
+            let diff = (d as i32).wrapping_sub(o as i32);
+            write_uvarint(out, zigzag_encode_i32(diff) as u64);
+        }
+        Dtype::F64 => {
+            let o = u64::from_le_bytes(origin_elem.try_into().unwrap());
+            let d = u64::from_le_bytes(dest_elem.try_into().unwrap());
+            let diff = (d as i64).wrapping_sub(o as i64);
+            write_uvarint(out, zigzag_encode_i64(diff));
+        }
+        _ => out.extend_from_slice(dest_elem),
+    }
+}
+
+/// Reverses [`write_sparse_value`], reconstructing the `elem_size`-byte
+/// destination value for one changed element.
+fn read_sparse_value(
+    data: &[u8],
+    cursor: &mut usize,
+    origin_elem: &[u8],
+    elem_size: usize,
+    dtype: Dtype,
+) -> Option<Vec<u8>> {
+    match dtype {
+        Dtype::F32 => {
+            let zz = read_uvarint(data, cursor)? as u32;
+            let diff = zigzag_decode_i32(zz);
+            let o = u32::from_le_bytes(origin_elem.try_into().ok()?);
+            let d = (o as i32).wrapping_add(diff) as u32;
+            Some(d.to_le_bytes().to_vec())
+        }
+        Dtype::F64 => {
+            let zz = read_uvarint(data, cursor)?;
+            let diff = zigzag_decode_i64(zz);
+            let o = u64::from_le_bytes(origin_elem.try_into().ok()?);
+            let d = (o as i64).wrapping_add(diff) as u64;
+            Some(d.to_le_bytes().to_vec())
+        }
+        _ => {
+            let value = data.get(*cursor..*cursor + elem_size)?.to_vec();
+            *cursor += elem_size;
+            Some(value)
+        }
+    }
+}
+
 /// Decode sparse payload format and apply to origin to reconstruct dest.
-/// Format: [elem_size:u8][n_changes:u32 LE][ repeated: index:u32 LE | value:elem_size bytes ]
+///
+/// Dispatches on the leading byte: the delta+varint layout written by
+/// [`compute_sparse_bytes`] (tagged with [`SPARSE_FORMAT_DELTA_VARINT`]), or
+/// the legacy fixed-width layout `[elem_size:u8][n_changes:u32 LE][
+/// repeated: index:u32 LE | value:elem_size bytes ]`.
 pub fn apply_sparse_bytes(origin: &[u8], sparse: &[u8], dtype: Dtype) -> Option<Vec<u8>> {
+    if sparse.first() == Some(&SPARSE_FORMAT_DELTA_VARINT) {
+        apply_sparse_bytes_delta_varint(origin, sparse, dtype)
+    } else {
+        apply_sparse_bytes_legacy(origin, sparse, dtype)
+    }
+}
+
+fn apply_sparse_bytes_delta_varint(origin: &[u8], sparse: &[u8], dtype: Dtype) -> Option<Vec<u8>> {
+    let mut cursor = 1usize; // skip the format tag
+    let elem_size = *sparse.get(cursor)? as usize;
+    cursor += 1;
+    if elem_size == 0 || origin.len() % elem_size != 0 {
+        return None;
+    }
+    let n_changes = u32::from_le_bytes(sparse.get(cursor..cursor + 4)?.try_into().ok()?) as usize;
+    cursor += 4;
+
+    let mut out = origin.to_vec();
+    let mut idx: u32 = 0;
+    for _ in 0..n_changes {
+        let delta = read_uvarint(sparse, &mut cursor)?;
+        idx = idx.checked_add(u32::try_from(delta).ok()?)?;
+        let off = (idx as usize).checked_mul(elem_size)?;
+        if off + elem_size > out.len() {
+            return None;
+        }
+        let origin_elem = &origin[off..off + elem_size];
+        let value = read_sparse_value(sparse, &mut cursor, origin_elem, elem_size, dtype)?;
+        out[off..off + elem_size].copy_from_slice(&value);
+    }
+    Some(out)
+}
+
+fn apply_sparse_bytes_legacy(origin: &[u8], sparse: &[u8], _dtype: Dtype) -> Option<Vec<u8>> {
     if sparse.len() < 5 {
         return None;
     }
@@ -287,7 +794,15 @@ fn elem_size_for_dtype(dtype: Dtype) -> Option<usize> {
 }
 
 /// Compute a sparse encoding of the destination tensor relative to origin.
-/// Format: [elem_size:u8][n_changes:u32 LE][ repeated: index:u32 LE | value:elem_size bytes ]
+///
+/// Changed element indices are sorted ascending and stored as successive
+/// varint-packed deltas rather than fixed `u32` values, which compresses
+/// well when changes are clustered. Values for F32/F64 are zigzag-varint
+/// packed against the origin element (see [`write_sparse_value`]); other
+/// dtypes store raw `elem_size` bytes. The indices are deduped before
+/// encoding, so the delta stream is always strictly increasing and thus
+/// non-negative.
+///
 /// Returns None if dtype not supported or lengths mismatch.
 pub fn compute_sparse_bytes(origin: &[u8], dest: &[u8], dtype: Dtype) -> Option<Vec<u8>> {
     let elem_size = elem_size_for_dtype(dtype)?;
@@ -298,22 +813,216 @@ pub fn compute_sparse_bytes(origin: &[u8], dest: &[u8], dtype: Dtype) -> Option<
         return None;
     }
     let n_elems = origin.len() / elem_size;
-    let mut changes: Vec<(u32, Vec<u8>)> = Vec::new();
+    let mut changes: Vec<u32> = Vec::new();
     for i in 0..n_elems {
         let off = i * elem_size;
         if origin[off..off + elem_size] != dest[off..off + elem_size] {
-            changes.push((i as u32, dest[off..off + elem_size].to_vec()));
+            changes.push(i as u32);
         }
     }
+    changes.sort_unstable();
+    changes.dedup();
 
-    // encode
-    let mut out: Vec<u8> = Vec::with_capacity(1 + 4 + changes.len() * (4 + elem_size));
+    let mut out = Vec::new();
+    out.push(SPARSE_FORMAT_DELTA_VARINT);
     out.push(elem_size as u8);
-    let n_changes = changes.len() as u32;
-    out.extend_from_slice(&n_changes.to_le_bytes());
-    for (idx, val) in changes.into_iter() {
-        out.extend_from_slice(&idx.to_le_bytes());
-        out.extend_from_slice(&val);
+    out.extend_from_slice(&(changes.len() as u32).to_le_bytes());
+
+    let mut prev = 0u32;
+    for &idx in &changes {
+        write_uvarint(&mut out, (idx - prev) as u64);
+        prev = idx;
+        let off = idx as usize * elem_size;
+        write_sparse_value(
+            &mut out,
+            &origin[off..off + elem_size],
+            &dest[off..off + elem_size],
+            dtype,
+        );
     }
     Some(out)
 }
+
+/// Default block size for [`compress_blocked`]: 256 KiB.
+pub const DEFAULT_BLOCK_SIZE: u32 = 256 * 1024;
+
+/// Splits `data` into fixed-size blocks, zstd-compresses each independently,
+/// and lays them out as a header of `[block_size:u32 LE][n_blocks:u32 LE]
+/// [compressed_len:u32 LE, one per block]` followed by the concatenated
+/// compressed blocks. Unlike a single monolithic zstd blob, this lets
+/// [`decompress_block_range`] reconstruct a byte window by decompressing
+/// only the blocks it overlaps.
+pub fn compress_blocked(data: &[u8], block_size: u32, is_fp16: bool) -> io::Result<Vec<u8>> {
+    let block_size = block_size.max(1) as usize;
+    let level = optimal_compression_level(data.len(), is_fp16);
+    let codec = ZstdCodec { level };
+
+    let blocks: Vec<Vec<u8>> = data
+        .chunks(block_size)
+        .map(|block| codec.compress(block))
+        .collect::<io::Result<_>>()?;
+
+    let total_compressed: usize = blocks.iter().map(Vec::len).sum();
+    let mut out = Vec::with_capacity(8 + blocks.len() * 4 + total_compressed);
+    out.extend_from_slice(&(block_size as u32).to_le_bytes());
+    out.extend_from_slice(&(blocks.len() as u32).to_le_bytes());
+    for block in &blocks {
+        out.extend_from_slice(&(block.len() as u32).to_le_bytes());
+    }
+    for block in &blocks {
+        out.extend_from_slice(block);
+    }
+    Ok(out)
+}
+
+/// Maps `[byte_start, byte_end)` of the original (uncompressed) tensor to
+/// the blocks written by [`compress_blocked`], decompresses only those
+/// blocks, and returns the requested slice.
+pub fn decompress_block_range(
+    payload: &[u8],
+    byte_start: usize,
+    byte_end: usize,
+) -> io::Result<Vec<u8>> {
+    if byte_end < byte_start {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "byte_end must not be before byte_start",
+        ));
+    }
+    if payload.len() < 8 {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "blocked payload too short for header",
+        ));
+    }
+
+    let block_size = u32::from_le_bytes(payload[0..4].try_into().unwrap()) as usize;
+    let n_blocks = u32::from_le_bytes(payload[4..8].try_into().unwrap()) as usize;
+    if block_size == 0 {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "blocked payload has zero block size",
+        ));
+    }
+
+    let table_offset = 8;
+    let table_end = table_offset + n_blocks * 4;
+    if payload.len() < table_end {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "blocked payload too short for offset table",
+        ));
+    }
+
+    let compressed_lens: Vec<usize> = (0..n_blocks)
+        .map(|i| {
+            let off = table_offset + i * 4;
+            u32::from_le_bytes(payload[off..off + 4].try_into().unwrap()) as usize
+        })
+        .collect();
+
+    let mut out = Vec::with_capacity(byte_end.saturating_sub(byte_start));
+    if byte_start >= byte_end {
+        return Ok(out);
+    }
+
+    let first_block = byte_start / block_size;
+    let last_block = (byte_end - 1) / block_size;
+
+    let mut block_data_offset = table_end;
+    for (i, &compressed_len) in compressed_lens.iter().enumerate() {
+        if i < first_block || i > last_block {
+            block_data_offset += compressed_len;
+            continue;
+        }
+
+        let block_start = block_data_offset;
+        let block_end = block_start + compressed_len;
+        if payload.len() < block_end {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "blocked payload truncated before end of block",
+            ));
+        }
+
+        let block =
+            ZstdCodec { level: 0 }.decompress(&payload[block_start..block_end], block_size)?;
+
+        let block_byte_start = i * block_size;
+        let want_start = byte_start.max(block_byte_start) - block_byte_start;
+        let want_end = byte_end.min(block_byte_start + block.len()) - block_byte_start;
+        out.extend_from_slice(&block[want_start..want_end]);
+
+        block_data_offset += compressed_len;
+    }
+
+    Ok(out)
+}
+
+#[cfg(test)]
+mod zstd_dict_codec_tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_without_any_prior_registration() {
+        let dictionary = b"repeated residual pattern ".repeat(64);
+        let data = b"some residual payload bytes that share structure with the dictionary above"
+            .repeat(8);
+
+        let codec = ZstdDictCodec {
+            level: 3,
+            dictionary: dictionary.clone(),
+        };
+        let compressed = codec.compress(&data).expect("compress");
+
+        // Decoding goes through a freshly constructed codec with an empty
+        // `dictionary` field, exactly as `decompress_by_id` does, to prove the
+        // dictionary travels inside the payload rather than through any
+        // process-local state.
+        let decoded = ZstdDictCodec {
+            level: 0,
+            dictionary: Vec::new(),
+        }
+        .decompress(&compressed, data.len())
+        .expect("decompress");
+
+        assert_eq!(decoded, data);
+    }
+
+    #[test]
+    fn decompress_by_id_dispatches_a_residual_zstd_dict_payload() {
+        let dictionary = b"shared residual dictionary bytes ".repeat(32);
+        let data = b"residual bytes compressible against the dictionary above".repeat(4);
+
+        // Build the framed payload the same way `evaluate_payload_candidates`
+        // would for a winning "residual_zstd_dict" candidate: the codec ID
+        // byte followed by `ZstdDictCodec::compress`'s output.
+        let codec = ZstdDictCodec {
+            level: 1,
+            dictionary,
+        };
+        let mut payload = vec![ZstdDictCodec::ID];
+        payload.extend_from_slice(&codec.compress(&data).expect("compress"));
+
+        let decoded = decompress_by_id(&payload, data.len()).expect("decompress_by_id");
+        assert_eq!(decoded, data);
+    }
+
+    #[test]
+    fn rejects_a_payload_truncated_before_the_embedded_dictionary_ends() {
+        let dictionary = vec![0u8; 16];
+        let codec = ZstdDictCodec {
+            level: 1,
+            dictionary,
+        };
+        let compressed = codec.compress(b"some data").expect("compress");
+
+        // Cut inside the dictionary region itself (length prefix says 16
+        // bytes of dictionary follow, but only 4 remain).
+        let truncated = &compressed[..8];
+        let err = codec
+            .decompress(truncated, 9)
+            .expect_err("should fail on truncated dictionary");
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+}
@@ -196,6 +196,11 @@ pub fn resolve_diff_and_write_patch(
                         if let Some(q) = compression::quantize_residual_int8(&res_bytes) {
                             candidates.push(("quant_res8".to_string(), q));
                         }
+                        for bits in [2u8, 4u8] {
+                            if let Some(q) = compression::quantize_residual_bits(&res_bytes, bits) {
+                                candidates.push((format!("quant_res{bits}"), q));
+                            }
+                        }
                     }
                     // sparse encoding: encode only changed elements (lossless)
                     if let Some(s) = compression::compute_sparse_bytes(
@@ -211,7 +216,12 @@ pub fn resolve_diff_and_write_patch(
             // Evaluate payloads (raw, residual, quantized) and their compressed forms
             let is_fp16 = matches!(dest_tensor.dtype(), Dtype::F16);
             let (method_opt, payload) =
-                match compression::evaluate_payload_candidates(candidates, is_fp16, allow_lossy) {
+                match compression::evaluate_payload_candidates(
+                    candidates,
+                    is_fp16,
+                    allow_lossy,
+                    None,
+                ) {
                     Ok(pair) => pair,
                     Err(_) => (None, data.clone()),
                 };
@@ -210,30 +210,24 @@ impl<T: Read + Write + Seek> TensorPatchFile<T> {
             self.file.read_exact(&mut raw_data)?;
         }
 
-        // Handle decompression if recorded. We accept labels produced by the
-        // chooser such as "raw_zstd", "residual_zstd", "quant_res8_zstd".
+        // Any recorded label means the payload carries a leading codec-ID
+        // byte written by `evaluate_payload_candidates`; decode it uniformly
+        // via `decompress_by_id` regardless of which codec actually won.
         if let Some(method) = &patch.compression {
-            // If the method name contains "zstd" we assume the stored bytes
-            // are zstd-compressed and attempt to decompress them. Otherwise
-            // we return the raw payload as-is and let higher-level logic
-            // interpret the payload format (residual, quantized, etc.).
-            if method.contains("zstd") {
-                // attempt to decompress with an increasing buffer size guess
-                let mut cap = (patch.data_len as usize).saturating_mul(4).max(1024);
-                for _ in 0..8 {
-                    if let Ok(d) = crate::compression::decompress_data(&raw_data, cap) {
-                        return Ok((patch.clone(), d));
-                    }
-                    cap = cap.saturating_mul(2);
+            // The decoded size isn't stored, so guess with an increasing
+            // buffer; codecs that encode their own size (raw, lz4) ignore
+            // this and succeed on the first attempt.
+            let mut cap = (patch.data_len as usize).saturating_mul(4).max(1024);
+            for _ in 0..8 {
+                if let Ok(d) = crate::compression::decompress_by_id(&raw_data, cap) {
+                    return Ok((patch.clone(), d));
                 }
-                return Err(io::Error::new(
-                    io::ErrorKind::InvalidData,
-                    format!("failed to decompress zstd payload for method {}", method),
-                ));
-            } else {
-                // Known non-zstd labels (residual, quant_res8, raw) are returned as-is
-                return Ok((patch.clone(), raw_data));
+                cap = cap.saturating_mul(2);
             }
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("failed to decompress payload for method {}", method),
+            ));
         }
         Ok((patch.clone(), raw_data))
     }
@@ -257,14 +251,12 @@ impl<T: Read + Write + Seek> TensorPatchFile<T> {
             return Ok((patch, payload));
         }
 
+        // The payload was already decompressed (if needed) in read_patch, so
+        // the label here is always the plain delta-type name.
         let label = patch.compression.clone().unwrap();
-        // If payload was stored compressed with zstd suffix, we already
-        // decompressed in read_patch. The label may include suffixes like
-        // "_zstd"; normalize by removing that for dispatch.
-        let base_label = label.strip_suffix("_zstd").unwrap_or(&label).to_string();
 
-        match base_label.as_str() {
-            "residual" => {
+        match label.as_str() {
+            "residual" | "residual_zstd_dict" => {
                 let origin = origin_bytes_opt.ok_or_else(|| {
                     io::Error::new(
                         io::ErrorKind::InvalidInput,
@@ -298,6 +290,23 @@ impl<T: Read + Write + Seek> TensorPatchFile<T> {
                     "failed to apply quantized payload",
                 ));
             }
+            "quant_res2" | "quant_res4" => {
+                let origin = origin_bytes_opt.ok_or_else(|| {
+                    io::Error::new(
+                        io::ErrorKind::InvalidInput,
+                        "origin bytes required for quantized payload",
+                    )
+                })?;
+                if let Some(out) =
+                    crate::compression::apply_quant_resn(origin, &payload, patch.dtype)
+                {
+                    return Ok((patch, out));
+                }
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "failed to apply quantized payload",
+                ));
+            }
             "sparse" => {
                 let origin = origin_bytes_opt.ok_or_else(|| {
                     io::Error::new(
@@ -641,7 +650,7 @@ mod roundtrip_test {
 
             let is_fp16 = matches!(dtype, Dtype::F16);
             let (method_opt, payload) =
-                compression::evaluate_payload_candidates(candidates, is_fp16, false)
+                compression::evaluate_payload_candidates(candidates, is_fp16, false, None)
                     .expect("evaluate candidates");
 
             let patch = TensorPatch {
@@ -1,25 +1,82 @@
-use core::f32;
+use gdal::Dataset;
 use ndarray::{Array, Array2, Array3, ArrayView2, Zip};
 use numpy::{IntoPyArray, PyArray2, PyArray3, PyReadonlyArray2};
 use pyo3::prelude::*;
 use rayon::prelude::*;
-use std::f32::consts::PI;
-use std::sync::atomic::{AtomicUsize, Ordering};
+use std::fs;
+use std::io::Write;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::{Arc, Mutex};
 
 // Import the correct result struct from shadowing
 use crate::shadowing::{calculate_shadows_rust, ShadowingResultRust};
 
+// Compute precision for the whole SVF pipeline. Defaults to `f32`; build with
+// `--features f64` to switch every array, constant and numpy buffer below to
+// `f64` for validating against the reference Python implementation. Note
+// that `crate::shadowing` must be compiled against the same precision for
+// the whole compute path to line up end to end.
+#[cfg(not(feature = "f64"))]
+pub type Float = f32;
+#[cfg(feature = "f64")]
+pub type Float = f64;
+
+#[cfg(not(feature = "f64"))]
+const PI: Float = std::f32::consts::PI;
+#[cfg(feature = "f64")]
+const PI: Float = std::f64::consts::PI;
+
 // Correction factor applied in finalize step
-const LAST_ANNULUS_CORRECTION: f32 = 3.0459e-4;
+const LAST_ANNULUS_CORRECTION: Float = 3.0459e-4;
+
+/// Compute backend for the per-patch shadow + SVF accumulation loop.
+///
+/// `Gpu` is a placeholder only: no `wgpu`/CUDA dependency is vendored in
+/// this source tree, and selecting it always falls back to running the
+/// same `Cpu` rayon path. It exists so the `backend` kwarg is forward
+/// compatible with a future GPU implementation without breaking callers
+/// who already pass `backend="gpu"`; it does not offload any work today.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SvfBackend {
+    #[default]
+    Cpu,
+    /// Not yet implemented; behaves identically to `Cpu`.
+    Gpu,
+}
+
+impl SvfBackend {
+    fn from_py(name: Option<&str>) -> PyResult<Self> {
+        match name {
+            None => Ok(Self::Cpu),
+            Some("cpu") => Ok(Self::Cpu),
+            Some("gpu") => Ok(Self::Gpu),
+            Some(other) => Err(pyo3::exceptions::PyValueError::new_err(format!(
+                "Unknown backend {:?}, expected \"cpu\" or \"gpu\"",
+                other
+            ))),
+        }
+    }
+}
+
+// Raised by `SkyviewRunner::calculate_svf` when `request_cancel()` was called
+// mid-run, in place of a partial result.
+pyo3::create_exception!(umep_rust, CancelledError, pyo3::exceptions::PyException);
+
+#[cfg(feature = "gpu")]
+fn gpu_device_available() -> bool {
+    // Always false: there is no GPU kernel to run a device against yet (see
+    // `SvfBackend::Gpu`'s doc comment). This function is a placeholder for
+    // the real device-acquisition check a future implementation would add.
+    false
+}
 
 // Struct to hold patch configurations
 
 pub struct PatchInfo {
-    pub altitude: f32,
-    pub azimuth: f32,
-    pub azimuth_patches: f32,
-    pub azimuth_patches_aniso: f32,
+    pub altitude: Float,
+    pub azimuth: Float,
+    pub azimuth_patches: Float,
+    pub azimuth_patches_aniso: Float,
     pub annulino_start: i32,
     pub annulino_end: i32,
 }
@@ -56,18 +113,18 @@ fn create_patches(option: u8) -> Vec<PatchInfo> {
     // Iterate over the patch configurations and create PatchInfo instances
     let mut patches: Vec<PatchInfo> = Vec::new();
     for i in 0..altitudes.len() {
-        let azimuth_interval = 360.0 / azimuth_patches[i] as f32;
+        let azimuth_interval = 360.0 / azimuth_patches[i] as Float;
         for j in 0..azimuth_patches[i] as usize {
             // Calculate azimuth based on the start and interval
             // Use rem_euclid to ensure azimuth is within [0, 360)
             let azimuth =
-                (azi_starts[i] as f32 + j as f32 * azimuth_interval as f32).rem_euclid(360.0);
+                (azi_starts[i] as Float + j as Float * azimuth_interval as Float).rem_euclid(360.0);
             patches.push(PatchInfo {
-                altitude: altitudes[i] as f32,
+                altitude: altitudes[i] as Float,
                 azimuth,
-                azimuth_patches: azimuth_patches[i] as f32,
+                azimuth_patches: azimuth_patches[i] as Float,
                 // Calculate anisotropic azimuth patches (ceil(interval/2))
-                azimuth_patches_aniso: (azimuth_patches[i] as f32 / 2.0).ceil(),
+                azimuth_patches_aniso: (azimuth_patches[i] as Float / 2.0).ceil(),
                 annulino_start: annulino[i] + 1, // Start from the next annulino degree to avoid overlap
                 annulino_end: annulino[i + 1],
             });
@@ -80,63 +137,77 @@ fn create_patches(option: u8) -> Vec<PatchInfo> {
 #[pyclass]
 pub struct SvfResult {
     #[pyo3(get)]
-    pub svf: Py<PyArray2<f32>>,
+    pub svf: Py<PyArray2<Float>>,
+    #[pyo3(get)]
+    pub svf_north: Py<PyArray2<Float>>,
+    #[pyo3(get)]
+    pub svf_east: Py<PyArray2<Float>>,
     #[pyo3(get)]
-    pub svf_north: Py<PyArray2<f32>>,
+    pub svf_south: Py<PyArray2<Float>>,
     #[pyo3(get)]
-    pub svf_east: Py<PyArray2<f32>>,
+    pub svf_west: Py<PyArray2<Float>>,
     #[pyo3(get)]
-    pub svf_south: Py<PyArray2<f32>>,
+    pub svf_veg: Py<PyArray2<Float>>,
     #[pyo3(get)]
-    pub svf_west: Py<PyArray2<f32>>,
+    pub svf_veg_north: Py<PyArray2<Float>>,
     #[pyo3(get)]
-    pub svf_veg: Py<PyArray2<f32>>,
+    pub svf_veg_east: Py<PyArray2<Float>>,
     #[pyo3(get)]
-    pub svf_veg_north: Py<PyArray2<f32>>,
+    pub svf_veg_south: Py<PyArray2<Float>>,
     #[pyo3(get)]
-    pub svf_veg_east: Py<PyArray2<f32>>,
+    pub svf_veg_west: Py<PyArray2<Float>>,
     #[pyo3(get)]
-    pub svf_veg_south: Py<PyArray2<f32>>,
+    pub svf_veg_blocks_bldg_sh: Py<PyArray2<Float>>,
     #[pyo3(get)]
-    pub svf_veg_west: Py<PyArray2<f32>>,
+    pub svf_veg_blocks_bldg_sh_north: Py<PyArray2<Float>>,
     #[pyo3(get)]
-    pub svf_veg_blocks_bldg_sh: Py<PyArray2<f32>>,
+    pub svf_veg_blocks_bldg_sh_east: Py<PyArray2<Float>>,
     #[pyo3(get)]
-    pub svf_veg_blocks_bldg_sh_north: Py<PyArray2<f32>>,
+    pub svf_veg_blocks_bldg_sh_south: Py<PyArray2<Float>>,
     #[pyo3(get)]
-    pub svf_veg_blocks_bldg_sh_east: Py<PyArray2<f32>>,
+    pub svf_veg_blocks_bldg_sh_west: Py<PyArray2<Float>>,
     #[pyo3(get)]
-    pub svf_veg_blocks_bldg_sh_south: Py<PyArray2<f32>>,
+    pub bldg_sh_matrix: Py<PyArray3<Float>>,
     #[pyo3(get)]
-    pub svf_veg_blocks_bldg_sh_west: Py<PyArray2<f32>>,
+    pub veg_sh_matrix: Py<PyArray3<Float>>,
+    #[pyo3(get)]
+    pub veg_blocks_bldg_sh_matrix: Py<PyArray3<Float>>,
+}
+
+// Time-averaged shadow result from `SkyviewRunner::calculate_time_resolved_shadow`:
+// the fraction of stepped-over timestamps with the sun at or above
+// `min_sun_elev_deg` that each pixel spent in
+// building (and, when vegetation was used, vegetation) shadow.
+#[pyclass]
+pub struct TimeResolvedShadowResult {
     #[pyo3(get)]
-    pub bldg_sh_matrix: Py<PyArray3<f32>>,
+    pub mean_shadow: Py<PyArray2<Float>>,
     #[pyo3(get)]
-    pub veg_sh_matrix: Py<PyArray3<f32>>,
+    pub mean_veg_shadow: Py<PyArray2<Float>>,
     #[pyo3(get)]
-    pub veg_blocks_bldg_sh_matrix: Py<PyArray3<f32>>,
+    pub num_timesteps: usize,
 }
 
 // Intermediate (pure Rust) SVF result used to avoid holding the GIL during compute
 pub struct SvfIntermediate {
-    pub svf: Array2<f32>,
-    pub svf_n: Array2<f32>,
-    pub svf_e: Array2<f32>,
-    pub svf_s: Array2<f32>,
-    pub svf_w: Array2<f32>,
-    pub svf_veg: Array2<f32>,
-    pub svf_veg_n: Array2<f32>,
-    pub svf_veg_e: Array2<f32>,
-    pub svf_veg_s: Array2<f32>,
-    pub svf_veg_w: Array2<f32>,
-    pub svf_veg_blocks_bldg_sh: Array2<f32>,
-    pub svf_veg_blocks_bldg_sh_n: Array2<f32>,
-    pub svf_veg_blocks_bldg_sh_e: Array2<f32>,
-    pub svf_veg_blocks_bldg_sh_s: Array2<f32>,
-    pub svf_veg_blocks_bldg_sh_w: Array2<f32>,
-    pub bldg_sh_matrix: Array3<f32>,
-    pub veg_sh_matrix: Array3<f32>,
-    pub veg_blocks_bldg_sh_matrix: Array3<f32>,
+    pub svf: Array2<Float>,
+    pub svf_n: Array2<Float>,
+    pub svf_e: Array2<Float>,
+    pub svf_s: Array2<Float>,
+    pub svf_w: Array2<Float>,
+    pub svf_veg: Array2<Float>,
+    pub svf_veg_n: Array2<Float>,
+    pub svf_veg_e: Array2<Float>,
+    pub svf_veg_s: Array2<Float>,
+    pub svf_veg_w: Array2<Float>,
+    pub svf_veg_blocks_bldg_sh: Array2<Float>,
+    pub svf_veg_blocks_bldg_sh_n: Array2<Float>,
+    pub svf_veg_blocks_bldg_sh_e: Array2<Float>,
+    pub svf_veg_blocks_bldg_sh_s: Array2<Float>,
+    pub svf_veg_blocks_bldg_sh_w: Array2<Float>,
+    pub bldg_sh_matrix: Array3<Float>,
+    pub veg_sh_matrix: Array3<Float>,
+    pub veg_blocks_bldg_sh_matrix: Array3<Float>,
 }
 
 // Internal structure for accumulating contributions during parallel processing
@@ -144,21 +215,21 @@ pub struct SvfIntermediate {
 struct PatchContribution {
     num_rows: usize,
     num_cols: usize,
-    svf: Array2<f32>,
-    svf_n: Array2<f32>,
-    svf_e: Array2<f32>,
-    svf_s: Array2<f32>,
-    svf_w: Array2<f32>,
-    svf_veg: Array2<f32>,
-    svf_veg_n: Array2<f32>,
-    svf_veg_e: Array2<f32>,
-    svf_veg_s: Array2<f32>,
-    svf_veg_w: Array2<f32>,
-    svf_veg_blocks_bldg_sh: Array2<f32>,
-    svf_veg_blocks_bldg_sh_n: Array2<f32>,
-    svf_veg_blocks_bldg_sh_e: Array2<f32>,
-    svf_veg_blocks_bldg_sh_s: Array2<f32>,
-    svf_veg_blocks_bldg_sh_w: Array2<f32>,
+    svf: Array2<Float>,
+    svf_n: Array2<Float>,
+    svf_e: Array2<Float>,
+    svf_s: Array2<Float>,
+    svf_w: Array2<Float>,
+    svf_veg: Array2<Float>,
+    svf_veg_n: Array2<Float>,
+    svf_veg_e: Array2<Float>,
+    svf_veg_s: Array2<Float>,
+    svf_veg_w: Array2<Float>,
+    svf_veg_blocks_bldg_sh: Array2<Float>,
+    svf_veg_blocks_bldg_sh_n: Array2<Float>,
+    svf_veg_blocks_bldg_sh_e: Array2<Float>,
+    svf_veg_blocks_bldg_sh_s: Array2<Float>,
+    svf_veg_blocks_bldg_sh_w: Array2<Float>,
 }
 
 impl PatchContribution {
@@ -212,10 +283,10 @@ impl PatchContribution {
     fn finalize_intermediate(
         mut self,
         usevegdem: bool,
-        vegdem2: ArrayView2<f32>,
-        bldg_sh_matrix: Array3<f32>,
-        veg_sh_matrix: Array3<f32>,
-        veg_blocks_bldg_sh_matrix: Array3<f32>,
+        vegdem2: ArrayView2<Float>,
+        bldg_sh_matrix: Array3<Float>,
+        veg_sh_matrix: Array3<Float>,
+        veg_blocks_bldg_sh_matrix: Array3<Float>,
     ) -> SvfIntermediate {
         // Apply correction factors (matching Python code)
         self.svf_s += LAST_ANNULUS_CORRECTION;
@@ -283,46 +354,128 @@ impl PatchContribution {
 }
 
 // --- Helper Functions ---
-fn calculate_max_local_dsm_ht(dsm: ArrayView2<f32>, scale: f32) -> f32 {
+
+// Sliding-window minimum over a 1D slice with a clamped window, i.e.
+// `result[i] = min(values[j] for j in i.saturating_sub(radius)..=(i+radius).min(len-1))`.
+//
+// Uses the van Herk / Gil-Werman algorithm, which is O(len) regardless of
+// window size: conceptually pad `values` with `radius` +inf sentinels on
+// each side (a clamped window is then exactly a fixed-size window over the
+// padded slice, since +inf never wins a minimum), split the padded slice
+// into blocks of length `window = 2*radius+1`, and precompute a forward
+// prefix-minimum and backward suffix-minimum within each block. Every
+// fixed-size window spans at most two adjacent blocks, so its minimum is
+// `min(suffix[start], prefix[start + window - 1])`.
+fn sliding_window_min(values: &[Float], radius: usize) -> Vec<Float> {
+    let len = values.len();
+    if len == 0 {
+        return Vec::new();
+    }
+    let window = 2 * radius + 1;
+    let padded_len = len + 2 * radius;
+    let block_count = padded_len.div_ceil(window);
+    let ext_len = block_count * window;
+
+    // Index into the conceptually-padded slice; out-of-range reads back to
+    // `values` are +inf so they never influence a block's min/max.
+    let padded = |i: usize| -> Float {
+        if i < radius || i >= radius + len {
+            Float::INFINITY
+        } else {
+            values[i - radius]
+        }
+    };
+
+    let mut prefix = vec![Float::INFINITY; ext_len];
+    let mut suffix = vec![Float::INFINITY; ext_len];
+    for block in 0..block_count {
+        let start = block * window;
+        let end = (start + window).min(ext_len);
+
+        let mut running = Float::INFINITY;
+        for (i, slot) in prefix.iter_mut().enumerate().skip(start).take(end - start) {
+            running = running.min(padded(i));
+            *slot = running;
+        }
+
+        let mut running = Float::INFINITY;
+        for (i, slot) in suffix
+            .iter_mut()
+            .enumerate()
+            .skip(start)
+            .take(end - start)
+            .rev()
+        {
+            running = running.min(padded(i));
+            *slot = running;
+        }
+    }
+
+    (0..len)
+        .map(|i| suffix[i].min(prefix[i + window - 1]))
+        .collect()
+}
+
+fn calculate_max_local_dsm_ht(dsm: ArrayView2<Float>, scale: Float) -> Float {
     // Sliding-window size in meters (assumption). Use 100m by default.
-    const LOCAL_WINDOW_M: f32 = 100.0;
+    const LOCAL_WINDOW_M: Float = 100.0;
     if !(scale.is_finite()) || scale <= 0.0 {
         return 0.0;
     }
     // Convert window radius from meters to pixels
-    let radius = ((LOCAL_WINDOW_M / scale).ceil() as usize).max(0);
+    let radius = (LOCAL_WINDOW_M / scale).ceil() as usize;
     if radius == 0 {
         return 0.0;
     }
     let (num_rows, num_cols) = (dsm.nrows(), dsm.ncols());
+
+    // Non-finite DSM cells act as 0 when contributing to a neighbor's window
+    // minimum (matching the previous per-pixel neighbor handling).
+    let clamped = dsm.mapv(|v| if v.is_finite() { v } else { 0.0 });
+
+    // The box-window minimum is separable: slide along rows first, then
+    // along the columns of that result, each pass an O(n) sliding-window
+    // minimum rather than rescanning the full window per pixel.
+    let row_mins: Vec<Vec<Float>> = (0..num_rows)
+        .into_par_iter()
+        .map(|r| {
+            let row: Vec<Float> = clamped.row(r).iter().copied().collect();
+            sliding_window_min(&row, radius)
+        })
+        .collect();
+    let mut row_pass = Array2::<Float>::zeros((num_rows, num_cols));
+    for (r, mins) in row_mins.into_iter().enumerate() {
+        for (c, v) in mins.into_iter().enumerate() {
+            row_pass[[r, c]] = v;
+        }
+    }
+
+    let col_mins: Vec<Vec<Float>> = (0..num_cols)
+        .into_par_iter()
+        .map(|c| {
+            let col: Vec<Float> = row_pass.column(c).iter().copied().collect();
+            sliding_window_min(&col, radius)
+        })
+        .collect();
+    let mut win_min = Array2::<Float>::zeros((num_rows, num_cols));
+    for (c, mins) in col_mins.into_iter().enumerate() {
+        for (r, v) in mins.into_iter().enumerate() {
+            win_min[[r, c]] = v;
+        }
+    }
+
     // Parallel per-pixel implementation:
     // - Iterate over the flattened pixel indices in parallel
-    // - Compute local min/max for each pixel's square window and return the range
+    // - Compute the range between the pixel and its window minimum
     let total_pixels = num_rows.saturating_mul(num_cols);
-    let ranges: Vec<f32> = (0..total_pixels)
+    let ranges: Vec<Float> = (0..total_pixels)
         .into_par_iter()
         .map(|idx| {
             let r = idx / num_cols;
             let c = idx % num_cols;
-
-            let r0 = if r >= radius { r - radius } else { 0 };
-            let r1 = (r + radius).min(num_rows - 1);
-            let c0 = if c >= radius { c - radius } else { 0 };
-            let c1 = (c + radius).min(num_cols - 1);
-
-            let mut local_range = f32::NEG_INFINITY;
-            let val: f32 = dsm[[r, c]];
-            for rr in r0..=r1 {
-                for cc in c0..=c1 {
-                    let dv = dsm[[rr, cc]];
-                    let nv = if dv.is_finite() { dv } else { 0.0 };
-                    if val - nv > local_range {
-                        local_range = val - nv;
-                    }
-                }
-            }
+            let local_range = dsm[[r, c]] - win_min[[r, c]];
             if local_range.is_finite() {
-                (local_range).max(0.0)
+                local_range.max(0.0)
             } else {
                 0.0
             }
@@ -330,13 +483,13 @@ fn calculate_max_local_dsm_ht(dsm: ArrayView2<f32>, scale: f32) -> f32 {
         .collect();
 
     // Keep only finite values (should already be finite) to be safe
-    let mut finite_ranges: Vec<f32> = ranges.into_iter().filter(|v| v.is_finite()).collect();
+    let mut finite_ranges: Vec<Float> = ranges.into_iter().filter(|v| v.is_finite()).collect();
 
     let final_value = if finite_ranges.is_empty() {
         0.0
     } else {
         let idx = (((finite_ranges.len() - 1) as f64) * 0.99).floor() as usize;
-        // Use comparator for f32 partial ordering
+        // Use comparator for partial ordering
         finite_ranges.select_nth_unstable_by(idx, |a, b| a.partial_cmp(b).unwrap());
         finite_ranges[idx]
     };
@@ -349,9 +502,9 @@ fn calculate_max_local_dsm_ht(dsm: ArrayView2<f32>, scale: f32) -> f32 {
     final_value
 }
 
-fn prepare_bushes(vegdem: ArrayView2<f32>, vegdem2: ArrayView2<f32>) -> Array2<f32> {
+fn prepare_bushes(vegdem: ArrayView2<Float>, vegdem2: ArrayView2<Float>) -> Array2<Float> {
     // Allocate output array with same shape as input
-    let mut bush_areas = Array2::<f32>::zeros(vegdem.raw_dim());
+    let mut bush_areas = Array2::<Float>::zeros(vegdem.raw_dim());
     // Fill bush_areas in place, no unnecessary clones
     Zip::from(&mut bush_areas)
         .and(&vegdem)
@@ -362,33 +515,191 @@ fn prepare_bushes(vegdem: ArrayView2<f32>, vegdem2: ArrayView2<f32>) -> Array2<f
     bush_areas
 }
 
+// Grid metadata for a raster band read via `read_geotiff_band`, used to
+// cross-check that the DSM and (optional) vegetation layers passed to
+// `SkyviewRunner::calculate_svf_from_paths` line up pixel-for-pixel.
+struct RasterGrid {
+    rows: usize,
+    cols: usize,
+    pixel_size: Float,
+    projection: String,
+}
+
+// Read band 1 of a GeoTIFF into a plain `Array2<Float>`, deriving the raster's
+// grid metadata (shape, pixel size in meters, projection) from its affine
+// geotransform. Requires square, axis-aligned pixels, since `calculate_svf`
+// takes a single `scale` for the whole raster.
+fn read_geotiff_band(path: &str) -> PyResult<(Array2<Float>, RasterGrid)> {
+    let dataset = Dataset::open(path).map_err(|e| {
+        pyo3::exceptions::PyIOError::new_err(format!("failed to open {:?}: {}", path, e))
+    })?;
+    let transform = dataset.geo_transform().map_err(|e| {
+        pyo3::exceptions::PyValueError::new_err(format!(
+            "{:?} has no affine geotransform: {}",
+            path, e
+        ))
+    })?;
+    let pixel_w = transform[1].abs() as Float;
+    let pixel_h = transform[5].abs() as Float;
+    if !pixel_w.is_finite() || !pixel_h.is_finite() || pixel_w <= 0.0 || pixel_h <= 0.0 {
+        return Err(pyo3::exceptions::PyValueError::new_err(format!(
+            "{:?} has a non-positive or non-finite pixel size",
+            path
+        )));
+    }
+    if (pixel_w - pixel_h).abs() > 1e-6 * pixel_w.max(pixel_h) {
+        return Err(pyo3::exceptions::PyValueError::new_err(format!(
+            "{:?} has non-square pixels ({} x {} m); calculate_svf needs a single uniform scale",
+            path, pixel_w, pixel_h
+        )));
+    }
+
+    let band = dataset.rasterband(1).map_err(|e| {
+        pyo3::exceptions::PyValueError::new_err(format!("{:?} has no raster band 1: {}", path, e))
+    })?;
+    let (cols, rows) = band.size();
+    let buffer = band
+        .read_as::<f64>((0, 0), (cols, rows), (cols, rows), None)
+        .map_err(|e| {
+            pyo3::exceptions::PyValueError::new_err(format!("failed to read {:?}: {}", path, e))
+        })?;
+    let data: Vec<Float> = buffer.data().iter().map(|&v| v as Float).collect();
+    let array = Array2::from_shape_vec((rows, cols), data).map_err(|e| {
+        pyo3::exceptions::PyValueError::new_err(format!(
+            "{:?}: unexpected raster buffer shape: {}",
+            path, e
+        ))
+    })?;
+
+    Ok((
+        array,
+        RasterGrid {
+            rows,
+            cols,
+            pixel_size: pixel_w,
+            projection: dataset.projection(),
+        },
+    ))
+}
+
+// Ensure `other` shares `reference`'s grid (shape, pixel size, and -- when
+// both are set -- projection), so rasters loaded from independent files can
+// be combined pixel-for-pixel. `reference_name`/`other_name` are used only
+// to make the error message point at the offending path argument.
+fn ensure_grid_matches(
+    reference: &RasterGrid,
+    other: &RasterGrid,
+    reference_name: &str,
+    other_name: &str,
+) -> PyResult<()> {
+    if reference.rows != other.rows || reference.cols != other.cols {
+        return Err(pyo3::exceptions::PyValueError::new_err(format!(
+            "{} is {}x{} pixels but {} is {}x{}; all layers must share the same grid",
+            other_name, other.rows, other.cols, reference_name, reference.rows, reference.cols
+        )));
+    }
+    if (reference.pixel_size - other.pixel_size).abs() > 1e-6 * reference.pixel_size {
+        return Err(pyo3::exceptions::PyValueError::new_err(format!(
+            "{} has pixel size {} m but {} has {} m; all layers must share the same scale",
+            other_name, other.pixel_size, reference_name, reference.pixel_size
+        )));
+    }
+    if !reference.projection.is_empty()
+        && !other.projection.is_empty()
+        && reference.projection != other.projection
+    {
+        return Err(pyo3::exceptions::PyValueError::new_err(format!(
+            "{} and {} have different CRS/projection definitions",
+            reference_name, other_name
+        )));
+    }
+    Ok(())
+}
+
+// --- Solar position (self-contained, no calendar/timezone dependency) ---
+
+// Solar declination (degrees) for day-of-year `n`, per the standard Cooper
+// (1969) approximation.
+fn solar_declination_deg(day_of_year: u32) -> Float {
+    23.45 * ((360.0 / 365.0) * (284.0 + day_of_year as Float) * PI / 180.0).sin()
+}
+
+// Equation of time correction (minutes), per the three-term approximation
+// 9.87 sin 2B - 7.53 cos B - 1.5 sin B with B = 360/365 (n - 81) degrees --
+// corrects mean solar time to apparent (true) solar time.
+fn equation_of_time_minutes(day_of_year: u32) -> Float {
+    let b = (360.0 / 365.0) * (day_of_year as Float - 81.0) * PI / 180.0;
+    9.87 * (2.0 * b).sin() - 7.53 * b.cos() - 1.5 * b.sin()
+}
+
+// Solar elevation and azimuth (both degrees; azimuth normalized to
+// [0, 360)) for latitude/longitude `latitude_deg`/`longitude_deg`, a given
+// day-of-year, and a fractional UTC hour (e.g. 13.5 == 13:30 UTC).
+// Self-contained: no calendar or timezone handling beyond a plain
+// day-of-year/hour-of-day pair, so callers can step over an arbitrary
+// `(start, end, step)` time range without pulling in a date/time crate.
+fn solar_position(
+    latitude_deg: Float,
+    longitude_deg: Float,
+    day_of_year: u32,
+    utc_hour: Float,
+) -> (Float, Float) {
+    let declination = solar_declination_deg(day_of_year) * PI / 180.0;
+    let latitude = latitude_deg * PI / 180.0;
+
+    // Apparent local solar time (hours): UTC plus the longitude's implied
+    // time-zone offset (15 degrees per hour) plus the equation-of-time
+    // correction.
+    let local_solar_time =
+        utc_hour + longitude_deg / 15.0 + equation_of_time_minutes(day_of_year) / 60.0;
+    let hour_angle = 15.0 * (local_solar_time - 12.0) * PI / 180.0;
+
+    let elevation = (latitude.sin() * declination.sin()
+        + latitude.cos() * declination.cos() * hour_angle.cos())
+    .asin();
+    let azimuth = hour_angle
+        .sin()
+        .atan2(hour_angle.cos() * latitude.sin() - declination.tan() * latitude.cos());
+
+    let elevation_deg = elevation * 180.0 / PI;
+    let azimuth_deg = (azimuth * 180.0 / PI).rem_euclid(360.0);
+    (elevation_deg, azimuth_deg)
+}
+
 // --- Main Calculation Function ---
 // Calculate SVF with 153 patches (equivalent to Python's svfForProcessing153)
-// Internal implementation that supports an optional progress counter
-fn calculate_svf_inner(
-    dsm_py: PyReadonlyArray2<f32>,
-    vegdem_py: PyReadonlyArray2<f32>,
-    vegdem2_py: PyReadonlyArray2<f32>,
-    scale: f32,
+// Core implementation over plain array views, shared by the single-shot
+// pyfunction/SkyviewRunner entry points and the tiled out-of-core driver below.
+fn calculate_svf_core(
+    dsm_f: ArrayView2<Float>,
+    vegdem_f: ArrayView2<Float>,
+    vegdem2_f: ArrayView2<Float>,
+    scale: Float,
     usevegdem: bool,
     patch_option: u8,
-    min_sun_elev_deg: Option<f32>,
-    max_shadow_length: Option<f32>,
+    min_sun_elev_deg: Option<Float>,
+    max_shadow_length: Option<Float>,
     progress_counter: Option<Arc<AtomicUsize>>,
+    cancel_flag: Option<Arc<AtomicBool>>,
+    backend: SvfBackend,
 ) -> PyResult<SvfIntermediate> {
-    // Get array views from Python arrays
-    let dsm_f32 = dsm_py.as_array();
-    let vegdem_f32 = vegdem_py.as_array();
-    let vegdem2_f32 = vegdem2_py.as_array(); // Keep f32 version for finalize step
+    #[cfg(feature = "gpu")]
+    if backend == SvfBackend::Gpu && !gpu_device_available() {
+        eprintln!("[umep-rust] GPU backend is not yet implemented, running on CPU instead");
+    }
+    #[cfg(not(feature = "gpu"))]
+    if backend == SvfBackend::Gpu {
+        eprintln!("[umep-rust] GPU backend is not yet implemented, running on CPU instead");
+    }
 
-    let num_rows = dsm_f32.nrows();
-    let num_cols = dsm_f32.ncols();
+    let num_rows = dsm_f.nrows();
+    let num_cols = dsm_f.ncols();
 
     // Calculate maximum height for shadow calculations (local sliding-window)
-    let max_local_dsm_ht = calculate_max_local_dsm_ht(dsm_f32, scale);
+    let max_local_dsm_ht = calculate_max_local_dsm_ht(dsm_f, scale);
 
     // Prepare bushes
-    let bush_f32 = prepare_bushes(vegdem_f32.view(), vegdem2_f32.view());
+    let bush_f = prepare_bushes(vegdem_f.view(), vegdem2_f.view());
 
     // Create sky patches (use patch_option argument)
     let patches = create_patches(patch_option);
@@ -416,13 +727,23 @@ fn calculate_svf_inner(
         .par_iter()
         .enumerate()
         .map(|(patch_idx, patch)| {
-            let dsm_view = dsm_f32.view();
+            // Check cancellation at the same per-patch cadence the progress
+            // counter is bumped at, so a cancelled run stops promptly rather
+            // than draining the remaining patch queue.
+            if cancel_flag
+                .as_ref()
+                .is_some_and(|f| f.load(Ordering::SeqCst))
+            {
+                return PatchContribution::zeros(num_rows, num_cols);
+            }
+
+            let dsm_view = dsm_f.view();
             // Only pass vegetation views if usevegdem is true, otherwise pass None
             let (vegdem_view, vegdem2_view, bush_view) = if usevegdem {
                 (
-                    Some(vegdem_f32.view()),
-                    Some(vegdem2_f32.view()),
-                    Some(bush_f32.view()),
+                    Some(vegdem_f.view()),
+                    Some(vegdem2_f.view()),
+                    Some(bush_f.view()),
                 )
             } else {
                 (None, None, None)
@@ -441,8 +762,8 @@ fn calculate_svf_inner(
                 None,
                 None,
                 None,
-                min_sun_elev_deg.unwrap_or(6.0_f32),
-                max_shadow_length.unwrap_or(1000.0_f32),
+                min_sun_elev_deg.unwrap_or(6.0),
+                max_shadow_length.unwrap_or(1000.0),
             );
 
             // --- Calculate SVF contribution for this patch ---
@@ -455,7 +776,7 @@ fn calculate_svf_inner(
             let steprad_aniso = (360.0 / patch.azimuth_patches_aniso) * (PI / 180.0);
 
             for annulus_idx in patch.annulino_start..=patch.annulino_end {
-                let annulus = 91.0 - annulus_idx as f32;
+                let annulus = 91.0 - annulus_idx as Float;
                 let sin_term = ((PI * (2.0 * annulus - 1.0)) / (2.0 * n)).sin();
                 let common_w_part = common_w_factor * sin_term;
 
@@ -551,6 +872,13 @@ fn calculate_svf_inner(
             |a, b| a.combine(b),
         );
 
+    if cancel_flag
+        .as_ref()
+        .is_some_and(|f| f.load(Ordering::SeqCst))
+    {
+        return Err(CancelledError::new_err("calculate_svf was cancelled"));
+    }
+
     // Unwrap the matrices from Arc<Mutex<...>>
     let bldg_sh_matrix = Arc::try_unwrap(bldg_sh_matrix)
         .unwrap()
@@ -568,13 +896,44 @@ fn calculate_svf_inner(
     // Finalize and return an intermediate result - pass the populated 3D arrays
     Ok(final_contribution.finalize_intermediate(
         usevegdem,
-        vegdem2_f32,
+        vegdem2_f,
         bldg_sh_matrix,
         veg_sh_matrix,
         veg_blocks_bldg_sh_matrix,
     ))
 }
 
+// Internal implementation that supports an optional progress counter.
+// Thin wrapper around `calculate_svf_core` that borrows array views out of
+// the numpy buffers handed in from Python.
+fn calculate_svf_inner(
+    dsm_py: PyReadonlyArray2<Float>,
+    vegdem_py: PyReadonlyArray2<Float>,
+    vegdem2_py: PyReadonlyArray2<Float>,
+    scale: Float,
+    usevegdem: bool,
+    patch_option: u8,
+    min_sun_elev_deg: Option<Float>,
+    max_shadow_length: Option<Float>,
+    progress_counter: Option<Arc<AtomicUsize>>,
+    cancel_flag: Option<Arc<AtomicBool>>,
+    backend: SvfBackend,
+) -> PyResult<SvfIntermediate> {
+    calculate_svf_core(
+        dsm_py.as_array(),
+        vegdem_py.as_array(),
+        vegdem2_py.as_array(),
+        scale,
+        usevegdem,
+        patch_option,
+        min_sun_elev_deg,
+        max_shadow_length,
+        progress_counter,
+        cancel_flag,
+        backend,
+    )
+}
+
 // Convert SvfIntermediate into Python SvfResult under the GIL
 fn svf_intermediate_to_py(py: Python, inter: SvfIntermediate) -> PyResult<Py<SvfResult>> {
     Py::new(
@@ -606,17 +965,19 @@ fn svf_intermediate_to_py(py: Python, inter: SvfIntermediate) -> PyResult<Py<Svf
 #[pyfunction]
 pub fn calculate_svf(
     py: Python,
-    dsm_py: PyReadonlyArray2<f32>,
-    vegdem_py: PyReadonlyArray2<f32>,
-    vegdem2_py: PyReadonlyArray2<f32>,
-    scale: f32,
+    dsm_py: PyReadonlyArray2<Float>,
+    vegdem_py: PyReadonlyArray2<Float>,
+    vegdem2_py: PyReadonlyArray2<Float>,
+    scale: Float,
     usevegdem: bool,
     patch_option: Option<u8>, // New argument for patch option
-    min_sun_elev_deg: Option<f32>,
-    max_shadow_length: Option<f32>,
+    min_sun_elev_deg: Option<Float>,
+    max_shadow_length: Option<Float>,
     _progress_callback: Option<PyObject>,
+    backend: Option<&str>,
 ) -> PyResult<Py<SvfResult>> {
     let patch_option = patch_option.unwrap_or(2);
+    let backend = SvfBackend::from_py(backend)?;
     let inter = calculate_svf_inner(
         dsm_py,
         vegdem_py,
@@ -624,9 +985,11 @@ pub fn calculate_svf(
         scale,
         usevegdem,
         patch_option,
-        Some(min_sun_elev_deg.unwrap_or(6.0_f32)),
-        Some(max_shadow_length.unwrap_or(1000.0_f32)),
+        Some(min_sun_elev_deg.unwrap_or(6.0)),
+        Some(max_shadow_length.unwrap_or(1000.0)),
+        None,
         None,
+        backend,
     )?;
     svf_intermediate_to_py(py, inter)
 }
@@ -635,6 +998,7 @@ pub fn calculate_svf(
 #[pyclass]
 pub struct SkyviewRunner {
     progress: Arc<AtomicUsize>,
+    cancel: Arc<AtomicBool>,
 }
 
 #[pymethods]
@@ -643,6 +1007,7 @@ impl SkyviewRunner {
     pub fn new() -> Self {
         Self {
             progress: Arc::new(AtomicUsize::new(0)),
+            cancel: Arc::new(AtomicBool::new(false)),
         }
     }
 
@@ -650,21 +1015,37 @@ impl SkyviewRunner {
         self.progress.load(Ordering::SeqCst)
     }
 
+    /// Request that the in-flight `calculate_svf` call stop early.
+    /// Checked at the same per-patch cadence as the progress counter; once
+    /// observed, `calculate_svf` returns a `CancelledError` instead of a
+    /// partial result. Every run of this runner clears the flag when it
+    /// starts, so a request made before a call does not cancel that call.
+    pub fn request_cancel(&self) {
+        self.cancel.store(true, Ordering::SeqCst);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancel.load(Ordering::SeqCst)
+    }
+
     pub fn calculate_svf(
         &self,
         py: Python,
-        dsm_py: PyReadonlyArray2<f32>,
-        vegdem_py: PyReadonlyArray2<f32>,
-        vegdem2_py: PyReadonlyArray2<f32>,
-        scale: f32,
+        dsm_py: PyReadonlyArray2<Float>,
+        vegdem_py: PyReadonlyArray2<Float>,
+        vegdem2_py: PyReadonlyArray2<Float>,
+        scale: Float,
         usevegdem: bool,
         patch_option: Option<u8>,
-        min_sun_elev_deg: Option<f32>,
-        max_shadow_length: Option<f32>,
+        min_sun_elev_deg: Option<Float>,
+        max_shadow_length: Option<Float>,
+        backend: Option<&str>,
     ) -> PyResult<Py<SvfResult>> {
         let patch_option = patch_option.unwrap_or(2);
-        // reset progress
+        let backend = SvfBackend::from_py(backend)?;
+        // reset progress and any previously requested cancellation
         self.progress.store(0, Ordering::SeqCst);
+        self.cancel.store(false, Ordering::SeqCst);
         let inter = calculate_svf_inner(
             dsm_py,
             vegdem_py,
@@ -672,10 +1053,865 @@ impl SkyviewRunner {
             scale,
             usevegdem,
             patch_option,
-            Some(min_sun_elev_deg.unwrap_or(6.0_f32)),
-            Some(max_shadow_length.unwrap_or(1000.0_f32)),
+            Some(min_sun_elev_deg.unwrap_or(6.0)),
+            Some(max_shadow_length.unwrap_or(1000.0)),
+            Some(self.progress.clone()),
+            Some(self.cancel.clone()),
+            backend,
+        )?;
+        svf_intermediate_to_py(py, inter)
+    }
+
+    /// Load the DSM and (optional) vegetation canopy/trunk-zone layers
+    /// straight from GeoTIFF, deriving `scale` (pixels per meter) from the
+    /// DSM's affine geotransform instead of requiring the caller to supply
+    /// pre-loaded NumPy arrays and a scale by hand. `vegdem_path` (and
+    /// `vegdem2_path`, which requires it) are optional: when `vegdem_path`
+    /// is omitted, `usevegdem` is set to `false` and vegetation is treated
+    /// as absent everywhere, following the same run/instrument-resolution
+    /// idea as Mantid's loaders -- supply what you have and let the loader
+    /// fill in the rest. Raises a `ValueError` when a vegetation layer's
+    /// grid, pixel size, or projection disagrees with the DSM's.
+    #[pyo3(signature = (dsm_path, vegdem_path=None, vegdem2_path=None, patch_option=None, min_sun_elev_deg=None, max_shadow_length=None, backend=None))]
+    #[allow(clippy::too_many_arguments)]
+    pub fn calculate_svf_from_paths(
+        &self,
+        py: Python,
+        dsm_path: &str,
+        vegdem_path: Option<&str>,
+        vegdem2_path: Option<&str>,
+        patch_option: Option<u8>,
+        min_sun_elev_deg: Option<Float>,
+        max_shadow_length: Option<Float>,
+        backend: Option<&str>,
+    ) -> PyResult<Py<SvfResult>> {
+        let patch_option = patch_option.unwrap_or(2);
+        let backend = SvfBackend::from_py(backend)?;
+
+        let (dsm_arr, dsm_grid) = read_geotiff_band(dsm_path)?;
+        let scale = dsm_grid.pixel_size;
+
+        let usevegdem = vegdem_path.is_some();
+        let vegdem_arr = match vegdem_path {
+            Some(path) => {
+                let (arr, grid) = read_geotiff_band(path)?;
+                ensure_grid_matches(&dsm_grid, &grid, "dsm_path", "vegdem_path")?;
+                arr
+            }
+            None => Array2::zeros((dsm_grid.rows, dsm_grid.cols)),
+        };
+        let vegdem2_arr = match vegdem2_path {
+            Some(path) => {
+                if !usevegdem {
+                    return Err(pyo3::exceptions::PyValueError::new_err(
+                        "vegdem2_path (trunk zone) was given without vegdem_path (canopy)",
+                    ));
+                }
+                let (arr, grid) = read_geotiff_band(path)?;
+                ensure_grid_matches(&dsm_grid, &grid, "dsm_path", "vegdem2_path")?;
+                arr
+            }
+            None => Array2::zeros((dsm_grid.rows, dsm_grid.cols)),
+        };
+
+        self.progress.store(0, Ordering::SeqCst);
+        self.cancel.store(false, Ordering::SeqCst);
+        let inter = calculate_svf_core(
+            dsm_arr.view(),
+            vegdem_arr.view(),
+            vegdem2_arr.view(),
+            scale,
+            usevegdem,
+            patch_option,
+            Some(min_sun_elev_deg.unwrap_or(6.0)),
+            Some(max_shadow_length.unwrap_or(1000.0)),
             Some(self.progress.clone()),
+            Some(self.cancel.clone()),
+            backend,
         )?;
         svf_intermediate_to_py(py, inter)
     }
+
+    /// Average binary building (and, when `usevegdem`, vegetation) shadow
+    /// masks over real sun positions stepped across `(start_hour, end_hour,
+    /// step_hours)`, each expressed as fractional hours-of-year UTC (hour
+    /// `0.0` is Jan 1st 00:00 UTC), e.g. `(0.0, 8760.0, 1.0)` for an hourly
+    /// annual mean. Timestamps whose solar elevation falls below
+    /// `min_sun_elev_deg` are skipped (night/near-horizon). Reuses the
+    /// `solar_position` routine and the same shadow-casting machinery
+    /// `calculate_svf` uses, bounded by `max_shadow_length`, and reports
+    /// per-timestep progress through `progress()` (and honors
+    /// `request_cancel()`) the same way `calculate_svf` does.
+    #[allow(clippy::too_many_arguments)]
+    #[pyo3(signature = (dsm_py, vegdem_py, vegdem2_py, scale, usevegdem, latitude_deg, longitude_deg, start_hour, end_hour, step_hours, min_sun_elev_deg=None, max_shadow_length=None))]
+    pub fn calculate_time_resolved_shadow(
+        &self,
+        py: Python,
+        dsm_py: PyReadonlyArray2<Float>,
+        vegdem_py: PyReadonlyArray2<Float>,
+        vegdem2_py: PyReadonlyArray2<Float>,
+        scale: Float,
+        usevegdem: bool,
+        latitude_deg: Float,
+        longitude_deg: Float,
+        start_hour: Float,
+        end_hour: Float,
+        step_hours: Float,
+        min_sun_elev_deg: Option<Float>,
+        max_shadow_length: Option<Float>,
+    ) -> PyResult<Py<TimeResolvedShadowResult>> {
+        if !step_hours.is_finite() || step_hours <= 0.0 {
+            return Err(pyo3::exceptions::PyValueError::new_err(
+                "step_hours must be a positive, finite number of hours",
+            ));
+        }
+        let min_sun_elev_deg = min_sun_elev_deg.unwrap_or(6.0);
+        let max_shadow_length = max_shadow_length.unwrap_or(1000.0);
+
+        let dsm_f = dsm_py.as_array();
+        let vegdem_f = vegdem_py.as_array();
+        let vegdem2_f = vegdem2_py.as_array();
+        let (num_rows, num_cols) = (dsm_f.nrows(), dsm_f.ncols());
+
+        let max_local_dsm_ht = calculate_max_local_dsm_ht(dsm_f, scale);
+        let bush_f = prepare_bushes(vegdem_f.view(), vegdem2_f.view());
+
+        self.progress.store(0, Ordering::SeqCst);
+        self.cancel.store(false, Ordering::SeqCst);
+
+        let mut shadow_sum = Array2::<Float>::zeros((num_rows, num_cols));
+        let mut veg_shadow_sum = Array2::<Float>::zeros((num_rows, num_cols));
+        let mut counted = 0usize;
+
+        let mut hour_of_year = start_hour;
+        while hour_of_year < end_hour {
+            if self.cancel.load(Ordering::SeqCst) {
+                return Err(CancelledError::new_err(
+                    "calculate_time_resolved_shadow was cancelled",
+                ));
+            }
+
+            let day_of_year = (hour_of_year / 24.0).floor() as u32 + 1;
+            let utc_hour = hour_of_year.rem_euclid(24.0);
+            let (elevation_deg, azimuth_deg) =
+                solar_position(latitude_deg, longitude_deg, day_of_year, utc_hour);
+
+            self.progress.fetch_add(1, Ordering::SeqCst);
+
+            if elevation_deg >= min_sun_elev_deg {
+                let (vegdem_view, vegdem2_view, bush_view) = if usevegdem {
+                    (
+                        Some(vegdem_f.view()),
+                        Some(vegdem2_f.view()),
+                        Some(bush_f.view()),
+                    )
+                } else {
+                    (None, None, None)
+                };
+
+                let shadow_result: ShadowingResultRust = calculate_shadows_rust(
+                    azimuth_deg,
+                    elevation_deg,
+                    scale,
+                    max_local_dsm_ht,
+                    dsm_f.view(),
+                    vegdem_view,
+                    vegdem2_view,
+                    bush_view,
+                    None,
+                    None,
+                    None,
+                    None,
+                    min_sun_elev_deg,
+                    max_shadow_length,
+                );
+
+                shadow_sum += &shadow_result.bldg_sh;
+                if usevegdem {
+                    veg_shadow_sum += &shadow_result.veg_sh;
+                }
+                counted += 1;
+            }
+
+            hour_of_year += step_hours;
+        }
+
+        if counted > 0 {
+            let divisor = counted as Float;
+            shadow_sum.mapv_inplace(|v| v / divisor);
+            if usevegdem {
+                veg_shadow_sum.mapv_inplace(|v| v / divisor);
+            }
+        }
+
+        Py::new(
+            py,
+            TimeResolvedShadowResult {
+                mean_shadow: shadow_sum.into_pyarray(py).unbind(),
+                mean_veg_shadow: veg_shadow_sum.into_pyarray(py).unbind(),
+                num_timesteps: counted,
+            },
+        )
+    }
+}
+
+// --- Memory estimation ---
+
+// Byte size of one `Float` element; the unit every estimate below is built from.
+const FLOAT_BYTES: usize = std::mem::size_of::<Float>();
+
+// Estimated peak footprint of a named intermediate buffer, as returned in
+// `SvfMemoryEstimate::buffers`.
+#[pyclass]
+#[derive(Clone)]
+pub struct MemoryBufferEstimate {
+    #[pyo3(get)]
+    pub name: String,
+    #[pyo3(get)]
+    pub bytes: usize,
+}
+
+// Breakdown of `calculate_svf`'s estimated peak memory use for a given
+// raster shape and `patch_option`, as returned by `estimate_svf_memory_bytes`.
+#[pyclass]
+#[derive(Clone)]
+pub struct SvfMemoryEstimate {
+    #[pyo3(get)]
+    pub buffers: Vec<MemoryBufferEstimate>,
+    #[pyo3(get)]
+    pub total_bytes: usize,
+}
+
+// Estimate the peak bytes `calculate_svf_core` holds live for a raster of
+// `num_rows x num_cols` pixels: the input/derived 2D layers (DSM,
+// vegetation, bush areas), the per-annulus accumulation arrays, and --
+// dominating everything else for any non-trivial raster -- the per-patch
+// shadow volumes (`bldg_sh_matrix`/`veg_sh_matrix`/
+// `veg_blocks_bldg_sh_matrix`), each `num_rows x num_cols x total_patches`.
+fn estimate_svf_memory_breakdown(
+    num_rows: usize,
+    num_cols: usize,
+    patch_option: u8,
+    _usevegdem: bool,
+) -> SvfMemoryEstimate {
+    let pixels = num_rows.saturating_mul(num_cols);
+    let total_patches = create_patches(patch_option).len();
+    let plane_bytes = pixels.saturating_mul(FLOAT_BYTES);
+    let volume_bytes = pixels
+        .saturating_mul(total_patches)
+        .saturating_mul(FLOAT_BYTES);
+
+    let mut buffers = vec![
+        MemoryBufferEstimate {
+            name: "dsm".to_string(),
+            bytes: plane_bytes,
+        },
+        MemoryBufferEstimate {
+            name: "vegdem".to_string(),
+            bytes: plane_bytes,
+        },
+        MemoryBufferEstimate {
+            name: "vegdem2".to_string(),
+            bytes: plane_bytes,
+        },
+        MemoryBufferEstimate {
+            name: "bush_areas".to_string(),
+            bytes: plane_bytes,
+        },
+        MemoryBufferEstimate {
+            name: "bldg_sh_matrix".to_string(),
+            bytes: volume_bytes,
+        },
+    ];
+
+    // `calculate_svf_core` allocates all 15 accumulators (svf, svf_n/e/s/w
+    // and the 10 vegetation-transmissivity ones) and all three per-patch
+    // shadow volumes whether or not `usevegdem` is set.
+    buffers.push(MemoryBufferEstimate {
+        name: "svf_accumulators".to_string(),
+        bytes: plane_bytes.saturating_mul(15),
+    });
+    buffers.push(MemoryBufferEstimate {
+        name: "veg_sh_matrix".to_string(),
+        bytes: volume_bytes,
+    });
+    buffers.push(MemoryBufferEstimate {
+        name: "veg_blocks_bldg_sh_matrix".to_string(),
+        bytes: volume_bytes,
+    });
+
+    let total_bytes = buffers.iter().map(|b| b.bytes).sum();
+    SvfMemoryEstimate {
+        buffers,
+        total_bytes,
+    }
+}
+
+/// Estimate `calculate_svf`'s peak memory use for a raster of
+/// `num_rows x num_cols` pixels, broken down by buffer name. Useful for
+/// sizing a machine or a `max_memory_bytes` tiling budget (see
+/// `TiledSkyviewRunner::calculate_svf_budgeted`) before running a job.
+#[pyfunction]
+#[pyo3(signature = (num_rows, num_cols, usevegdem, patch_option=None))]
+pub fn estimate_svf_memory_bytes(
+    num_rows: usize,
+    num_cols: usize,
+    usevegdem: bool,
+    patch_option: Option<u8>,
+) -> SvfMemoryEstimate {
+    estimate_svf_memory_breakdown(num_rows, num_cols, patch_option.unwrap_or(2), usevegdem)
+}
+
+// --- Tiled, out-of-core driver ---
+
+// Copy the interior (halo-trimmed) region of a tile's output array into its
+// place in the full-resolution output array.
+fn stitch_tile(
+    dst: &mut Array2<Float>,
+    src: &Array2<Float>,
+    dst_rows: std::ops::Range<usize>,
+    dst_cols: std::ops::Range<usize>,
+    interior_rows: std::ops::Range<usize>,
+    interior_cols: std::ops::Range<usize>,
+) {
+    dst.slice_mut(ndarray::s![dst_rows, dst_cols])
+        .assign(&src.slice(ndarray::s![interior_rows, interior_cols]));
+}
+
+// Append the interior region of a tile's per-patch shadow matrix to `path` as
+// raw native-endian `Float` values, in (row, col, patch) order. Used instead
+// of materializing the full-resolution `num_rows x num_cols x total_patches`
+// matrices in RAM when `calculate_svf_tiled` is asked to keep them at all.
+fn stream_tile_matrix_to_disk(
+    path: &std::path::Path,
+    src: &Array3<Float>,
+    interior_rows: std::ops::Range<usize>,
+    interior_cols: std::ops::Range<usize>,
+) -> std::io::Result<()> {
+    let mut file = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?;
+    for row in interior_rows {
+        for col in interior_cols.clone() {
+            for patch_idx in 0..src.shape()[2] {
+                file.write_all(&src[[row, col, patch_idx]].to_ne_bytes())?;
+            }
+        }
+    }
+    Ok(())
+}
+
+// Halo margin (in pixels) large enough that a shadow cast from a
+// neighbouring tile can still reach into this tile's interior, clamped to
+// the raster's own extent. `halo_override`, when given, pins an explicit
+// pixel count instead of deriving one from `max_shadow_length / scale`.
+fn compute_halo_px(
+    scale: Float,
+    max_shadow_length: Float,
+    halo_override: Option<usize>,
+    num_rows: usize,
+    num_cols: usize,
+) -> usize {
+    halo_override
+        .unwrap_or_else(|| {
+            if scale.is_finite() && scale > 0.0 {
+                (max_shadow_length / scale).ceil() as usize
+            } else {
+                0
+            }
+        })
+        .min(num_rows.max(num_cols))
+}
+
+// Pick the largest square tile edge length (excluding the halo) whose
+// estimated padded-tile memory (see `estimate_svf_memory_breakdown`) still
+// fits under `max_memory_bytes`, via exponential search for an upper bound
+// followed by binary search. Returns 1 if even a single-pixel tile (plus
+// halo) would not fit, leaving it to the caller to raise its budget.
+fn pick_tile_size_for_budget(
+    halo: usize,
+    patch_option: u8,
+    usevegdem: bool,
+    max_memory_bytes: usize,
+) -> usize {
+    let fits = |tile_size: usize| -> bool {
+        let padded = tile_size.saturating_add(2 * halo);
+        estimate_svf_memory_breakdown(padded, padded, patch_option, usevegdem).total_bytes
+            <= max_memory_bytes
+    };
+
+    if !fits(1) {
+        return 1;
+    }
+
+    let mut lo = 1usize;
+    let mut hi = 2usize;
+    // Cap the search at 2^20 pixels/edge so a pathologically generous
+    // budget can't spin forever; that already covers any raster this crate
+    // could plausibly be asked to tile.
+    while hi < (1 << 20) && fits(hi) {
+        lo = hi;
+        hi = hi.saturating_mul(2);
+    }
+
+    while lo + 1 < hi {
+        let mid = lo + (hi - lo) / 2;
+        if fits(mid) {
+            lo = mid;
+        } else {
+            hi = mid;
+        }
+    }
+    lo
+}
+
+// Shared implementation behind `calculate_svf_tiled` and
+// `TiledSkyviewRunner::calculate_svf`. Partitions the DSM into
+// `tile_size x tile_size` tiles plus a halo margin sized from
+// `max_shadow_length / scale` (so shadows cast across a tile boundary are
+// still captured, unless `halo_override` pins an explicit pixel count),
+// runs the existing per-patch pipeline on each padded tile, and stitches
+// the interior region of each tile's SVF output back into the
+// full-resolution arrays. The per-patch working memory stays bounded by
+// tile size (plus halo) regardless of the raster's full extent; the input
+// rasters and the 15 stitched output arrays are full-resolution.
+//
+// The per-patch shadow matrices (`bldg_sh_matrix`/`veg_sh_matrix`/
+// `veg_blocks_bldg_sh_matrix`) are the dominant cost for large rasters, so
+// unless `stream_shadow_matrices_dir` is given they are omitted from the
+// result entirely (returned as empty arrays); when a directory is given,
+// each tile's interior shadow maps are appended to a `.bin` file per matrix
+// in that directory as raw `Float` values rather than kept in memory.
+//
+// `progress_counter`, when given, is incremented once per finished tile
+// (not per-patch, since each tile already runs the full per-patch pipeline
+// internally). `on_block`, when given, is called after each tile with its
+// full-resolution `(row0, col0, row1, col1)` interior bounds so a caller can
+// stitch its own mosaic (e.g. writing tiles straight to a memory-mapped
+// output) alongside the one this function already stitches internally.
+#[allow(clippy::too_many_arguments)]
+fn calculate_svf_tiled_impl(
+    py: Python,
+    dsm_py: &PyReadonlyArray2<Float>,
+    vegdem_py: &PyReadonlyArray2<Float>,
+    vegdem2_py: &PyReadonlyArray2<Float>,
+    scale: Float,
+    usevegdem: bool,
+    patch_option: u8,
+    min_sun_elev_deg: Float,
+    max_shadow_length: Float,
+    tile_size: usize,
+    halo_override: Option<usize>,
+    stream_shadow_matrices_dir: Option<&str>,
+    progress_counter: Option<Arc<AtomicUsize>>,
+    on_block: Option<&PyObject>,
+) -> PyResult<SvfIntermediate> {
+    let tile_size = tile_size.max(1);
+
+    let dsm_f = dsm_py.as_array();
+    let vegdem_f = vegdem_py.as_array();
+    let vegdem2_f = vegdem2_py.as_array();
+
+    let num_rows = dsm_f.nrows();
+    let num_cols = dsm_f.ncols();
+
+    // Halo margin (in pixels) large enough that a shadow cast from a
+    // neighbouring tile can still reach into this tile's interior.
+    let halo = compute_halo_px(scale, max_shadow_length, halo_override, num_rows, num_cols);
+
+    if let Some(dir) = stream_shadow_matrices_dir {
+        fs::create_dir_all(dir)?;
+    }
+
+    let zero_array = || Array2::<Float>::zeros((num_rows, num_cols));
+    let mut svf = zero_array();
+    let mut svf_n = zero_array();
+    let mut svf_e = zero_array();
+    let mut svf_s = zero_array();
+    let mut svf_w = zero_array();
+    let mut svf_veg = zero_array();
+    let mut svf_veg_n = zero_array();
+    let mut svf_veg_e = zero_array();
+    let mut svf_veg_s = zero_array();
+    let mut svf_veg_w = zero_array();
+    let mut svf_veg_blocks_bldg_sh = zero_array();
+    let mut svf_veg_blocks_bldg_sh_n = zero_array();
+    let mut svf_veg_blocks_bldg_sh_e = zero_array();
+    let mut svf_veg_blocks_bldg_sh_s = zero_array();
+    let mut svf_veg_blocks_bldg_sh_w = zero_array();
+
+    let mut row_start = 0usize;
+    while row_start < num_rows {
+        let row_end = (row_start + tile_size).min(num_rows);
+        let r0 = row_start.saturating_sub(halo);
+        let r1 = (row_end + halo).min(num_rows);
+
+        let mut col_start = 0usize;
+        while col_start < num_cols {
+            let col_end = (col_start + tile_size).min(num_cols);
+            let c0 = col_start.saturating_sub(halo);
+            let c1 = (col_end + halo).min(num_cols);
+
+            let dsm_tile = dsm_f.slice(ndarray::s![r0..r1, c0..c1]);
+            let vegdem_tile = vegdem_f.slice(ndarray::s![r0..r1, c0..c1]);
+            let vegdem2_tile = vegdem2_f.slice(ndarray::s![r0..r1, c0..c1]);
+
+            let tile_inter = calculate_svf_core(
+                dsm_tile,
+                vegdem_tile,
+                vegdem2_tile,
+                scale,
+                usevegdem,
+                patch_option,
+                Some(min_sun_elev_deg),
+                Some(max_shadow_length),
+                None,
+                None,
+                SvfBackend::Cpu,
+            )?;
+
+            let interior_rows = (row_start - r0)..(row_end - r0);
+            let interior_cols = (col_start - c0)..(col_end - c0);
+            let dst_rows = row_start..row_end;
+            let dst_cols = col_start..col_end;
+
+            stitch_tile(
+                &mut svf,
+                &tile_inter.svf,
+                dst_rows.clone(),
+                dst_cols.clone(),
+                interior_rows.clone(),
+                interior_cols.clone(),
+            );
+            stitch_tile(
+                &mut svf_n,
+                &tile_inter.svf_n,
+                dst_rows.clone(),
+                dst_cols.clone(),
+                interior_rows.clone(),
+                interior_cols.clone(),
+            );
+            stitch_tile(
+                &mut svf_e,
+                &tile_inter.svf_e,
+                dst_rows.clone(),
+                dst_cols.clone(),
+                interior_rows.clone(),
+                interior_cols.clone(),
+            );
+            stitch_tile(
+                &mut svf_s,
+                &tile_inter.svf_s,
+                dst_rows.clone(),
+                dst_cols.clone(),
+                interior_rows.clone(),
+                interior_cols.clone(),
+            );
+            stitch_tile(
+                &mut svf_w,
+                &tile_inter.svf_w,
+                dst_rows.clone(),
+                dst_cols.clone(),
+                interior_rows.clone(),
+                interior_cols.clone(),
+            );
+            if usevegdem {
+                stitch_tile(
+                    &mut svf_veg,
+                    &tile_inter.svf_veg,
+                    dst_rows.clone(),
+                    dst_cols.clone(),
+                    interior_rows.clone(),
+                    interior_cols.clone(),
+                );
+                stitch_tile(
+                    &mut svf_veg_n,
+                    &tile_inter.svf_veg_n,
+                    dst_rows.clone(),
+                    dst_cols.clone(),
+                    interior_rows.clone(),
+                    interior_cols.clone(),
+                );
+                stitch_tile(
+                    &mut svf_veg_e,
+                    &tile_inter.svf_veg_e,
+                    dst_rows.clone(),
+                    dst_cols.clone(),
+                    interior_rows.clone(),
+                    interior_cols.clone(),
+                );
+                stitch_tile(
+                    &mut svf_veg_s,
+                    &tile_inter.svf_veg_s,
+                    dst_rows.clone(),
+                    dst_cols.clone(),
+                    interior_rows.clone(),
+                    interior_cols.clone(),
+                );
+                stitch_tile(
+                    &mut svf_veg_w,
+                    &tile_inter.svf_veg_w,
+                    dst_rows.clone(),
+                    dst_cols.clone(),
+                    interior_rows.clone(),
+                    interior_cols.clone(),
+                );
+                stitch_tile(
+                    &mut svf_veg_blocks_bldg_sh,
+                    &tile_inter.svf_veg_blocks_bldg_sh,
+                    dst_rows.clone(),
+                    dst_cols.clone(),
+                    interior_rows.clone(),
+                    interior_cols.clone(),
+                );
+                stitch_tile(
+                    &mut svf_veg_blocks_bldg_sh_n,
+                    &tile_inter.svf_veg_blocks_bldg_sh_n,
+                    dst_rows.clone(),
+                    dst_cols.clone(),
+                    interior_rows.clone(),
+                    interior_cols.clone(),
+                );
+                stitch_tile(
+                    &mut svf_veg_blocks_bldg_sh_e,
+                    &tile_inter.svf_veg_blocks_bldg_sh_e,
+                    dst_rows.clone(),
+                    dst_cols.clone(),
+                    interior_rows.clone(),
+                    interior_cols.clone(),
+                );
+                stitch_tile(
+                    &mut svf_veg_blocks_bldg_sh_s,
+                    &tile_inter.svf_veg_blocks_bldg_sh_s,
+                    dst_rows.clone(),
+                    dst_cols.clone(),
+                    interior_rows.clone(),
+                    interior_cols.clone(),
+                );
+                stitch_tile(
+                    &mut svf_veg_blocks_bldg_sh_w,
+                    &tile_inter.svf_veg_blocks_bldg_sh_w,
+                    dst_rows.clone(),
+                    dst_cols.clone(),
+                    interior_rows.clone(),
+                    interior_cols.clone(),
+                );
+            }
+
+            if let Some(dir) = stream_shadow_matrices_dir {
+                let base = std::path::Path::new(dir);
+                stream_tile_matrix_to_disk(
+                    &base.join("bldg_sh_matrix.bin"),
+                    &tile_inter.bldg_sh_matrix,
+                    interior_rows.clone(),
+                    interior_cols.clone(),
+                )?;
+                if usevegdem {
+                    stream_tile_matrix_to_disk(
+                        &base.join("veg_sh_matrix.bin"),
+                        &tile_inter.veg_sh_matrix,
+                        interior_rows.clone(),
+                        interior_cols.clone(),
+                    )?;
+                    stream_tile_matrix_to_disk(
+                        &base.join("veg_blocks_bldg_sh_matrix.bin"),
+                        &tile_inter.veg_blocks_bldg_sh_matrix,
+                        interior_rows.clone(),
+                        interior_cols.clone(),
+                    )?;
+                }
+            }
+
+            if let Some(cb) = on_block {
+                cb.call1(py, (row_start, col_start, row_end, col_end))?;
+            }
+            if let Some(ref counter) = progress_counter {
+                counter.fetch_add(1, Ordering::SeqCst);
+            }
+
+            col_start += tile_size;
+        }
+        row_start += tile_size;
+    }
+
+    Ok(SvfIntermediate {
+        svf,
+        svf_n,
+        svf_e,
+        svf_s,
+        svf_w,
+        svf_veg,
+        svf_veg_n,
+        svf_veg_e,
+        svf_veg_s,
+        svf_veg_w,
+        svf_veg_blocks_bldg_sh,
+        svf_veg_blocks_bldg_sh_n,
+        svf_veg_blocks_bldg_sh_e,
+        svf_veg_blocks_bldg_sh_s,
+        svf_veg_blocks_bldg_sh_w,
+        // Omitted: materializing these at full-raster resolution is exactly
+        // what tiling avoids. Use `stream_shadow_matrices_dir` to retain them
+        // on disk instead.
+        bldg_sh_matrix: Array3::zeros((0, 0, 0)),
+        veg_sh_matrix: Array3::zeros((0, 0, 0)),
+        veg_blocks_bldg_sh_matrix: Array3::zeros((0, 0, 0)),
+    })
+}
+
+// Tiled, out-of-core variant of `calculate_svf`. See `calculate_svf_tiled_impl`
+// for the tiling/halo/memory-bound behavior this wraps.
+#[pyfunction]
+#[pyo3(signature = (dsm_py, vegdem_py, vegdem2_py, scale, usevegdem, patch_option=None, min_sun_elev_deg=None, max_shadow_length=None, tile_size=None, stream_shadow_matrices_dir=None))]
+#[allow(clippy::too_many_arguments)]
+pub fn calculate_svf_tiled(
+    py: Python,
+    dsm_py: PyReadonlyArray2<Float>,
+    vegdem_py: PyReadonlyArray2<Float>,
+    vegdem2_py: PyReadonlyArray2<Float>,
+    scale: Float,
+    usevegdem: bool,
+    patch_option: Option<u8>,
+    min_sun_elev_deg: Option<Float>,
+    max_shadow_length: Option<Float>,
+    tile_size: Option<usize>,
+    stream_shadow_matrices_dir: Option<&str>,
+) -> PyResult<Py<SvfResult>> {
+    let inter = calculate_svf_tiled_impl(
+        py,
+        &dsm_py,
+        &vegdem_py,
+        &vegdem2_py,
+        scale,
+        usevegdem,
+        patch_option.unwrap_or(2),
+        min_sun_elev_deg.unwrap_or(6.0),
+        max_shadow_length.unwrap_or(1000.0),
+        tile_size.unwrap_or(512),
+        None,
+        stream_shadow_matrices_dir,
+        None,
+        None,
+    )?;
+    svf_intermediate_to_py(py, inter)
+}
+
+// Tiled counterpart to `SkyviewRunner`: bounds the per-patch working memory
+// to one block plus halo regardless of raster extent (see `calculate_svf_tiled_impl`), while
+// still exposing a `progress()` method — incremented once per finished
+// block rather than per-patch.
+#[pyclass]
+pub struct TiledSkyviewRunner {
+    progress: Arc<AtomicUsize>,
+}
+
+#[pymethods]
+impl TiledSkyviewRunner {
+    #[new]
+    pub fn new() -> Self {
+        Self {
+            progress: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+
+    pub fn progress(&self) -> usize {
+        self.progress.load(Ordering::SeqCst)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    #[pyo3(signature = (dsm_py, vegdem_py, vegdem2_py, scale, usevegdem, patch_option=None, min_sun_elev_deg=None, max_shadow_length=None, tile_size=None, halo_px=None, stream_shadow_matrices_dir=None, on_block=None))]
+    pub fn calculate_svf(
+        &self,
+        py: Python,
+        dsm_py: PyReadonlyArray2<Float>,
+        vegdem_py: PyReadonlyArray2<Float>,
+        vegdem2_py: PyReadonlyArray2<Float>,
+        scale: Float,
+        usevegdem: bool,
+        patch_option: Option<u8>,
+        min_sun_elev_deg: Option<Float>,
+        max_shadow_length: Option<Float>,
+        tile_size: Option<usize>,
+        halo_px: Option<usize>,
+        stream_shadow_matrices_dir: Option<&str>,
+        on_block: Option<PyObject>,
+    ) -> PyResult<Py<SvfResult>> {
+        self.progress.store(0, Ordering::SeqCst);
+        let inter = calculate_svf_tiled_impl(
+            py,
+            &dsm_py,
+            &vegdem_py,
+            &vegdem2_py,
+            scale,
+            usevegdem,
+            patch_option.unwrap_or(2),
+            min_sun_elev_deg.unwrap_or(6.0),
+            max_shadow_length.unwrap_or(1000.0),
+            tile_size.unwrap_or(512),
+            halo_px,
+            stream_shadow_matrices_dir,
+            Some(self.progress.clone()),
+            on_block.as_ref(),
+        )?;
+        svf_intermediate_to_py(py, inter)
+    }
+
+    /// Like `calculate_svf`, but picks `tile_size` automatically: the
+    /// largest block whose estimated memory footprint (block + halo +
+    /// intermediates, see `estimate_svf_memory_bytes`) fits under
+    /// `max_memory_bytes`, instead of the caller tuning `tile_size` by
+    /// trial and error.
+    #[allow(clippy::too_many_arguments)]
+    #[pyo3(signature = (dsm_py, vegdem_py, vegdem2_py, scale, usevegdem, max_memory_bytes, patch_option=None, min_sun_elev_deg=None, max_shadow_length=None, halo_px=None, stream_shadow_matrices_dir=None, on_block=None))]
+    pub fn calculate_svf_budgeted(
+        &self,
+        py: Python,
+        dsm_py: PyReadonlyArray2<Float>,
+        vegdem_py: PyReadonlyArray2<Float>,
+        vegdem2_py: PyReadonlyArray2<Float>,
+        scale: Float,
+        usevegdem: bool,
+        max_memory_bytes: usize,
+        patch_option: Option<u8>,
+        min_sun_elev_deg: Option<Float>,
+        max_shadow_length: Option<Float>,
+        halo_px: Option<usize>,
+        stream_shadow_matrices_dir: Option<&str>,
+        on_block: Option<PyObject>,
+    ) -> PyResult<Py<SvfResult>> {
+        let patch_option = patch_option.unwrap_or(2);
+        let max_shadow_length = max_shadow_length.unwrap_or(1000.0);
+        let (num_rows, num_cols) = {
+            let dsm_f = dsm_py.as_array();
+            (dsm_f.nrows(), dsm_f.ncols())
+        };
+        let halo = compute_halo_px(scale, max_shadow_length, halo_px, num_rows, num_cols);
+        let tile_size = pick_tile_size_for_budget(halo, patch_option, usevegdem, max_memory_bytes);
+
+        self.progress.store(0, Ordering::SeqCst);
+        let inter = calculate_svf_tiled_impl(
+            py,
+            &dsm_py,
+            &vegdem_py,
+            &vegdem2_py,
+            scale,
+            usevegdem,
+            patch_option,
+            min_sun_elev_deg.unwrap_or(6.0),
+            max_shadow_length,
+            tile_size,
+            Some(halo),
+            stream_shadow_matrices_dir,
+            Some(self.progress.clone()),
+            on_block.as_ref(),
+        )?;
+        svf_intermediate_to_py(py, inter)
+    }
+}
+
+impl Default for TiledSkyviewRunner {
+    fn default() -> Self {
+        Self::new()
+    }
 }